@@ -0,0 +1,272 @@
+#!/usr/bin/env python3
+# 由 GNU Unifont 的 unifont.hex 生成内置的中日文后备字体 fallback_cjk.ttf
+#
+# 用法: python3 make_fallback_font.py [--full] <unifont.hex> [界面文本文件...]
+#
+# 收录假名、中日文标点和全角字符，以及命令行中给出的文件（如 src/locales/*.ftl）里出现的所有中日韩文字符，
+# 生成的字体约260KB；指定 --full 时再收录 GB2312 全部汉字和 JIS X 0208 第一水准汉字（约2.6MB），
+# 用于显示更新说明等界面文本以外的文字。
+# 每个像素转换为方形轮廓，同一行相邻的像素合并为矩形，上下相同的矩形再合并。
+
+import struct
+import sys
+
+UNITS_PER_PIXEL = 64
+UNITS_PER_EM = 16 * UNITS_PER_PIXEL
+# Unifont 字形高16像素，基线以下2像素
+ASCENT = 14 * UNITS_PER_PIXEL
+DESCENT = 2 * UNITS_PER_PIXEL
+
+FAMILY = "Software Updater Fallback CJK"
+COPYRIGHT = "Glyphs from GNU Unifont, Copyright (C) 1998-2023 Roman Czyborra, Paul Hardy, and contributors"
+LICENSE = ("Dual licensed under the SIL Open Font License 1.1 and the GNU GPL version 2 or later "
+           "with the GNU font embedding exception")
+LICENSE_URL = "https://openfontlicense.org/"
+
+RANGES = [
+    (0x3000, 0x303F),  # 中日文标点
+    (0x3040, 0x309F),  # 平假名
+    (0x30A0, 0x30FF),  # 片假名
+    (0x31F0, 0x31FF),  # 片假名扩展
+    (0xFF00, 0xFFEF),  # 全角和半角字符
+]
+
+
+def codec_chars(encoding, lead_bytes):
+    chars = set()
+    for lead in lead_bytes:
+        for trail in range(0xA1, 0xFF):
+            try:
+                chars.add(bytes([lead, trail]).decode(encoding))
+            except UnicodeDecodeError:
+                pass
+    return chars
+
+
+def wanted_chars(extra_files, full):
+    chars = set()
+    for start, end in RANGES:
+        chars.update(chr(c) for c in range(start, end + 1))
+    # GB2312 汉字位于第16至87区，JIS X 0208 第一水准汉字位于第16至47区
+    if full:
+        chars |= codec_chars("gb2312", range(0xB0, 0xF8))
+        chars |= codec_chars("euc_jp", range(0xB0, 0xD0))
+    for path in extra_files:
+        with open(path, encoding="utf-8") as f:
+            chars.update(c for c in f.read() if ord(c) >= 0x2E80)
+    return {ord(c) for c in chars if ord(c) <= 0xFFFF}
+
+
+def load_hex(path, codepoints):
+    glyphs = {}
+    with open(path) as f:
+        for line in f:
+            code, data = line.strip().split(":")
+            code = int(code, 16)
+            if code in codepoints:
+                width = len(data) // 4
+                rows = [int(data[i:i + width // 4], 16) for i in range(0, len(data), width // 4)]
+                glyphs[code] = (width, rows)
+    return glyphs
+
+
+def rectangles(width, rows):
+    # 先求出每行的连续像素段，再把上下相邻且范围相同的段合并
+    finished = []
+    open_runs = {}
+    for y, bits in enumerate(rows):
+        runs = []
+        x = 0
+        while x < width:
+            if bits >> (width - 1 - x) & 1:
+                start = x
+                while x < width and bits >> (width - 1 - x) & 1:
+                    x += 1
+                runs.append((start, x))
+            else:
+                x += 1
+        next_open = {}
+        for run in runs:
+            top = open_runs.pop(run, y)
+            next_open[run] = top
+        for (x0, x1), top in open_runs.items():
+            finished.append((x0, x1, top, y))
+        open_runs = next_open
+    for (x0, x1), top in open_runs.items():
+        finished.append((x0, x1, top, len(rows)))
+    return finished
+
+
+def encode_glyph(width, rows):
+    rects = rectangles(width, rows)
+    if not rects:
+        return b"", 0, 0, (0, 0, 0, 0)
+    contours = []
+    for x0, x1, top, bottom in rects:
+        # 像素坐标向下增长，转换为基线为0、向上增长的字体坐标；外轮廓按顺时针方向
+        left, right = x0 * UNITS_PER_PIXEL, x1 * UNITS_PER_PIXEL
+        high, low = ASCENT - top * UNITS_PER_PIXEL, ASCENT - bottom * UNITS_PER_PIXEL
+        contours.append([(left, low), (left, high), (right, high), (right, low)])
+    points = [p for contour in contours for p in contour]
+    xs = [p[0] for p in points]
+    ys = [p[1] for p in points]
+    bbox = (min(xs), min(ys), max(xs), max(ys))
+
+    flags = bytearray()
+    x_data = bytearray()
+    y_data = bytearray()
+    last_x = last_y = 0
+    for x, y in points:
+        flag = 0x01
+        dx, dy = x - last_x, y - last_y
+        last_x, last_y = x, y
+        if dx == 0:
+            flag |= 0x10
+        elif -255 <= dx <= 255:
+            flag |= 0x02 | (0x10 if dx > 0 else 0)
+            x_data.append(abs(dx))
+        else:
+            x_data += struct.pack(">h", dx)
+        if dy == 0:
+            flag |= 0x20
+        elif -255 <= dy <= 255:
+            flag |= 0x04 | (0x20 if dy > 0 else 0)
+            y_data.append(abs(dy))
+        else:
+            y_data += struct.pack(">h", dy)
+        flags.append(flag)
+
+    data = struct.pack(">hhhhh", len(contours), *bbox)
+    end = -1
+    for contour in contours:
+        end += len(contour)
+        data += struct.pack(">H", end)
+    data += struct.pack(">H", 0) + bytes(flags) + bytes(x_data) + bytes(y_data)
+    if len(data) % 2:
+        data += b"\0"
+    return data, len(points), len(contours), bbox
+
+
+def cmap_table(codes):
+    # 字形编号按码位顺序分配，连续码位合并为一个段并使用 idDelta 映射
+    segments = []
+    for index, code in enumerate(codes, start=1):
+        if segments and segments[-1][1] == code - 1:
+            segments[-1][1] = code
+        else:
+            segments.append([code, code, index])
+    segments.append([0xFFFF, 0xFFFF, 0])
+    count = len(segments)
+    search_range = 2 ** (count.bit_length() - 1) * 2
+    entry_selector = count.bit_length() - 1
+    range_shift = count * 2 - search_range
+    ends = b"".join(struct.pack(">H", end) for _, end, _ in segments)
+    starts = b"".join(struct.pack(">H", start) for start, _, _ in segments)
+    deltas = b"".join(struct.pack(">H", (gid - start) % 0x10000 if gid else 1) for start, _, gid in segments)
+    offsets = b"\0\0" * count
+    body = ends + b"\0\0" + starts + deltas + offsets
+    subtable = struct.pack(">HHHHHHH", 4, 14 + len(body), 0, count * 2, search_range, entry_selector, range_shift) + body
+    return struct.pack(">HHHHI", 0, 1, 3, 1, 12) + subtable
+
+
+def name_table():
+    names = {
+        0: COPYRIGHT,
+        1: FAMILY,
+        2: "Regular",
+        3: FAMILY + " Regular",
+        4: FAMILY,
+        5: "Version 1.0",
+        6: FAMILY.replace(" ", ""),
+        13: LICENSE,
+        14: LICENSE_URL,
+    }
+    records = b""
+    strings = b""
+    for name_id, text in names.items():
+        encoded = text.encode("utf-16-be")
+        records += struct.pack(">HHHHHH", 3, 1, 0x409, name_id, len(encoded), len(strings))
+        strings += encoded
+    return struct.pack(">HHH", 0, len(names), 6 + len(records)) + records + strings
+
+
+def checksum(data):
+    data += b"\0" * (-len(data) % 4)
+    return sum(struct.unpack(">%dI" % (len(data) // 4), data)) & 0xFFFFFFFF
+
+
+def build_font(glyphs):
+    codes = sorted(glyphs)
+    glyf = bytearray()
+    loca = [0, 0]  # .notdef 没有轮廓
+    hmtx = bytearray(struct.pack(">Hh", UNITS_PER_EM // 2, 0))  # .notdef
+    max_points = max_contours = 0
+    x_min = y_min = 0x7FFF
+    x_max = y_max = -0x8000
+    min_rsb = 0x7FFF
+    for code in codes:
+        width, rows = glyphs[code]
+        data, points, contours, bbox = encode_glyph(width, rows)
+        advance = width * UNITS_PER_PIXEL
+        glyf += data
+        loca.append(len(glyf))
+        hmtx += struct.pack(">Hh", advance, bbox[0])
+        max_points = max(max_points, points)
+        max_contours = max(max_contours, contours)
+        if data:
+            x_min, y_min = min(x_min, bbox[0]), min(y_min, bbox[1])
+            x_max, y_max = max(x_max, bbox[2]), max(y_max, bbox[3])
+            min_rsb = min(min_rsb, advance - bbox[2])
+    num_glyphs = len(codes) + 1
+
+    tables = {
+        b"cmap": cmap_table(codes),
+        b"glyf": bytes(glyf),
+        b"head": struct.pack(">IIIIHHqqhhhhHHhhh", 0x00010000, 0x00010000, 0, 0x5F0F3CF5, 0x000B, UNITS_PER_EM,
+                             0, 0, x_min, y_min, x_max, y_max, 0, 8, 2, 1, 0),
+        b"hhea": struct.pack(">IhhhHhhhhhhhhhhhH", 0x00010000, ASCENT, -DESCENT, 0, UNITS_PER_EM, 0, min_rsb, x_max,
+                             1, 0, 0, 0, 0, 0, 0, 0, num_glyphs),
+        b"hmtx": bytes(hmtx),
+        b"loca": b"".join(struct.pack(">I", offset) for offset in loca),
+        b"maxp": struct.pack(">IHHHHHHHHHHHHHH", 0x00010000, num_glyphs, max_points, max_contours, 0, 0, 2,
+                             0, 0, 0, 0, 0, 0, 0, 0),
+        b"name": name_table(),
+        b"post": struct.pack(">IIhhIIIII", 0x00030000, 0, -UNITS_PER_PIXEL * 2, UNITS_PER_PIXEL, 0, 0, 0, 0, 0),
+    }
+
+    count = len(tables)
+    search_range = 2 ** (count.bit_length() - 1) * 16
+    header = struct.pack(">IHHHH", 0x00010000, count, search_range, count.bit_length() - 1, count * 16 - search_range)
+    offset = len(header) + 16 * count
+    directory = b""
+    body = b""
+    head_offset = 0
+    for tag in sorted(tables):
+        data = tables[tag]
+        if tag == b"head":
+            head_offset = offset + len(body)
+        directory += struct.pack(">4sIII", tag, checksum(data), offset + len(body), len(data))
+        body += data + b"\0" * (-len(data) % 4)
+    font = bytearray(header + directory + body)
+
+    # 写入 head 表中的整体校验值
+    adjustment = (0xB1B0AFBA - checksum(bytes(font))) & 0xFFFFFFFF
+    font[head_offset + 8:head_offset + 12] = struct.pack(">I", adjustment)
+    return bytes(font)
+
+
+def main():
+    args = sys.argv[1:]
+    full = "--full" in args
+    args = [arg for arg in args if arg != "--full"]
+    if not args:
+        sys.exit("用法: make_fallback_font.py [--full] <unifont.hex> [界面文本文件...]")
+    glyphs = load_hex(args[0], wanted_chars(args[1:], full))
+    font = build_font(glyphs)
+    with open("fallback_cjk.ttf", "wb") as f:
+        f.write(font)
+    print("已生成 fallback_cjk.ttf: %d 个字形, %d 字节" % (len(glyphs), len(font)))
+
+
+if __name__ == "__main__":
+    main()
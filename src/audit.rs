@@ -0,0 +1,89 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use csv::Writer;
+
+use crate::long_path;
+
+// 每写入多少行刷新一次到磁盘，程序崩溃时最多丢失这么多行
+const FLUSH_INTERVAL: usize = 20;
+
+// 审计记录中的操作类型
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Action {
+    Copy,
+    // 断点中已完成或用户选择不覆盖的文件
+    Skip,
+    // 文件被占用，写入暂存文件并登记为重启时替换（仅Windows）
+    #[cfg(windows)]
+    Stage,
+    // 与更新包中已复制的文件是同一个inode，在目标目录中创建硬链接（仅Unix）
+    #[cfg(unix)]
+    Link,
+    Delete,
+    Rollback,
+}
+
+impl Action {
+    fn as_str(self) -> &'static str {
+        match self {
+            Action::Copy => "copy",
+            Action::Skip => "skip",
+            #[cfg(windows)]
+            Action::Stage => "stage",
+            #[cfg(unix)]
+            Action::Link => "link",
+            Action::Delete => "delete",
+            Action::Rollback => "rollback",
+        }
+    }
+}
+
+struct State {
+    writer: Writer<fs::File>,
+    rows: usize,
+}
+
+// 逐个文件操作的CSV审计记录：每个操作一行（时间、操作、源路径、目标路径、字节数、结果），未指定路径时不记录；
+// 剩余的记录在更新结束后丢弃时写入
+pub(crate) struct Audit {
+    state: Option<Mutex<State>>,
+}
+
+impl Audit {
+    // 创建或覆盖审计文件并写入表头
+    pub fn open(path: Option<&str>) -> io::Result<Self> {
+        let Some(path) = path else {
+            return Ok(Self { state: None });
+        };
+        let mut writer = Writer::from_path(path)?;
+        writer.write_record(["timestamp", "action", "source", "destination", "bytes", "result"])?;
+        writer.flush()?;
+        log::info!("审计记录写入: {}", path);
+        Ok(Self { state: Some(Mutex::new(State { writer, rows: 0 })) })
+    }
+
+    // 记录一个操作，result 为错误时写入错误信息；写入失败只记录日志，不中止更新
+    pub fn record(&self, action: Action, source: Option<&Path>, dest: &Path, bytes: u64, result: Result<(), &io::Error>) {
+        let Some(state) = &self.state else {
+            return;
+        };
+        let mut state = state.lock().unwrap();
+        let timestamp = humantime::format_rfc3339_millis(SystemTime::now()).to_string();
+        let source = source.map(long_path::display).unwrap_or_default();
+        let result = match result {
+            Ok(()) => "ok".to_string(),
+            Err(e) => e.to_string(),
+        };
+        let row = [timestamp, action.as_str().to_string(), source, long_path::display(dest), bytes.to_string(), result];
+        state.rows += 1;
+        let written = state.writer.write_record(&row).map_err(io::Error::from)
+            .and_then(|_| if state.rows.is_multiple_of(FLUSH_INTERVAL) { state.writer.flush() } else { Ok(()) });
+        if let Err(e) = written {
+            log::warn!("写入审计记录失败: {}", e);
+        }
+    }
+}
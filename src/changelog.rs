@@ -0,0 +1,56 @@
+use std::fs;
+
+use software_updater::read_package_file;
+
+use crate::config::Config;
+use crate::theme::Palette;
+
+// 更新包中的更新说明文件名
+const CHANGELOG_NAME: &str = "CHANGELOG.md";
+
+// 读取更新说明：优先使用 --changelog 指定的文件，其次是更新包内指定目录或根目录下的 CHANGELOG.md
+pub fn load(config: &Config) -> Option<String> {
+    if let Some(path) = &config.changelog_path {
+        return match fs::read_to_string(path) {
+            Ok(text) => Some(text),
+            Err(e) => {
+                log::warn!("无法读取更新说明 {}: {}", path, e);
+                None
+            }
+        };
+    }
+
+    let package_path = &config.update.package_path;
+    let inner_path = config.update.zip_inner_path.trim_matches('/');
+    let mut candidates = Vec::new();
+    if !inner_path.is_empty() {
+        candidates.push(format!("{}/{}", inner_path, CHANGELOG_NAME));
+    }
+    candidates.push(CHANGELOG_NAME.to_string());
+    // 与更新流程一样按 --format 或检测到的格式打开更新包，无法读取时不显示更新说明
+    let text = candidates.iter().find_map(|name| {
+        read_package_file(package_path, config.update.format, config.update.password.as_deref(), name).ok().flatten()
+    })?;
+    log::info!("已读取更新包中的更新说明");
+    Some(text)
+}
+
+// 简单渲染Markdown：支持标题和列表项，其余按普通文本显示
+pub fn show(ui: &mut egui::Ui, text: &str, palette: &Palette) {
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if let Some(heading) = trimmed.strip_prefix('#') {
+            let level = 1 + heading.chars().take_while(|c| *c == '#').count();
+            let size = match level {
+                1 => 16.0,
+                2 => 14.0,
+                _ => 13.0,
+            };
+            ui.label(egui::RichText::new(heading.trim_start_matches('#').trim()).font(egui::FontId::proportional(size)).strong().color(palette.title));
+        } else if let Some(item) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")) {
+            ui.label(egui::RichText::new(format!("  • {}", item)).font(egui::FontId::proportional(12.0)));
+        } else if !trimmed.is_empty() {
+            ui.label(egui::RichText::new(trimmed).font(egui::FontId::proportional(12.0)));
+        }
+    }
+}
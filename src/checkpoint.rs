@@ -0,0 +1,111 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+// 写入目标目录的断点文件名，清理旧文件时不会被删除
+pub const CHECKPOINT_NAME: &str = "update-checkpoint.json";
+
+// 每完成多少个文件保存一次断点，中断时最多重新复制这么多文件
+const SAVE_INTERVAL: usize = 20;
+
+// 断点对应的更新包，路径、大小或修改时间不同时视为另一个更新包，不沿用其中的记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct PackageId {
+    path: String,
+    size: u64,
+    modified: u64,
+}
+
+impl PackageId {
+    fn of(package_path: &str) -> io::Result<Self> {
+        let metadata = fs::metadata(package_path)?;
+        let modified = metadata.modified()?.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let path = fs::canonicalize(package_path).unwrap_or_else(|_| PathBuf::from(package_path));
+        Ok(Self { path: path.display().to_string(), size: metadata.len(), modified })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CheckpointFile {
+    package: PackageId,
+    // 已成功复制的文件（相对于目标目录，使用 / 分隔）
+    files: BTreeSet<String>,
+}
+
+// 断点续传记录：保存同一个更新包已成功复制到目标目录的文件，中断后重新运行时跳过这些文件
+pub(crate) struct Checkpoint {
+    path: PathBuf,
+    package: PackageId,
+    files: Mutex<BTreeSet<String>>,
+}
+
+// 统一使用 / 作为分隔符，断点文件在各系统上格式相同
+fn key(relative_path: &Path) -> String {
+    relative_path.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+}
+
+impl Checkpoint {
+    // 读取目标目录中的断点，不存在、无法读取或属于另一个更新包时从头开始
+    pub fn load(target: &Path, package_path: &str) -> io::Result<Self> {
+        let package = PackageId::of(package_path)?;
+        let path = target.join(CHECKPOINT_NAME);
+        let files = match fs::read_to_string(&path) {
+            Ok(text) => match serde_json::from_str::<CheckpointFile>(&text) {
+                Ok(file) if file.package == package => {
+                    log::info!("从断点继续，已完成 {} 个文件: {:?}", file.files.len(), path);
+                    file.files
+                },
+                Ok(_) => {
+                    log::info!("断点属于另一个更新包，从头开始: {:?}", path);
+                    BTreeSet::new()
+                },
+                Err(e) => {
+                    log::warn!("断点文件无效，从头开始: {:?}: {}", path, e);
+                    BTreeSet::new()
+                },
+            },
+            Err(_) => BTreeSet::new(),
+        };
+        Ok(Self { path, package, files: Mutex::new(files) })
+    }
+
+    // 文件是否已在上次运行中成功复制
+    pub fn is_done(&self, relative_path: &Path) -> bool {
+        self.files.lock().unwrap().contains(&key(relative_path))
+    }
+
+    // 记录成功复制的文件，每完成一定数量的文件保存一次；保存失败只影响续传，不中止更新
+    pub fn mark(&self, relative_path: &Path) {
+        let mut files = self.files.lock().unwrap();
+        if files.insert(key(relative_path)) && files.len().is_multiple_of(SAVE_INTERVAL) && let Err(e) = self.write(&files) {
+            log::warn!("保存断点失败: {:?}: {}", self.path, e);
+        }
+    }
+
+    // 立即保存断点，更新失败或中止时调用
+    pub fn save(&self) -> io::Result<()> {
+        self.write(&self.files.lock().unwrap())
+    }
+
+    // 全部文件更新成功后删除断点
+    pub fn remove(&self) -> io::Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    // 先写入临时文件再重命名，中断时不会留下不完整的断点
+    fn write(&self, files: &BTreeSet<String>) -> io::Result<()> {
+        let file = CheckpointFile { package: self.package.clone(), files: files.clone() };
+        let json = serde_json::to_string_pretty(&file).map_err(io::Error::other)?;
+        let temp_path = self.path.with_extension("json.tmp");
+        fs::write(&temp_path, json)?;
+        fs::rename(&temp_path, &self.path)
+    }
+}
@@ -0,0 +1,1009 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+use software_updater::{LockPolicy, PackageFormat, UpdateConfig};
+use software_updater::language::{LangDict, Language, detect_system_language, get_dict, parse_language};
+use software_updater::validate_glob;
+
+use crate::package_meta::{self, Rejection};
+use crate::theme::{Theme, parse_theme};
+
+// 命令行解析得到的运行配置
+#[derive(Debug, Clone)]
+pub struct Config {
+    // 传给更新核心库的配置
+    pub update: UpdateConfig,
+    pub delay_seconds: u64,
+    pub start_exe_path: Option<String>,
+    // 更新成功后自动关闭窗口前等待的秒数
+    pub auto_close: Option<u64>,
+    // 延时期间显示的更新说明文件
+    pub changelog_path: Option<String>,
+    // 延时期间显示本次更新将写入的总大小
+    pub show_size: bool,
+    // 不显示界面，以JSON行输出进度
+    pub json: bool,
+    // 不显示界面，结果只写入日志并以系统通知提示
+    pub silent: bool,
+    // 没有管理员权限时请求提升权限并重新启动（仅Windows）
+    pub elevate: bool,
+    pub show_help: bool,
+    // 不执行更新，检查运行环境后输出结果
+    pub self_test: bool,
+    // 不执行更新，列出更新包中的条目
+    pub inspect: bool,
+    pub log_path: Option<String>,
+    // 窗口大小（逻辑像素）以及是否允许调整大小
+    pub width: f32,
+    pub height: f32,
+    pub resizable: bool,
+    // 窗口是否置顶显示
+    pub topmost: bool,
+    // 界面缩放比例（每个逻辑点对应的物理像素数），None时按系统DPI自动缩放
+    pub scale: Option<f32>,
+    // 窗口是否在屏幕中央显示
+    pub center: bool,
+    pub theme: Theme,
+    // 是否使用了已弃用的位置参数
+    pub positional_args: bool,
+}
+
+impl Config {
+    // 参数无效时使用的默认配置，仍尽量沿用命令行中指定的语言
+    pub fn default_for(args: &[String]) -> Self {
+        Self {
+            update: UpdateConfig {
+                lang: requested_language(args).unwrap_or_else(detect_system_language),
+                ..UpdateConfig::default()
+            },
+            delay_seconds: 0,
+            start_exe_path: None,
+            auto_close: None,
+            changelog_path: None,
+            show_size: false,
+            json: args.iter().any(|arg| arg == "--json"),
+            silent: args.iter().any(|arg| arg == "--silent"),
+            elevate: false,
+            show_help: false,
+            self_test: false,
+            inspect: false,
+            log_path: None,
+            width: DEFAULT_WIDTH,
+            height: DEFAULT_HEIGHT,
+            resizable: false,
+            topmost: true,
+            scale: None,
+            center: true,
+            theme: Theme::Light,
+            positional_args: false,
+        }
+    }
+}
+
+// 参数解析错误，在确定界面语言后再转换为本地化字符串
+enum ArgError {
+    MissingValue(String),
+    InvalidDelay(String),
+    InvalidAutoClose(String),
+    InvalidJobs(String),
+    InvalidSize(String),
+    InvalidScale(String),
+    InvalidLockPolicy(String),
+    InvalidFormat(String),
+    InvalidRetries(String),
+    InvalidTimeout(String),
+    InvalidSuffix(String),
+    InvalidTargetSubdir(String),
+    InvalidGlob(String, String),
+    InvalidLanguage(String),
+    InvalidTheme(String),
+    InvalidConfig(String, String),
+    InvalidMap(String),
+    UnknownOption(String),
+    UnexpectedArgument(String),
+    InvalidPackageMetadata(String),
+    // 更新包中的目标路径引用了未定义的环境变量：目标路径和变量名
+    UndefinedVariable(String, String),
+    UpdaterTooOld(String),
+    InvalidVersion(String),
+    NotNewer(String, String),
+    CurrentTooOld(String, String),
+    IncompatibleOptions(&'static str, &'static str),
+    NoPackage,
+    NoTarget,
+}
+
+impl ArgError {
+    fn localize(&self, dict: &LangDict) -> String {
+        match self {
+            ArgError::MissingValue(flag) => dict.error_missing_value(flag),
+            ArgError::InvalidDelay(value) => dict.error_invalid_delay(value),
+            ArgError::InvalidAutoClose(value) => dict.error_invalid_auto_close(value),
+            ArgError::InvalidJobs(value) => dict.error_invalid_jobs(value),
+            ArgError::InvalidSize(value) => dict.error_invalid_size(value),
+            ArgError::InvalidScale(value) => dict.error_invalid_scale(value),
+            ArgError::InvalidLockPolicy(value) => dict.error_invalid_lock_policy(value),
+            ArgError::InvalidFormat(value) => dict.error_invalid_format(value),
+            ArgError::InvalidRetries(value) => dict.error_invalid_retries(value),
+            ArgError::InvalidTimeout(value) => dict.error_invalid_timeout(value),
+            ArgError::InvalidSuffix(value) => dict.error_invalid_suffix(value),
+            ArgError::InvalidTargetSubdir(value) => dict.error_invalid_target_subdir(value),
+            ArgError::InvalidGlob(pattern, reason) => dict.error_invalid_glob(pattern, reason),
+            ArgError::InvalidLanguage(value) => dict.error_invalid_language(value),
+            ArgError::InvalidTheme(value) => dict.error_invalid_theme(value),
+            ArgError::InvalidConfig(path, reason) => dict.error_invalid_config(path, reason),
+            ArgError::InvalidMap(value) => dict.error_invalid_map(value),
+            ArgError::UnknownOption(flag) => dict.error_unknown_option(flag),
+            ArgError::UnexpectedArgument(arg) => dict.error_unexpected_argument(arg),
+            ArgError::InvalidPackageMetadata(reason) => dict.error_invalid_package_metadata(reason),
+            ArgError::UndefinedVariable(target, name) => dict.error_undefined_variable(target, name),
+            ArgError::UpdaterTooOld(min) => dict.error_updater_too_old(min, env!("CARGO_PKG_VERSION")),
+            ArgError::InvalidVersion(value) => dict.error_invalid_version(value),
+            ArgError::NotNewer(package, current) => dict.error_not_newer(package, current),
+            ArgError::CurrentTooOld(current, min) => dict.error_current_too_old(current, min),
+            ArgError::IncompatibleOptions(first, second) => dict.error_incompatible_options(first, second),
+            ArgError::NoPackage => dict.error_no_package.to_string(),
+            ArgError::NoTarget => dict.error_no_target.to_string(),
+        }
+    }
+}
+
+// 从标准输入读取参数的选项，必须在命令行中指定
+const STDIN_ARGS_FLAG: &str = "--args-from-stdin";
+
+// 命令行中有 --args-from-stdin 时从标准输入读取参数，替换该选项所在的位置，之前和之后的命令行参数照常生效。
+// 输入中有空字符时按空字符分隔，否则按行分隔并忽略空行；每个参数原样使用，不需要引号或转义
+pub fn with_stdin_args(args: Vec<String>) -> Result<Vec<String>, String> {
+    with_args_from(args, io::stdin().lock())
+}
+
+// 从指定的输入读取参数，与 with_stdin_args 相同，只在命令行中有 --args-from-stdin 时读取
+fn with_args_from(args: Vec<String>, mut reader: impl Read) -> Result<Vec<String>, String> {
+    let Some(pos) = args.iter().position(|arg| arg == STDIN_ARGS_FLAG) else {
+        return Ok(args);
+    };
+    let mut input = String::new();
+    if let Err(e) = reader.read_to_string(&mut input) {
+        let lang = requested_language(&args).unwrap_or_else(detect_system_language);
+        return Err(get_dict(lang).error_stdin_args(&e.to_string()));
+    }
+    let stdin_args: Vec<String> = if input.contains('\0') {
+        input.split('\0').filter(|arg| !arg.is_empty()).map(str::to_string).collect()
+    } else {
+        input.lines().filter(|line| !line.is_empty()).map(str::to_string).collect()
+    };
+    let mut merged = args[..pos].to_vec();
+    merged.extend(stdin_args);
+    merged.extend_from_slice(&args[pos + 1..]);
+    Ok(merged)
+}
+
+// 解析命令行参数（不包含程序名）并与配置文件合并，参数无效时返回本地化的错误信息
+pub fn parse_args(args: &[String]) -> Result<Config, String> {
+    let file = load_config_file(args);
+    let file_lang = file.as_ref().ok().and_then(|file| file.lang.as_deref()).and_then(parse_language);
+    let lang = requested_language(args).or(file_lang).unwrap_or_else(detect_system_language);
+    file.and_then(|file| parse_args_with_lang(args, lang, file)).map_err(|err| err.localize(get_dict(lang)))
+}
+
+// 默认的配置文件名，位于程序所在目录
+const CONFIG_FILE_NAME: &str = "updater.toml";
+
+// 配置文件中的设置，字段与命令行选项对应，命令行中指定的值优先
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FileConfig {
+    package: Option<String>,
+    inner_path: Option<String>,
+    target: Option<String>,
+    delay: Option<u64>,
+    start_exe: Option<String>,
+    auto_close: Option<u64>,
+    changelog: Option<String>,
+    show_size: Option<bool>,
+    lang: Option<String>,
+    theme: Option<String>,
+    log: Option<String>,
+    width: Option<f32>,
+    height: Option<f32>,
+    resizable: Option<bool>,
+    topmost: Option<bool>,
+    scale: Option<f32>,
+    center: Option<bool>,
+    pubkey: Option<String>,
+    sig: Option<String>,
+    kill_process: Option<String>,
+    password: Option<String>,
+    pre_hook: Option<String>,
+    post_hook: Option<String>,
+    jobs: Option<usize>,
+    retries: Option<u32>,
+    timeout: Option<u64>,
+    include: Option<Vec<String>>,
+    protect: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    map: Option<Vec<String>>,
+    expect_file: Option<Vec<String>>,
+    dry_run: Option<bool>,
+    confirm: Option<bool>,
+    verify: Option<bool>,
+    prune: Option<bool>,
+    continue_on_error: Option<bool>,
+    atomic: Option<bool>,
+    preserve_mtime: Option<bool>,
+    skip_unchanged: Option<bool>,
+    skip_newer: Option<bool>,
+    delta: Option<bool>,
+    flatten: Option<bool>,
+    confirm_overwrite: Option<bool>,
+    keep_package: Option<bool>,
+    fsync: Option<bool>,
+    receipt: Option<bool>,
+    preserve_hardlinks: Option<bool>,
+    dedup: Option<bool>,
+    audit_csv: Option<String>,
+    temp_dir: Option<String>,
+    resumable: Option<bool>,
+    self_suffix: Option<String>,
+    on_locked: Option<String>,
+    format: Option<String>,
+    self_stage: Option<bool>,
+    target_subdir: Option<String>,
+    current_version: Option<String>,
+    allow_downgrade: Option<bool>,
+    self_dir: Option<bool>,
+    silent: Option<bool>,
+    elevate: Option<bool>,
+}
+
+// 读取配置文件：优先使用 --config 指定的文件，否则读取程序目录下的 updater.toml（不存在时忽略）
+fn load_config_file(args: &[String]) -> Result<FileConfig, ArgError> {
+    let mut explicit = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let (name, inline) = split_flag(arg);
+        if name == "--config" {
+            explicit = Some(inline.or_else(|| iter.next().cloned()).ok_or_else(|| ArgError::MissingValue(name.to_string()))?);
+        } else if takes_value(name) && inline.is_none() {
+            iter.next();
+        }
+    }
+
+    let path = match explicit {
+        Some(path) => PathBuf::from(path),
+        None => match exe_dir().map(|dir| dir.join(CONFIG_FILE_NAME)) {
+            Some(path) if path.is_file() => path,
+            _ => return Ok(FileConfig::default()),
+        },
+    };
+    let invalid = |reason: String| ArgError::InvalidConfig(path.display().to_string(), reason);
+    let text = fs::read_to_string(&path).map_err(|e| invalid(e.to_string()))?;
+    let file: FileConfig = toml::from_str(&text).map_err(|e| invalid(e.message().to_string()))?;
+
+    // 与命令行参数使用相同的校验规则
+    if let Some(lang) = &file.lang && parse_language(lang).is_none() {
+        return Err(ArgError::InvalidLanguage(lang.clone()));
+    }
+    if let Some(theme) = &file.theme && parse_theme(theme).is_none() {
+        return Err(ArgError::InvalidTheme(theme.clone()));
+    }
+    if file.jobs == Some(0) {
+        return Err(ArgError::InvalidJobs("0".to_string()));
+    }
+    if file.timeout == Some(0) {
+        return Err(ArgError::InvalidTimeout("0".to_string()));
+    }
+    if let Some(policy) = &file.on_locked {
+        parse_lock_policy(policy)?;
+    }
+    if let Some(format) = &file.format {
+        parse_format(format)?;
+    }
+    if let Some(suffix) = &file.self_suffix {
+        parse_suffix(suffix)?;
+    }
+    if let Some(subdir) = &file.target_subdir {
+        validate_subdir(subdir)?;
+    }
+    if let Some(scale) = file.scale {
+        parse_scale(&scale.to_string())?;
+    }
+    for size in [file.width, file.height].into_iter().flatten() {
+        if !size.is_finite() || size <= 0.0 {
+            return Err(ArgError::InvalidSize(size.to_string()));
+        }
+    }
+    for pattern in file.include.iter().chain(&file.exclude).chain(&file.protect).flatten() {
+        validate_glob(pattern).map_err(|reason| ArgError::InvalidGlob(pattern.clone(), reason))?;
+    }
+    for mapping in file.map.iter().flatten() {
+        parse_mapping(mapping)?;
+    }
+    Ok(file)
+}
+
+// 更新程序所在的目录
+fn exe_dir() -> Option<PathBuf> {
+    env::current_exe().ok().and_then(|exe| exe.parent().map(PathBuf::from))
+}
+
+// 查找命令行中指定的语言，--lang 优先于位置参数中的语言代码
+fn requested_language(args: &[String]) -> Option<Language> {
+    let mut positional_lang = None;
+    let mut positional_count = 0;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let (name, inline) = split_flag(arg);
+        if name == "--lang" {
+            let value = inline.or_else(|| iter.next().cloned());
+            if let Some(lang) = value.as_deref().and_then(parse_language) {
+                return Some(lang);
+            }
+        } else if takes_value(name) {
+            if inline.is_none() {
+                iter.next();
+            }
+        } else if !arg.starts_with('-') {
+            if positional_count >= 2 && positional_lang.is_none() {
+                positional_lang = parse_language(arg);
+            }
+            positional_count += 1;
+        }
+    }
+    positional_lang
+}
+
+// 默认窗口大小
+const DEFAULT_WIDTH: f32 = 450.0;
+const DEFAULT_HEIGHT: f32 = 250.0;
+
+// 允许的界面缩放比例范围
+const MIN_SCALE: f32 = 0.5;
+const MAX_SCALE: f32 = 4.0;
+
+// 解析界面缩放比例，auto 表示按系统DPI自动缩放
+fn parse_scale(value: &str) -> Result<Option<f32>, ArgError> {
+    if value.eq_ignore_ascii_case("auto") {
+        return Ok(None);
+    }
+    value.parse::<f32>().ok()
+        .filter(|scale| (MIN_SCALE..=MAX_SCALE).contains(scale))
+        .map(Some)
+        .ok_or_else(|| ArgError::InvalidScale(value.to_string()))
+}
+
+// 解析文件被占用时的处理方式
+fn parse_lock_policy(value: &str) -> Result<LockPolicy, ArgError> {
+    match value.to_lowercase().as_str() {
+        "wait" => Ok(LockPolicy::Wait),
+        "abort" => Ok(LockPolicy::Abort),
+        "stage" => Ok(LockPolicy::Stage),
+        _ => Err(ArgError::InvalidLockPolicy(value.to_string())),
+    }
+}
+
+// 解析强制指定的更新包格式
+fn parse_format(value: &str) -> Result<PackageFormat, ArgError> {
+    match value.to_lowercase().as_str() {
+        "zip" => Ok(PackageFormat::Zip),
+        "tar.gz" | "tgz" => Ok(PackageFormat::TarGz),
+        "dir" => Ok(PackageFormat::Dir),
+        _ => Err(ArgError::InvalidFormat(value.to_string())),
+    }
+}
+
+// 拆分 --flag=value 形式的参数
+fn split_flag(arg: &str) -> (&str, Option<String>) {
+    if arg.starts_with("--") && let Some((name, value)) = arg.split_once('=') {
+        return (name, Some(value.to_string()));
+    }
+    (arg, None)
+}
+
+// 解析 <压缩包内路径>:<目标路径> 形式的目录映射，在第一个冒号处拆分，目标路径中可以包含盘符
+fn parse_mapping(value: &str) -> Result<(String, String), ArgError> {
+    match value.split_once(':') {
+        Some((inner, target)) if !target.is_empty() => Ok((inner.trim_matches('/').to_string(), target.to_string())),
+        _ => Err(ArgError::InvalidMap(value.to_string())),
+    }
+}
+
+// 解析暂存后缀，省略开头的点时自动补上；后缀不能为空或包含路径分隔符
+fn parse_suffix(value: &str) -> Result<String, ArgError> {
+    let suffix = if value.starts_with('.') { value.to_string() } else { format!(".{}", value) };
+    if suffix.len() < 2 || suffix.contains(['/', '\\']) {
+        return Err(ArgError::InvalidSuffix(value.to_string()));
+    }
+    Ok(suffix)
+}
+
+// 目标子目录必须是不包含..的非空相对路径，不能指向目标目录之外
+fn validate_subdir(value: &str) -> Result<(), ArgError> {
+    let path = Path::new(value);
+    let valid = path.components().all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+        && path.components().any(|component| matches!(component, Component::Normal(_)));
+    if valid { Ok(()) } else { Err(ArgError::InvalidTargetSubdir(value.to_string())) }
+}
+
+// 需要携带参数值的选项
+fn takes_value(name: &str) -> bool {
+    matches!(name, "--package" | "--inner-path" | "--target" | "--delay" | "--start-exe" | "--auto-close" | "--lang" | "--log" | "--changelog" | "--pubkey" | "--sig" | "--kill-process" | "--password" | "--pre-hook" | "--post-hook" | "--jobs" | "--width" | "--height" | "--scale" | "--theme" | "--retries" | "--timeout" | "--include" | "--exclude" | "--protect" | "--config" | "--map" | "--expect-file" | "--current-version" | "--self-suffix" | "--on-locked" | "--target-subdir" | "--audit-csv" | "--temp-dir" | "--format")
+}
+
+fn parse_args_with_lang(args: &[String], lang: Language, file: FileConfig) -> Result<Config, ArgError> {
+    let mut package_path = None;
+    let mut zip_inner_path = None;
+    let mut target_path = None;
+    let mut delay_seconds = None;
+    let mut auto_close = None;
+    let mut start_exe_path = None;
+    let mut changelog_path = None;
+    let mut pre_hook = None;
+    let mut post_hook = None;
+    let mut log_path = None;
+    let mut pubkey_path = None;
+    let mut sig_path = None;
+    let mut kill_process = None;
+    let mut password = None;
+    let mut current_version = None;
+    let mut jobs = None;
+    let mut width = None;
+    let mut height = None;
+    let mut resizable = false;
+    let mut topmost = true;
+    let mut scale = None;
+    let mut center = true;
+    let mut theme = None;
+    let mut retries = None;
+    let mut timeout = None;
+    let mut include = Vec::new();
+    let mut protect = Vec::new();
+    let mut exclude = Vec::new();
+    let mut mappings = Vec::new();
+    let mut expect_files = Vec::new();
+    let mut dry_run = false;
+    let mut confirm = false;
+    let mut verify = false;
+    let mut prune = false;
+    let mut continue_on_error = false;
+    let mut atomic = false;
+    let mut preserve_mtime = true;
+    let mut skip_unchanged = false;
+    let mut skip_newer = false;
+    let mut delta = false;
+    let mut flatten = false;
+    let mut confirm_overwrite = false;
+    let mut keep_package = false;
+    let mut fsync = false;
+    let mut receipt = false;
+    let mut preserve_hardlinks = false;
+    let mut dedup = false;
+    let mut audit_csv = None;
+    let mut temp_dir = None;
+    let mut resumable = false;
+    let mut self_suffix = None;
+    let mut on_locked = None;
+    let mut format = None;
+    let mut self_stage = true;
+    let mut target_subdir = None;
+    let mut allow_downgrade = false;
+    let mut self_dir = false;
+    let mut json = false;
+    let mut silent = false;
+    let mut elevate = false;
+    let mut show_help = false;
+    let mut self_test = false;
+    let mut show_size = false;
+    let mut inspect = false;
+    let mut positional = Vec::new();
+    let mut positional_lang_seen = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let (name, inline) = split_flag(arg);
+
+        // 读取选项的参数值
+        let mut value = || -> Result<String, ArgError> {
+            match &inline {
+                Some(value) => Ok(value.clone()),
+                None => iter.next().cloned().ok_or_else(|| ArgError::MissingValue(name.to_string())),
+            }
+        };
+
+        match name {
+            "--help" | "-h" => show_help = true,
+            "--self-test" => self_test = true,
+            "--show-size" => show_size = true,
+            "--inspect" => inspect = true,
+            "--dry-run" => dry_run = true,
+            "--confirm" => confirm = true,
+            "--verify" => verify = true,
+            "--prune" => prune = true,
+            "--continue-on-error" => continue_on_error = true,
+            "--resizable" => resizable = true,
+            "--no-topmost" => topmost = false,
+            "--no-center" => center = false,
+            "--scale" => scale = Some(parse_scale(&value()?)?),
+            "--atomic" => atomic = true,
+            "--no-preserve-mtime" => preserve_mtime = false,
+            "--skip-unchanged" => skip_unchanged = true,
+            "--skip-newer" => skip_newer = true,
+            "--delta" => delta = true,
+            "--flatten" => flatten = true,
+            "--confirm-overwrite" => confirm_overwrite = true,
+            "--keep-package" => keep_package = true,
+            "--fsync" => fsync = true,
+            "--receipt" => receipt = true,
+            "--preserve-hardlinks" => preserve_hardlinks = true,
+            "--dedup" => dedup = true,
+            "--resumable" => resumable = true,
+            "--no-self-stage" => self_stage = false,
+            "--allow-downgrade" => allow_downgrade = true,
+            "--self-dir" => self_dir = true,
+            "--json" => json = true,
+            // 已在解析前展开，标准输入中重复出现时忽略
+            STDIN_ARGS_FLAG => {},
+            "--silent" => silent = true,
+            "--elevate" => elevate = true,
+            "--package" => package_path = Some(value()?),
+            "--inner-path" => zip_inner_path = Some(value()?),
+            "--target" => target_path = Some(value()?),
+            "--start-exe" => start_exe_path = Some(value()?),
+            "--log" => log_path = Some(value()?),
+            "--changelog" => changelog_path = Some(value()?),
+            "--pubkey" => pubkey_path = Some(value()?),
+            "--sig" => sig_path = Some(value()?),
+            "--kill-process" => kill_process = Some(value()?),
+            "--password" => password = Some(value()?),
+            "--pre-hook" => pre_hook = Some(value()?),
+            "--post-hook" => post_hook = Some(value()?),
+            "--audit-csv" => audit_csv = Some(value()?),
+            "--temp-dir" => temp_dir = Some(value()?),
+            "--delay" => {
+                let value = value()?;
+                delay_seconds = Some(value.parse::<u64>().map_err(|_| ArgError::InvalidDelay(value))?);
+            },
+            "--auto-close" => {
+                let value = value()?;
+                auto_close = Some(value.parse::<u64>().map_err(|_| ArgError::InvalidAutoClose(value))?);
+            },
+            "--jobs" => {
+                let value = value()?;
+                jobs = Some(value.parse::<usize>().ok().filter(|n| *n > 0).ok_or(ArgError::InvalidJobs(value))?);
+            },
+            "--width" | "--height" => {
+                let value = value()?;
+                let size = value.parse::<f32>().ok().filter(|n| n.is_finite() && *n > 0.0).ok_or(ArgError::InvalidSize(value))?;
+                if name == "--width" {
+                    width = Some(size);
+                } else {
+                    height = Some(size);
+                }
+            },
+            "--retries" => {
+                let value = value()?;
+                retries = Some(value.parse::<u32>().map_err(|_| ArgError::InvalidRetries(value))?);
+            },
+            "--timeout" => {
+                let value = value()?;
+                timeout = Some(value.parse::<u64>().ok().filter(|n| *n > 0).ok_or(ArgError::InvalidTimeout(value))?);
+            },
+            "--self-suffix" => self_suffix = Some(parse_suffix(&value()?)?),
+            "--on-locked" => on_locked = Some(parse_lock_policy(&value()?)?),
+            "--format" => format = Some(parse_format(&value()?)?),
+            "--target-subdir" => {
+                let value = value()?;
+                validate_subdir(&value)?;
+                target_subdir = Some(value);
+            },
+            "--include" | "--exclude" | "--protect" => {
+                let value = value()?;
+                validate_glob(&value).map_err(|reason| ArgError::InvalidGlob(value.clone(), reason))?;
+                match name {
+                    "--include" => include.push(value),
+                    "--exclude" => exclude.push(value),
+                    _ => protect.push(value),
+                }
+            },
+            "--map" => mappings.push(parse_mapping(&value()?)?),
+            "--expect-file" => expect_files.push(value()?),
+            "--current-version" => current_version = Some(value()?),
+            "--theme" => {
+                let value = value()?;
+                theme = Some(parse_theme(&value).ok_or(ArgError::InvalidTheme(value))?);
+            },
+            // 配置文件已在解析命令行之前读取
+            "--config" => {
+                value()?;
+            },
+            "--lang" => {
+                let value = value()?;
+                if parse_language(&value).is_none() {
+                    return Err(ArgError::InvalidLanguage(value));
+                }
+            },
+            _ if arg.starts_with("--") => return Err(ArgError::UnknownOption(arg.clone())),
+            _ => {
+                // 位置参数中的语言代码之后的参数全部忽略
+                if positional_lang_seen {
+                    continue;
+                }
+                if positional.len() >= 2 && parse_language(arg).is_some() {
+                    positional_lang_seen = true;
+                    continue;
+                }
+                positional.push(arg.clone());
+            },
+        }
+    }
+
+    // 兼容旧的位置参数用法，命名选项优先，最后使用配置文件中的值
+    let positional_args = !positional.is_empty();
+    let mut positional = positional.into_iter();
+    let package_path = package_path.or_else(|| positional.next()).or(file.package).unwrap_or_default();
+    let zip_inner_path = zip_inner_path.or_else(|| positional.next()).or(file.inner_path);
+    let mut target_path = target_path.or_else(|| positional.next()).or(file.target);
+    // --self-dir 时没有指定目标路径则更新程序所在的目录，程序自身的文件按自我更新的方式暂存
+    if target_path.is_none() && (self_dir || file.self_dir.unwrap_or(false)) && let Some(dir) = exe_dir() {
+        target_path = Some(dir.display().to_string());
+    }
+    
+    // 更新包中的 update.json 提供其余未指定的值；指定了 --map 时不使用其中的目录和目标路径
+    let password = password.or(file.password);
+    let format = match format {
+        Some(format) => Some(format),
+        None => file.format.as_deref().map(parse_format).transpose()?,
+    };
+    let mut metadata = package_meta::load(&package_path, format, password.as_deref())
+        .map_err(ArgError::InvalidPackageMetadata)?
+        .unwrap_or_default();
+    if !package_meta::version_supported(&metadata) && let Some(min) = &metadata.min_version {
+        return Err(ArgError::UpdaterTooOld(min.to_string()));
+    }
+    
+    // 命令行中指定了映射时替换配置文件中的映射
+    let mappings = if mappings.is_empty() {
+        file.map.iter().flatten().map(|mapping| parse_mapping(mapping)).collect::<Result<_, _>>()?
+    } else {
+        mappings
+    };
+    let use_metadata_paths = mappings.is_empty();
+    // 更新包中的目标路径可以包含 %ProgramFiles%、$HOME 等环境变量，只在实际使用时展开
+    let metadata_target = match metadata.target.take() {
+        Some(target) if target_path.is_none() && use_metadata_paths => {
+            Some(package_meta::expand_env(&target).map_err(|name| ArgError::UndefinedVariable(target.clone(), name))?)
+        },
+        _ => None,
+    };
+    
+    // 已安装版本不低于更新包版本时拒绝更新以防止意外降级（--allow-downgrade 时允许），低于最低版本时总是拒绝
+    let current_version = match current_version.or(file.current_version) {
+        Some(value) => Some(semver::Version::parse(&value).map_err(|_| ArgError::InvalidVersion(value))?),
+        None => {
+            let target = target_path.as_deref()
+                .or(metadata_target.as_deref())
+                .or(mappings.first().map(|(_, target)| target.as_str()));
+            package_meta::installed_version(&metadata, target).map_err(ArgError::InvalidVersion)?
+        },
+    };
+    if let Some(current) = &current_version {
+        match package_meta::check_upgrade(current, &metadata) {
+            Err(Rejection::NotNewer(_)) if allow_downgrade || file.allow_downgrade.unwrap_or(false) => {},
+            Err(Rejection::NotNewer(version)) => return Err(ArgError::NotNewer(version.to_string(), current.to_string())),
+            Err(Rejection::BelowMinimum(min)) => return Err(ArgError::CurrentTooOld(current.to_string(), min.to_string())),
+            Ok(()) => {},
+        }
+    }
+    
+    let zip_inner_path = zip_inner_path.or(metadata.inner_path.filter(|_| use_metadata_paths)).unwrap_or_default();
+    let target_path = target_path.or(metadata_target);
+    // 相对的启动路径相对于目标目录
+    let relaunch = metadata.relaunch.map(|path| match &target_path {
+        Some(target) if Path::new(&path).is_relative() => Path::new(target).join(path).display().to_string(),
+        _ => path,
+    });
+    let delay_seconds = match (delay_seconds, positional.next()) {
+        (Some(seconds), _) => seconds,
+        (None, Some(value)) => value.parse::<u64>().map_err(|_| ArgError::InvalidDelay(value))?,
+        (None, None) => file.delay.unwrap_or(0),
+    };
+    let start_exe_path = start_exe_path.or_else(|| positional.next()).or(file.start_exe).or(relaunch);
+
+    if let Some(extra) = positional.next() {
+        return Err(ArgError::UnexpectedArgument(extra));
+    }
+
+    let defaults = UpdateConfig::default();
+    let config = Config {
+        update: UpdateConfig {
+            package_path,
+            zip_inner_path,
+            target_path,
+            // 命令行中指定了映射时替换配置文件中的映射
+            mappings,
+            lang,
+            dry_run: dry_run || file.dry_run.unwrap_or(false),
+            confirm: confirm || file.confirm.unwrap_or(false),
+            verify: verify || file.verify.unwrap_or(false),
+            prune: prune || file.prune.unwrap_or(false),
+            atomic: atomic || file.atomic.unwrap_or(false),
+            preserve_mtime: preserve_mtime && file.preserve_mtime.unwrap_or(true),
+            skip_unchanged: skip_unchanged || file.skip_unchanged.unwrap_or(false),
+            skip_newer: skip_newer || file.skip_newer.unwrap_or(false),
+            delta: delta || file.delta.unwrap_or(false),
+            flatten: flatten || file.flatten.unwrap_or(false),
+            confirm_overwrite: confirm_overwrite || file.confirm_overwrite.unwrap_or(false),
+            keep_package: keep_package || file.keep_package.unwrap_or(false),
+            fsync: fsync || file.fsync.unwrap_or(false),
+            receipt: receipt || file.receipt.unwrap_or(false),
+            preserve_hardlinks: preserve_hardlinks || file.preserve_hardlinks.unwrap_or(false),
+            dedup: dedup || file.dedup.unwrap_or(false),
+            audit_csv: audit_csv.or(file.audit_csv),
+            temp_dir: temp_dir.or(file.temp_dir),
+            format,
+            resumable: resumable || file.resumable.unwrap_or(false),
+            on_locked: match on_locked {
+                Some(policy) => policy,
+                None => file.on_locked.as_deref().map(parse_lock_policy).transpose()?.unwrap_or_default(),
+            },
+            self_suffix: match self_suffix {
+                Some(suffix) => suffix,
+                None => file.self_suffix.as_deref().map(parse_suffix).transpose()?.unwrap_or(defaults.self_suffix),
+            },
+            self_stage: self_stage && file.self_stage.unwrap_or(true),
+            target_subdir: target_subdir.or(file.target_subdir),
+            jobs: jobs.or(file.jobs).unwrap_or(defaults.jobs),
+            retries: retries.or(file.retries).unwrap_or(defaults.retries),
+            continue_on_error: continue_on_error || file.continue_on_error.unwrap_or(false),
+            timeout: timeout.or(file.timeout).map(Duration::from_secs),
+            // 命令行中指定了模式时替换配置文件中的模式
+            include: if include.is_empty() { file.include.unwrap_or_default() } else { include },
+            protect: if protect.is_empty() { file.protect.unwrap_or_default() } else { protect },
+            exclude: if exclude.is_empty() { file.exclude.unwrap_or_default() } else { exclude },
+            expect_files: if expect_files.is_empty() { file.expect_file.unwrap_or_default() } else { expect_files },
+            pre_hook: pre_hook.or(file.pre_hook),
+            post_hook: post_hook.or(file.post_hook),
+            pubkey_path: pubkey_path.or(file.pubkey),
+            sig_path: sig_path.or(file.sig),
+            kill_process: kill_process.or(file.kill_process),
+            password,
+        },
+        delay_seconds,
+        start_exe_path,
+        auto_close: auto_close.or(file.auto_close),
+        changelog_path: changelog_path.or(file.changelog),
+        show_size: show_size || file.show_size.unwrap_or(false),
+        json,
+        silent: silent || file.silent.unwrap_or(false),
+        elevate: elevate || file.elevate.unwrap_or(false),
+        show_help,
+        self_test,
+        inspect,
+        log_path: log_path.or(file.log),
+        width: width.or(file.width).unwrap_or(DEFAULT_WIDTH),
+        height: height.or(file.height).unwrap_or(DEFAULT_HEIGHT),
+        resizable: resizable || file.resizable.unwrap_or(false),
+        topmost: topmost && file.topmost.unwrap_or(true),
+        scale: scale.unwrap_or(file.scale),
+        center: center && file.center.unwrap_or(true),
+        theme: theme.or_else(|| file.theme.as_deref().and_then(parse_theme)).unwrap_or(Theme::Light),
+        positional_args,
+    };
+
+    // 显示帮助时不再校验必填参数
+    if config.show_help {
+        return Ok(config);
+    }
+
+    if config.update.package_path.is_empty() {
+        return Err(ArgError::NoPackage);
+    }
+    // 断点续传依赖目标目录中已复制的文件：原子替换失败时丢弃整个临时目录，增量补丁不能重复应用到已更新的文件
+    if config.update.resumable && config.update.atomic {
+        return Err(ArgError::IncompatibleOptions("--resumable", "--atomic"));
+    }
+    if config.update.resumable && config.update.delta {
+        return Err(ArgError::IncompatibleOptions("--resumable", "--delta"));
+    }
+    // 补丁按原有的目录结构对应目标文件
+    if config.update.flatten && config.update.delta {
+        return Err(ArgError::IncompatibleOptions("--flatten", "--delta"));
+    }
+    // 不保留修改时间时解压出的文件都是当前时间，无法与目标文件比较新旧
+    if config.update.skip_newer && !config.update.preserve_mtime {
+        return Err(ArgError::IncompatibleOptions("--skip-newer", "--no-preserve-mtime"));
+    }
+    // 没有界面时无法确认，不能在用户看不到操作列表的情况下直接应用
+    if config.update.confirm && config.json {
+        return Err(ArgError::IncompatibleOptions("--confirm", "--json"));
+    }
+    if config.update.confirm && config.silent {
+        return Err(ArgError::IncompatibleOptions("--confirm", "--silent"));
+    }
+    // 检查模式只读取更新包，不需要目标路径
+    if config.update.target_path.is_none() && config.update.mappings.is_empty() && !config.inspect {
+        return Err(ArgError::NoTarget);
+    }
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_named_options() {
+        let config = parse_args(&args(&["--lang", "en", "--package", "missing.zip", "--inner-path", "app", "--target", "out", "--delay=5", "--start-exe", "app.exe", "--json"])).unwrap();
+        assert_eq!(config.update.package_path, "missing.zip");
+        assert_eq!(config.update.zip_inner_path, "app");
+        assert_eq!(config.update.target_path.as_deref(), Some("out"));
+        assert_eq!(config.update.lang, Language::English);
+        assert_eq!(config.delay_seconds, 5);
+        assert_eq!(config.start_exe_path.as_deref(), Some("app.exe"));
+        assert!(config.json);
+        assert!(!config.positional_args);
+    }
+
+    #[test]
+    fn parses_legacy_positional_arguments() {
+        let config = parse_args(&args(&["missing.zip", "app", "out", "en", "ignored"])).unwrap();
+        assert_eq!(config.update.package_path, "missing.zip");
+        assert_eq!(config.update.zip_inner_path, "app");
+        assert_eq!(config.update.target_path.as_deref(), Some("out"));
+        assert_eq!(config.update.lang, Language::English);
+        assert!(config.positional_args);
+    }
+
+    #[test]
+    fn help_skips_required_arguments() {
+        assert!(parse_args(&args(&["--help"])).unwrap().show_help);
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        let error = |list: &[&str]| parse_args(&args(list)).unwrap_err();
+        assert_eq!(error(&["--lang", "en", "--target", "out"]), "No update package path was provided");
+        assert_eq!(error(&["--lang", "en", "--package", "missing.zip"]), "A target path must be provided");
+        assert_eq!(error(&["--lang", "en", "--bogus"]), "Unknown option: --bogus");
+        assert_eq!(error(&["--lang", "en", "--package"]), "Missing value for option: --package");
+        assert_eq!(error(&["--lang", "en", "--package", "missing.zip", "--target", "out", "--delay", "soon"]), get_dict(Language::English).error_invalid_delay("soon"));
+        assert_eq!(error(&["--lang", "en", "a.zip", "app", "out", "5", "start.exe", "extra"]), "Unexpected argument: extra");
+    }
+
+    // 配置文件提供默认值，命令行中的选项优先，命令行中的模式替换配置文件中的模式
+    #[test]
+    fn merges_config_file_with_arguments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("updater.toml");
+        fs::write(&path, r#"
+package = "missing.zip"
+target = "from-file"
+delay = 3
+jobs = 2
+verify = true
+exclude = ["*.log"]
+include = ["bin/**"]
+theme = "dark"
+"#).unwrap();
+        let config = parse_args(&args(&["--lang", "en", "--config", path.to_str().unwrap(), "--target", "from-args", "--include", "*.dll"])).unwrap();
+        assert_eq!(config.update.package_path, "missing.zip");
+        assert_eq!(config.update.target_path.as_deref(), Some("from-args"));
+        assert_eq!(config.delay_seconds, 3);
+        assert_eq!(config.update.jobs, 2);
+        assert!(config.update.verify);
+        assert_eq!(config.update.exclude, ["*.log"]);
+        assert_eq!(config.update.include, ["*.dll"]);
+        assert_eq!(config.theme, Theme::Dark);
+    }
+
+    #[test]
+    fn rejects_invalid_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("updater.toml");
+        fs::write(&path, "unknown_key = 1\n").unwrap();
+        let error = parse_args(&args(&["--lang", "en", "--config", path.to_str().unwrap()])).unwrap_err();
+        assert!(error.contains(path.to_str().unwrap()), "{}", error);
+        fs::write(&path, "jobs = 0\n").unwrap();
+        let error = parse_args(&args(&["--lang", "en", "--config", path.to_str().unwrap()])).unwrap_err();
+        assert_eq!(error, get_dict(Language::English).error_invalid_jobs("0"));
+    }
+
+    // 命令行中的值优先于更新包中 update.json 的值，未指定的值使用 update.json 中的
+    #[test]
+    fn package_metadata_fills_missing_values() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("update.json"), r#"{"inner_path": "app", "target": "meta-target", "relaunch": "app.exe"}"#).unwrap();
+        let package = dir.path().to_str().unwrap();
+
+        let config = parse_args(&args(&["--lang", "en", "--package", package])).unwrap();
+        assert_eq!(config.update.zip_inner_path, "app");
+        assert_eq!(config.update.target_path.as_deref(), Some("meta-target"));
+        assert_eq!(config.start_exe_path, Some(Path::new("meta-target").join("app.exe").display().to_string()));
+
+        let config = parse_args(&args(&["--lang", "en", "--package", package, "--inner-path", "other", "--target", "cli-target", "--start-exe", "run.exe"])).unwrap();
+        assert_eq!(config.update.zip_inner_path, "other");
+        assert_eq!(config.update.target_path.as_deref(), Some("cli-target"));
+        assert_eq!(config.start_exe_path.as_deref(), Some("run.exe"));
+    }
+
+    #[test]
+    fn rejects_malformed_package_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("update.json"), r#"{"target": "#).unwrap();
+        let error = parse_args(&args(&["--lang", "en", "--package", dir.path().to_str().unwrap(), "--target", "out"])).unwrap_err();
+        assert!(error.starts_with(&get_dict(Language::English).error_invalid_package_metadata("")), "{}", error);
+        fs::write(dir.path().join("update.json"), r#"{"unknown": 1}"#).unwrap();
+        assert!(parse_args(&args(&["--lang", "en", "--package", dir.path().to_str().unwrap(), "--target", "out"])).is_err());
+    }
+
+    #[test]
+    fn parses_self_suffix() {
+        assert_eq!(parse_suffix(".pending").ok(), Some(".pending".to_string()));
+        assert_eq!(parse_suffix("staged").ok(), Some(".staged".to_string()));
+        for value in ["", ".", "a/b", "..\\x"] {
+            assert!(parse_suffix(value).is_err(), "{}", value);
+        }
+        let config = parse_args(&args(&["--lang", "en", "--package", "missing.zip", "--target", "out", "--self-suffix", "next", "--no-self-stage"])).unwrap();
+        assert_eq!(config.update.self_suffix, ".next");
+        assert!(!config.update.self_stage);
+    }
+
+    // 未指定 --target 时使用更新包中的目标路径并展开环境变量，变量未定义时报告错误；
+    // 只读取所有系统上都有的 PATH，不在并行运行的测试中修改环境变量
+    #[test]
+    fn target_from_package_expands_variables() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("target.txt"), "${UPDATER_CONFIG_TEST_UNDEFINED}/app\n").unwrap();
+        let package = dir.path().to_str().unwrap();
+        let error = parse_args(&args(&["--lang", "en", "--package", package])).unwrap_err();
+        assert_eq!(error, get_dict(Language::English).error_undefined_variable("${UPDATER_CONFIG_TEST_UNDEFINED}/app", "UPDATER_CONFIG_TEST_UNDEFINED"));
+
+        fs::write(dir.path().join("target.txt"), "%PATH%/app\n").unwrap();
+        let config = parse_args(&args(&["--lang", "en", "--package", package])).unwrap();
+        assert_eq!(config.update.target_path, Some(format!("{}/app", env::var("PATH").unwrap())));
+        let config = parse_args(&args(&["--lang", "en", "--package", package, "--target", "explicit"])).unwrap();
+        assert_eq!(config.update.target_path.as_deref(), Some("explicit"));
+    }
+
+    #[test]
+    fn merges_arguments_from_stdin() {
+        let input = "--package\nfrom stdin.zip\n\n--target\nC:\\Program Files\\App\n--verify\n";
+        let merged = with_args_from(args(&["--lang", "en", "--args-from-stdin", "--target", "override"]), input.as_bytes()).unwrap();
+        assert_eq!(merged, args(&["--lang", "en", "--package", "from stdin.zip", "--target", "C:\\Program Files\\App", "--verify", "--target", "override"]));
+        let config = parse_args(&merged).unwrap();
+        assert_eq!(config.update.package_path, "from stdin.zip");
+        // 之后的命令行参数照常生效
+        assert_eq!(config.update.target_path.as_deref(), Some("override"));
+        assert!(config.update.verify);
+
+        // 有空字符时按空字符分隔，参数中可以包含换行
+        let merged = with_args_from(args(&["--args-from-stdin"]), "--package\0a\nb.zip\0--json\0".as_bytes()).unwrap();
+        assert_eq!(merged, args(&["--package", "a\nb.zip", "--json"]));
+        // 没有 --args-from-stdin 时不读取输入
+        assert_eq!(with_args_from(args(&["--json"]), "--verify\n".as_bytes()).unwrap(), args(&["--json"]));
+    }
+
+    // 没有界面时无法确认，确认模式不能与 JSON 或静默模式同时使用
+    #[test]
+    fn rejects_confirm_without_interface() {
+        let error = |mode: &str| parse_args(&args(&["--lang", "en", "--package", "missing.zip", "--target", "out", "--confirm", mode])).unwrap_err();
+        assert_eq!(error("--json"), "--confirm cannot be used together with --json");
+        assert_eq!(error("--silent"), "--confirm cannot be used together with --silent");
+        assert!(parse_args(&args(&["--lang", "en", "--package", "missing.zip", "--target", "out", "--confirm"])).unwrap().update.confirm);
+    }
+
+    #[test]
+    fn rejects_skip_newer_without_mtimes() {
+        let error = parse_args(&args(&["--lang", "en", "--package", "missing.zip", "--target", "out", "--skip-newer", "--no-preserve-mtime"])).unwrap_err();
+        assert_eq!(error, "--skip-newer cannot be used together with --no-preserve-mtime");
+    }
+}
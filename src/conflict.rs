@@ -0,0 +1,74 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, mpsc};
+
+use globset::GlobSet;
+
+use crate::filter::{build_globset, normalize};
+use crate::update::{EventSender, emit};
+use crate::watchdog::Watchdog;
+use crate::{ConflictChoice, UpdateEvent};
+
+// 覆盖受保护文件前的确认：目标文件为只读或匹配 --protect 模式时暂停复制并询问调用方
+pub(crate) struct Conflicts {
+    enabled: bool,
+    protect: GlobSet,
+    // 用户选择全部覆盖（true）或全部跳过（false）后不再询问；询问期间持有锁，保证同时只询问一个文件
+    decision: Mutex<Option<bool>>,
+    skipped: AtomicUsize,
+}
+
+impl Conflicts {
+    pub fn new(enabled: bool, protect: &[String]) -> io::Result<Self> {
+        Ok(Self {
+            enabled,
+            protect: build_globset(protect)?,
+            decision: Mutex::new(None),
+            skipped: AtomicUsize::new(0),
+        })
+    }
+
+    // 是否覆盖目标文件；受保护的文件需要等待调用方回复，等待期间不计入超时
+    pub fn should_overwrite(&self, dest_path: &Path, relative_path: &Path, sender: &EventSender, watchdog: &Watchdog) -> bool {
+        if !self.enabled || !self.is_protected(dest_path, relative_path) {
+            return true;
+        }
+        let mut decision = self.decision.lock().unwrap();
+        let overwrite = match *decision {
+            Some(overwrite) => overwrite,
+            None => {
+                let (reply, response) = mpsc::channel();
+                emit(sender, UpdateEvent::Conflict(relative_path.display().to_string(), reply));
+                // 调用方没有回复就丢弃了发送端时，与未启用确认时一样直接覆盖
+                let choice = watchdog.suspend(|| response.recv().unwrap_or(ConflictChoice::Overwrite));
+                log::info!("受保护的文件 {:?}: {:?}", relative_path, choice);
+                match choice {
+                    ConflictChoice::Overwrite => true,
+                    ConflictChoice::Skip => false,
+                    ConflictChoice::OverwriteAll => *decision.insert(true),
+                    ConflictChoice::SkipAll => *decision.insert(false),
+                }
+            },
+        };
+        if !overwrite {
+            log::info!("跳过受保护的文件: {:?}", relative_path);
+            self.skipped.fetch_add(1, Ordering::SeqCst);
+        }
+        overwrite
+    }
+
+    // 已跳过的受保护文件数
+    pub fn skipped(&self) -> usize {
+        self.skipped.load(Ordering::SeqCst)
+    }
+
+    // 已存在的只读文件或匹配 --protect 模式的文件
+    fn is_protected(&self, dest_path: &Path, relative_path: &Path) -> bool {
+        match fs::symlink_metadata(dest_path) {
+            Ok(metadata) => metadata.is_file() && (metadata.permissions().readonly() || self.protect.is_match(normalize(relative_path))),
+            Err(_) => false,
+        }
+    }
+}
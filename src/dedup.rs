@@ -0,0 +1,89 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::update::sha256_file;
+
+// 按内容去重：同一次更新中内容相同的源文件只从更新包复制一次，后续的目标位置从第一个副本复制
+pub(crate) struct Dedup {
+    enabled: bool,
+    // 至少有两个源文件是这个大小，只有这些文件需要计算摘要
+    shared_sizes: HashSet<u64>,
+    // 每个摘要第一次成功写入的目标文件
+    copies: Mutex<HashMap<Vec<u8>, PathBuf>>,
+}
+
+impl Dedup {
+    // 根据所有映射中待复制的源文件建立去重缓存，未启用时不检查任何文件
+    pub fn new<'a>(enabled: bool, files: impl IntoIterator<Item = &'a Path>) -> Self {
+        let mut shared_sizes = HashSet::new();
+        if enabled {
+            let mut sizes = HashSet::new();
+            for path in files {
+                if let Ok(metadata) = fs::symlink_metadata(path) && metadata.is_file() && metadata.len() > 0 && !sizes.insert(metadata.len()) {
+                    shared_sizes.insert(metadata.len());
+                }
+            }
+        }
+        Self { enabled, shared_sizes, copies: Mutex::new(HashMap::new()) }
+    }
+
+    // 计算源文件的摘要；未启用或没有其他源文件与其大小相同时返回None，不读取文件内容
+    pub fn digest(&self, src: &Path) -> io::Result<Option<Vec<u8>>> {
+        if !self.enabled || !self.shared_sizes.contains(&fs::metadata(src)?.len()) {
+            return Ok(None);
+        }
+        sha256_file(src).map(Some)
+    }
+
+    // 内容相同的文件已写入的目标位置；该文件已不存在时（如原子替换模式下暂存目录已被移走）返回None
+    pub fn copied(&self, digest: &[u8]) -> Option<PathBuf> {
+        self.copies.lock().unwrap().get(digest).filter(|path| path.is_file()).cloned()
+    }
+
+    // 记录摘要对应的目标文件，已有记录时保留第一个
+    pub fn record(&self, digest: Vec<u8>, dest: &Path) {
+        self.copies.lock().unwrap().entry(digest).or_insert_with(|| dest.to_path_buf());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::write_files;
+
+    // 只有大小相同的源文件才计算摘要，内容相同的文件共用第一个成功写入的目标位置
+    #[test]
+    fn reuses_first_copy_of_identical_content() {
+        let dir = tempfile::tempdir().unwrap();
+        write_files(dir.path(), &[("a/logo.png", b"same"), ("b/logo.png", b"same"), ("c/other.txt", b"different")]);
+        let files = ["a/logo.png", "b/logo.png", "c/other.txt"].map(|name| dir.path().join(name));
+        let dedup = Dedup::new(true, files.iter().map(PathBuf::as_path));
+
+        assert_eq!(dedup.digest(&files[2]).unwrap(), None);
+        let first = dedup.digest(&files[0]).unwrap().unwrap();
+        assert_eq!(dedup.digest(&files[1]).unwrap().as_ref(), Some(&first));
+        assert_eq!(dedup.copied(&first), None);
+
+        let dest = dir.path().join("dest/logo.png");
+        write_files(dir.path(), &[("dest/logo.png", b"same")]);
+        dedup.record(first.clone(), &dest);
+        dedup.record(first.clone(), &files[1]);
+        assert_eq!(dedup.copied(&first), Some(dest.clone()));
+
+        // 已写入的副本被移走后改为从更新包复制
+        fs::remove_file(&dest).unwrap();
+        assert_eq!(dedup.copied(&first), None);
+    }
+
+    #[test]
+    fn disabled_dedup_reads_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        write_files(dir.path(), &[("a.bin", b"same"), ("b.bin", b"same")]);
+        let files = ["a.bin", "b.bin"].map(|name| dir.path().join(name));
+        let dedup = Dedup::new(false, files.iter().map(PathBuf::as_path));
+        assert_eq!(dedup.digest(&files[0]).unwrap(), None);
+    }
+}
@@ -0,0 +1,84 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::language::LangDict;
+
+// 二进制补丁文件的扩展名，foo.exe.patch 是目标目录中 foo.exe 的补丁
+const PATCH_EXTENSION: &str = "patch";
+
+// 在解压出的目录中把每个 .patch 文件应用到目标目录中对应的现有文件，生成的新文件替换补丁文件，
+// 之后的校验清单验证和复制流程把它当作普通文件处理。返回应用的补丁数
+pub fn apply_patches(inner_path: &Path, root: &Path, target: &Path, dict: &LangDict) -> io::Result<usize> {
+    // 先收集补丁文件，避免遍历过程中看到新生成的文件
+    let patches: Vec<PathBuf> = WalkDir::new(inner_path).into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.file_type().is_file() && entry.path().extension().is_some_and(|ext| ext == PATCH_EXTENSION))
+        .map(|entry| entry.into_path())
+        .collect();
+
+    for patch_path in &patches {
+        let output_path = patch_path.with_extension("");
+        // 更新包中同时有完整文件时使用完整文件
+        if output_path.exists() {
+            log::warn!("更新包中已有完整文件，忽略补丁: {:?}", patch_path);
+            fs::remove_file(patch_path)?;
+            continue;
+        }
+        let relative_path = output_path.strip_prefix(root).map_err(|e| io::Error::other(e.to_string()))?;
+        let display = relative_path.display().to_string();
+        let old = fs::read(target.join(relative_path)).map_err(|e| {
+            log::error!("补丁对应的目标文件无法读取: {:?}: {}", relative_path, e);
+            io::Error::new(io::ErrorKind::NotFound, dict.error_patch_base_missing(&display))
+        })?;
+
+        let mut new = Vec::new();
+        bsdiff::patch(&old, &mut fs::File::open(patch_path)?, &mut new).map_err(|e| {
+            log::error!("应用补丁失败: {:?}: {}", patch_path, e);
+            io::Error::new(io::ErrorKind::InvalidData, dict.error_patch_failed(&display, &e.to_string()))
+        })?;
+        fs::write(&output_path, &new)?;
+        fs::remove_file(patch_path)?;
+        log::info!("已应用补丁: {:?} ({} -> {} 字节)", relative_path, old.len(), new.len());
+    }
+    Ok(patches.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::{Language, get_dict};
+
+    #[test]
+    fn patches_known_file() {
+        let dict = get_dict(Language::English);
+        let dir = tempfile::tempdir().unwrap();
+        let extracted = dir.path().join("extracted");
+        let target = dir.path().join("target");
+        fs::create_dir_all(extracted.join("bin")).unwrap();
+        fs::create_dir_all(target.join("bin")).unwrap();
+        let old = b"version 1 of the program binary".repeat(20);
+        let new = b"version 2 of the program binary!".repeat(20);
+        let mut patch = Vec::new();
+        bsdiff::diff(&old, &new, &mut patch).unwrap();
+        fs::write(target.join("bin/app.exe"), &old).unwrap();
+        fs::write(extracted.join("bin/app.exe.patch"), &patch).unwrap();
+
+        assert_eq!(apply_patches(&extracted, &extracted, &target, dict).unwrap(), 1);
+        assert_eq!(fs::read(extracted.join("bin/app.exe")).unwrap(), new);
+        assert!(!extracted.join("bin/app.exe.patch").exists());
+        // 目标目录中的文件由之后的复制流程替换
+        assert_eq!(fs::read(target.join("bin/app.exe")).unwrap(), old);
+    }
+
+    #[test]
+    fn missing_base_file_fails() {
+        let dict = get_dict(Language::English);
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("lib.dll.patch"), b"not a patch").unwrap();
+        let error = apply_patches(dir.path(), dir.path(), &dir.path().join("target"), dict).unwrap_err();
+        assert_eq!(error.to_string(), dict.error_patch_base_missing("lib.dll"));
+    }
+}
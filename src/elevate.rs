@@ -0,0 +1,102 @@
+// Windows上以管理员权限重新启动更新程序，用于更新安装在 Program Files 等受保护目录中的程序
+
+use std::env;
+use std::ffi::{OsStr, c_void};
+use std::io;
+use std::iter;
+use std::mem;
+use std::os::windows::ffi::OsStrExt;
+use std::ptr;
+
+use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+use windows_sys::Win32::Security::{GetTokenInformation, TOKEN_ELEVATION, TOKEN_QUERY, TokenElevation};
+use windows_sys::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+use windows_sys::Win32::UI::Shell::ShellExecuteW;
+use windows_sys::Win32::UI::WindowsAndMessaging::{MB_ICONERROR, MB_ICONINFORMATION, MB_OK, MessageBoxW, SW_SHOWNORMAL};
+
+// 转换为以0结尾的UTF-16字符串
+fn wide(s: impl AsRef<OsStr>) -> Vec<u16> {
+    s.as_ref().encode_wide().chain(iter::once(0)).collect()
+}
+
+// 按Windows命令行解析规则为参数加引号，引号前的反斜杠需要加倍
+fn quote_arg(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '"']) {
+        return arg.to_string();
+    }
+    let mut quoted = String::from('"');
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        if c == '\\' {
+            backslashes += 1;
+            continue;
+        }
+        let count = if c == '"' { backslashes * 2 + 1 } else { backslashes };
+        quoted.extend(iter::repeat_n('\\', count));
+        quoted.push(c);
+        backslashes = 0;
+    }
+    // 结尾引号前的反斜杠同样需要加倍
+    quoted.extend(iter::repeat_n('\\', backslashes * 2));
+    quoted.push('"');
+    quoted
+}
+
+// 当前进程是否已经以管理员权限运行
+pub fn is_elevated() -> bool {
+    unsafe {
+        let mut token: HANDLE = ptr::null_mut();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+            return false;
+        }
+        let mut elevation = TOKEN_ELEVATION { TokenIsElevated: 0 };
+        let mut size = 0;
+        let ok = GetTokenInformation(
+            token,
+            TokenElevation,
+            &mut elevation as *mut TOKEN_ELEVATION as *mut c_void,
+            mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut size,
+        );
+        CloseHandle(token);
+        ok != 0 && elevation.TokenIsElevated != 0
+    }
+}
+
+// 使用runas以管理员权限启动自身并传递原始参数，用户拒绝UAC提示时返回错误
+pub fn relaunch_elevated(args: &[String]) -> io::Result<()> {
+    let exe = env::current_exe()?;
+    let dir = env::current_dir()?;
+    let params = args.iter().map(|arg| quote_arg(arg)).collect::<Vec<_>>().join(" ");
+    log::info!("以管理员权限重新启动: {:?} {}", exe, params);
+
+    let verb = wide("runas");
+    let file = wide(&exe);
+    let params = wide(&params);
+    let dir = wide(&dir);
+    let result = unsafe { ShellExecuteW(ptr::null_mut(), verb.as_ptr(), file.as_ptr(), params.as_ptr(), dir.as_ptr(), SW_SHOWNORMAL) };
+    // 返回值不大于32表示启动失败
+    if result as usize <= 32 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+// 程序没有控制台窗口，使用消息框显示错误
+pub fn show_error(title: &str, message: &str) {
+    let title = wide(title);
+    let message = wide(message);
+    unsafe {
+        MessageBoxW(ptr::null_mut(), message.as_ptr(), title.as_ptr(), MB_OK | MB_ICONERROR);
+    }
+}
+
+// 使用消息框显示较长的结果，error 为真时显示错误图标
+pub fn show_message(title: &str, message: &str, error: bool) {
+    let title = wide(title);
+    let message = wide(message);
+    let icon = if error { MB_ICONERROR } else { MB_ICONINFORMATION };
+    unsafe {
+        MessageBoxW(ptr::null_mut(), message.as_ptr(), title.as_ptr(), MB_OK | icon);
+    }
+}
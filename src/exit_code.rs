@@ -0,0 +1,41 @@
+use software_updater::FailureKind;
+
+// 进程退出码，安装脚本可以根据退出码判断失败的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Success = 0,
+    // 钩子命令失败、无法结束进程、自检未通过等其他错误
+    Failed = 1,
+    // 命令行参数或配置文件无效
+    InvalidArgs = 2,
+    PackageMissing = 3,
+    // 更新包无法解压或其中缺少指定的目录
+    Extraction = 4,
+    // 复制或删除文件失败，包括继续执行模式下部分文件失败
+    Copy = 5,
+    // 签名、校验清单或复制后的文件校验失败
+    Verification = 6,
+    // 用户拒绝提升权限、在延时期间或确认界面中取消，或更新超时被中止
+    Cancelled = 7,
+}
+
+impl ExitCode {
+    // 以该退出码结束进程
+    pub fn exit(self) -> ! {
+        std::process::exit(self as i32)
+    }
+}
+
+impl From<FailureKind> for ExitCode {
+    fn from(kind: FailureKind) -> Self {
+        match kind {
+            FailureKind::InvalidConfig => ExitCode::InvalidArgs,
+            FailureKind::PackageMissing => ExitCode::PackageMissing,
+            FailureKind::Extraction => ExitCode::Extraction,
+            FailureKind::Copy => ExitCode::Copy,
+            FailureKind::Verification => ExitCode::Verification,
+            FailureKind::Cancelled => ExitCode::Cancelled,
+            FailureKind::Other => ExitCode::Failed,
+        }
+    }
+}
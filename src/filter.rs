@@ -0,0 +1,142 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use globset::{Glob, GlobBuilder, GlobMatcher, GlobSet, GlobSetBuilder};
+use walkdir::WalkDir;
+
+// 根据 --include / --exclude 模式过滤要复制的文件，模式匹配相对于压缩包内指定目录的路径
+pub struct FileFilter {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+}
+
+impl FileFilter {
+    pub fn new(include: &[String], exclude: &[String]) -> io::Result<Self> {
+        let include = if include.is_empty() {
+            None
+        } else {
+            Some(build_globset(include)?)
+        };
+        Ok(Self {
+            include,
+            exclude: build_globset(exclude)?,
+        })
+    }
+
+    // 未指定包含模式时默认包含所有文件，排除模式优先于包含模式
+    pub fn allows(&self, relative_path: &Path) -> bool {
+        let path = normalize(relative_path);
+        let included = self.include.as_ref().is_none_or(|set| set.is_match(&path));
+        included && !self.exclude.is_match(&path)
+    }
+
+    // 是否匹配排除模式
+    pub fn is_excluded(&self, relative_path: &Path) -> bool {
+        self.exclude.is_match(normalize(relative_path))
+    }
+}
+
+// 校验单个通配符模式是否有效
+pub fn validate_glob(pattern: &str) -> Result<(), String> {
+    Glob::new(pattern).map(|_| ()).map_err(|e| e.to_string())
+}
+
+// 路径中是否包含通配符
+pub(crate) fn is_glob(path: &str) -> bool {
+    path.contains(['*', '?', '[', '{'])
+}
+
+// 压缩包内路径的匹配规则，* 和 ? 不匹配路径分隔符
+fn inner_path_matcher(pattern: &str) -> io::Result<GlobMatcher> {
+    Ok(GlobBuilder::new(pattern.trim_matches('/'))
+        .literal_separator(true)
+        .build()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+        .compile_matcher())
+}
+
+// 查找根目录下与模式匹配的所有目录（相对路径），结果按名称排序
+pub(crate) fn matching_dirs(root: &Path, pattern: &str) -> io::Result<Vec<PathBuf>> {
+    let matcher = inner_path_matcher(pattern)?;
+    // 不含 ** 时只需要查找到与模式相同的层数
+    let mut walker = WalkDir::new(root).min_depth(1).sort_by_file_name();
+    if !pattern.contains("**") {
+        walker = walker.max_depth(pattern.trim_matches('/').split('/').count());
+    }
+    let mut dirs = Vec::new();
+    for entry in walker.into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+        if let Ok(relative_path) = entry.path().strip_prefix(root) && matcher.is_match(normalize(relative_path)) {
+            dirs.push(relative_path.to_path_buf());
+        }
+    }
+    Ok(dirs)
+}
+
+// 在压缩包内的目录名（使用 / 分隔）中查找与模式匹配的目录，保持原有顺序
+pub(crate) fn matching_names<'a>(names: impl IntoIterator<Item = &'a str>, pattern: &str) -> io::Result<Vec<String>> {
+    let matcher = inner_path_matcher(pattern)?;
+    Ok(names.into_iter().filter(|name| matcher.is_match(name)).map(str::to_string).collect())
+}
+
+pub(crate) fn build_globset(patterns: &[String]) -> io::Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        builder.add(glob);
+    }
+    builder.build().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+}
+
+// 统一使用正斜杠作为分隔符，使同一模式在不同平台上行为一致
+pub(crate) fn normalize(relative_path: &Path) -> String {
+    relative_path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(list: &[&str]) -> Vec<String> {
+        list.iter().map(|pattern| pattern.to_string()).collect()
+    }
+
+    #[test]
+    fn allows_everything_without_patterns() {
+        let filter = FileFilter::new(&[], &[]).unwrap();
+        assert!(filter.allows(Path::new("bin/app.exe")));
+        assert!(!filter.is_excluded(Path::new("bin/app.exe")));
+    }
+
+    #[test]
+    fn include_limits_copied_files() {
+        let filter = FileFilter::new(&patterns(&["*.dll", "bin/**"]), &[]).unwrap();
+        assert!(filter.allows(Path::new("core.dll")));
+        assert!(filter.allows(Path::new("bin/tools/app.exe")));
+        assert!(!filter.allows(Path::new("readme.txt")));
+        // 未排除的文件没有被包含时不算作排除
+        assert!(!filter.is_excluded(Path::new("readme.txt")));
+    }
+
+    #[test]
+    fn exclude_takes_precedence_over_include() {
+        let filter = FileFilter::new(&patterns(&["bin/**"]), &patterns(&["**/*.pdb"])).unwrap();
+        assert!(filter.allows(Path::new("bin/app.exe")));
+        assert!(!filter.allows(Path::new("bin/app.pdb")));
+        assert!(filter.is_excluded(Path::new("bin/app.pdb")));
+    }
+
+    #[test]
+    fn matches_windows_separators() {
+        let filter = FileFilter::new(&[], &patterns(&["logs/*"])).unwrap();
+        assert!(!filter.allows(Path::new("logs\\today.log")));
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        assert!(validate_glob("[").is_err());
+        assert!(FileFilter::new(&patterns(&["a{"]), &[]).is_err());
+    }
+}
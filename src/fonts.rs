@@ -0,0 +1,162 @@
+use std::fs;
+
+// 内置的中日韩文后备字体（由 GNU Unifont 生成的点阵字形子集，只收录界面文本用到的字符），系统中没有可用字体时也能显示界面文字
+const FALLBACK_FONT: &[u8] = include_bytes!("../assets/fallback_cjk.ttf");
+
+// 中文字体：运行时从各系统的常见位置加载
+#[cfg(windows)]
+const CHINESE_FONT_PATHS: &[&str] = &[
+    r"C:\Windows\Fonts\msyh.ttc",
+    r"C:\Windows\Fonts\msyh.ttf",
+    r"C:\Windows\Fonts\simhei.ttf",
+    r"C:\Windows\Fonts\simsun.ttc",
+];
+
+#[cfg(target_os = "macos")]
+const CHINESE_FONT_PATHS: &[&str] = &[
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/STHeiti Medium.ttc",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+];
+
+#[cfg(all(not(windows), not(target_os = "macos")))]
+const CHINESE_FONT_PATHS: &[&str] = &[
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc",
+    "/usr/share/fonts/wenquanyi/wqy-microhei/wqy-microhei.ttc",
+];
+
+// 日文字体作为补充，覆盖中文字体中缺失的日文字形
+#[cfg(windows)]
+const JAPANESE_FONT_PATHS: &[&str] = &[r"C:\Windows\Fonts\YuGothM.ttc", r"C:\Windows\Fonts\msgothic.ttc"];
+
+#[cfg(target_os = "macos")]
+const JAPANESE_FONT_PATHS: &[&str] = &[
+    "/System/Library/Fonts/ヒラギノ角ゴシック W3.ttc",
+    "/System/Library/Fonts/Hiragino Sans W3.ttc",
+];
+
+// Noto Sans CJK 已包含日文字形，这里只是缺少Noto字体时的后备
+#[cfg(all(not(windows), not(target_os = "macos")))]
+const JAPANESE_FONT_PATHS: &[&str] = &[
+    "/usr/share/fonts/opentype/ipafont-gothic/ipag.ttf",
+    "/usr/share/fonts/ipa-gothic/ipag.ttf",
+];
+
+// 韩文字体同样作为补充，中文字体不包含韩文字母，内置后备字体只包含界面文本用到的韩文字
+#[cfg(windows)]
+const KOREAN_FONT_PATHS: &[&str] = &[r"C:\Windows\Fonts\malgun.ttf", r"C:\Windows\Fonts\gulim.ttc"];
+
+#[cfg(target_os = "macos")]
+const KOREAN_FONT_PATHS: &[&str] = &["/System/Library/Fonts/AppleSDGothicNeo.ttc"];
+
+#[cfg(all(not(windows), not(target_os = "macos")))]
+const KOREAN_FONT_PATHS: &[&str] = &[
+    "/usr/share/fonts/truetype/nanum/NanumGothic.ttf",
+    "/usr/share/fonts/nanum/NanumGothic.ttf",
+    "/usr/share/fonts/naver-nanum/NanumGothic.ttf",
+];
+
+// 按顺序尝试加载字体文件，返回第一个成功读取的字体
+fn load_first(paths: &[&str]) -> Option<egui::FontData> {
+    for path in paths {
+        if let Ok(bytes) = fs::read(path) {
+            log::info!("已加载字体: {}", path);
+            return Some(egui::FontData::from_owned(bytes));
+        }
+    }
+    None
+}
+
+// 第一个可以读取的中文系统字体路径，用于自检
+pub fn system_font() -> Option<&'static str> {
+    CHINESE_FONT_PATHS.iter().copied().find(|path| fs::File::open(path).is_ok())
+}
+
+// 构建支持中文、日文和韩文显示的字体配置：优先使用系统字体，缺失的字形由内置后备字体补充
+fn definitions() -> egui::FontDefinitions {
+    let mut fonts = egui::FontDefinitions::default();
+
+    let mut names = Vec::new();
+    match load_first(CHINESE_FONT_PATHS) {
+        Some(font) => {
+            fonts.font_data.insert("system_font".to_owned(), font);
+            names.push("system_font");
+        },
+        None => log::warn!("未找到中文系统字体，使用内置的后备字体"),
+    }
+    if let Some(font) = load_first(JAPANESE_FONT_PATHS) {
+        fonts.font_data.insert("japanese_font".to_owned(), font);
+        names.push("japanese_font");
+    }
+    if let Some(font) = load_first(KOREAN_FONT_PATHS) {
+        fonts.font_data.insert("korean_font".to_owned(), font);
+        names.push("korean_font");
+    }
+
+    // 将系统字体添加到默认字体列表开头，日文和韩文字体作为后备
+    for family in fonts.families.values_mut() {
+        for (index, name) in names.iter().enumerate() {
+            family.insert(index, (*name).to_owned());
+        }
+    }
+
+    // 内置字体只包含中日韩文字形，放在列表末尾，拉丁字母仍使用egui默认字体
+    fonts.font_data.insert("fallback_cjk".to_owned(), egui::FontData::from_static(FALLBACK_FONT));
+    for family in fonts.families.values_mut() {
+        family.push("fallback_cjk".to_owned());
+    }
+    fonts
+}
+
+// 为界面配置支持中文、日文和韩文显示的字体
+pub fn configure_fonts(ctx: &egui::Context) {
+    ctx.set_fonts(definitions());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 只使用内置后备字体的字体族，确认字形来自 fallback_cjk 而不是系统字体
+    fn fallback_context() -> (egui::Context, egui::FontId) {
+        let mut fonts = definitions();
+        assert!(fonts.families.values().all(|family| family.last().map(String::as_str) == Some("fallback_cjk")));
+        let family = egui::FontFamily::Name("fallback_only".into());
+        fonts.families.insert(family.clone(), vec!["fallback_cjk".to_owned()]);
+        let ctx = egui::Context::default();
+        ctx.set_fonts(fonts);
+        // 字体在第一帧开始时才加载
+        let _ = ctx.run(Default::default(), |_| {});
+        (ctx, egui::FontId::new(14.0, family))
+    }
+
+    #[test]
+    fn fallback_font_resolves_cjk_glyphs() {
+        let (ctx, font_id) = fallback_context();
+        ctx.fonts(|fonts| {
+            for c in ['更', '新', 'ア', 'ん', '업', '。'] {
+                assert!(fonts.has_glyph(&font_id, c), "{}", c);
+            }
+        });
+    }
+
+    // 后备字体按界面文本生成子集，每种语言的界面文本中的中日韩文字符都要有字形
+    #[test]
+    fn fallback_font_covers_every_locale() {
+        let (ctx, font_id) = fallback_context();
+        let texts = [
+            include_str!("locales/zh.ftl"),
+            include_str!("locales/ja.ftl"),
+            include_str!("locales/ko.ftl"),
+        ];
+        ctx.fonts(|fonts| {
+            let missing: String = texts.iter().flat_map(|text| text.chars())
+                .filter(|c| *c as u32 >= 0x2E80 && !fonts.has_glyph(&font_id, *c))
+                .collect();
+            assert!(missing.is_empty(), "缺少字形: {}", missing);
+        });
+    }
+}
@@ -0,0 +1,188 @@
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+use software_updater::language::get_dict;
+use software_updater::{PlannedAction, UpdateEvent, run_update};
+
+use crate::config::Config;
+use crate::exit_code::ExitCode;
+use crate::{launch_start_exe, size_hint};
+
+// 输出到标准输出的JSON事件，每条消息一行
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Event<'a> {
+    Status { message: &'a str },
+    TotalFiles { total: usize },
+    Progress { phase: &'static str, current: usize, total: usize, file: &'a str },
+    Bytes { copied: u64, total: u64 },
+    PhaseProgress { phase: &'static str, fraction: f32 },
+    Plan { actions: Vec<Action<'a>> },
+    Complete,
+    PartialComplete { failures: Vec<Failure<'a>> },
+    Warning { message: &'a str },
+    Error { message: &'a str },
+    Conflict { path: &'a str },
+    Confirm { actions: Vec<Action<'a>>, total_bytes: u64 },
+    Waiting { message: &'a str },
+}
+
+// 演练模式下计划执行的操作
+#[derive(Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum Action<'a> {
+    Create { path: &'a str },
+    Overwrite { path: &'a str },
+    RenameExe { from: &'a str, to: &'a str },
+    Delete { path: &'a str },
+}
+
+// 继续执行模式下失败的文件
+#[derive(Serialize)]
+struct Failure<'a> {
+    path: String,
+    error: &'a str,
+}
+
+impl<'a> From<&'a PlannedAction> for Action<'a> {
+    fn from(action: &'a PlannedAction) -> Self {
+        match action {
+            PlannedAction::Create(path) => Action::Create { path },
+            PlannedAction::Overwrite(path) => Action::Overwrite { path },
+            PlannedAction::RenameExe(from, to) => Action::RenameExe { from, to },
+            PlannedAction::Delete(path) => Action::Delete { path },
+        }
+    }
+}
+
+impl<'a> From<&'a UpdateEvent> for Event<'a> {
+    fn from(msg: &'a UpdateEvent) -> Self {
+        match msg {
+            UpdateEvent::Status(message) => Event::Status { message },
+            UpdateEvent::TotalFiles(total) => Event::TotalFiles { total: *total },
+            UpdateEvent::Progress { phase, current, total, file } => Event::Progress { phase: phase.name(), current: *current, total: *total, file },
+            UpdateEvent::Bytes(copied, total) => Event::Bytes { copied: *copied, total: *total },
+            UpdateEvent::PhaseProgress { phase, fraction } => Event::PhaseProgress { phase: phase.name(), fraction: *fraction },
+            UpdateEvent::Plan(actions) => Event::Plan { actions: actions.iter().map(Action::from).collect() },
+            UpdateEvent::Complete => Event::Complete,
+            UpdateEvent::PartialComplete(failures) => Event::PartialComplete {
+                failures: failures.iter().map(|(path, error)| Failure { path: path.display().to_string(), error }).collect(),
+            },
+            UpdateEvent::Warning(message) => Event::Warning { message },
+            UpdateEvent::Error(_, message) => Event::Error { message },
+            UpdateEvent::Conflict(path, _) => Event::Conflict { path },
+            UpdateEvent::Confirm { actions, total_bytes, .. } => Event::Confirm {
+                actions: actions.iter().map(Action::from).collect(),
+                total_bytes: *total_bytes,
+            },
+            UpdateEvent::Waiting { message, .. } => Event::Waiting { message },
+        }
+    }
+}
+
+// 写出一行JSON，标准输出被关闭时忽略错误
+fn emit(event: &Event) {
+    match serde_json::to_string(event) {
+        Ok(line) => {
+            let mut stdout = io::stdout().lock();
+            let _ = writeln!(stdout, "{}", line).and_then(|_| stdout.flush());
+        },
+        Err(e) => log::error!("无法序列化JSON事件: {}", e),
+    }
+}
+
+// 静默模式下通过系统通知显示更新结果，通知服务不可用时只写入日志
+fn notify(summary: &str, body: &str) {
+    if let Err(e) = notify_rust::Notification::new().summary(summary).body(body).show() {
+        log::warn!("无法显示系统通知: {}", e);
+    }
+}
+
+// 静默模式下没有界面，把状态和警告写入日志（错误已由更新核心库记录）
+fn log_event(event: &UpdateEvent) {
+    match event {
+        UpdateEvent::Status(message) | UpdateEvent::Waiting { message, .. } => log::info!("{}", message),
+        UpdateEvent::Warning(message) => log::warn!("{}", message),
+        _ => {},
+    }
+}
+
+// 不显示界面，在主线程执行更新并返回进程的退出码：
+// JSON模式下把进度以JSON行输出到标准输出，静默模式下只写入日志并在结束时显示系统通知
+pub fn run(config: &Config, arg_error: Option<String>) -> ExitCode {
+    let dict = get_dict(config.update.lang);
+    if let Some(err) = arg_error {
+        if config.json {
+            emit(&Event::Error { message: &err });
+        }
+        if config.silent {
+            notify(dict.title, &err);
+        }
+        return ExitCode::InvalidArgs;
+    }
+
+    if let Some(hint) = size_hint(config) {
+        if config.json {
+            emit(&Event::Status { message: &hint });
+        } else {
+            log::info!("{}", hint);
+        }
+    }
+
+    // 启动前延时，每秒输出一次剩余时间
+    for remaining in (1..=config.delay_seconds).rev() {
+        let message = dict.status_starting_in(remaining);
+        if config.json {
+            emit(&Event::Status { message: &message });
+        } else {
+            log::info!("{}", message);
+        }
+        thread::sleep(Duration::from_secs(1));
+    }
+
+    // 更新核心库在调用线程上回调每个事件，直接写出；根据返回的结果决定退出码和结束时的通知
+    let result = run_update(&config.update, |event| {
+        // 没有界面无法询问，事件结束时丢弃回复的发送端，更新核心库按覆盖处理；--confirm 在解析参数时已被拒绝
+        if let UpdateEvent::Conflict(path, _) = &event {
+            log::warn!("无界面模式下无法询问，覆盖受保护的文件: {}", path);
+        }
+        if config.json {
+            emit(&Event::from(&event));
+        } else {
+            log_event(&event);
+        }
+    });
+    let failure = match result {
+        Err(e) => Some((ExitCode::from(e.kind()), e.to_string())),
+        Ok(summary) if !summary.failed_files.is_empty() => {
+            let message = dict.status_partial_complete(summary.failed_files.len()).trim_end_matches([':', '：']).to_string();
+            Some((ExitCode::Copy, message))
+        },
+        Ok(_) => None,
+    };
+
+    if config.silent {
+        match &failure {
+            Some((_, message)) => {
+                log::error!("静默更新失败: {}", message);
+                notify(dict.title, message);
+            },
+            None => {
+                log::info!("静默更新成功");
+                notify(dict.title, if config.update.dry_run { dict.status_dry_run_complete } else { dict.status_complete });
+            },
+        }
+    }
+
+    match failure {
+        Some((code, _)) => code,
+        None => {
+            if !config.update.dry_run {
+                launch_start_exe(config);
+            }
+            ExitCode::Success
+        },
+    }
+}
@@ -0,0 +1,53 @@
+use std::io;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use crate::language::LangDict;
+
+// 通过系统shell执行命令，命令字符串可以包含参数和重定向
+fn shell_command(command: &str) -> Command {
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    }
+    #[cfg(not(windows))]
+    {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+}
+
+// 将命令的标准输出和标准错误逐行写入日志
+fn log_output(output: &Output) {
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        log::info!("[hook stdout] {}", line);
+    }
+    for line in String::from_utf8_lossy(&output.stderr).lines() {
+        log::warn!("[hook stderr] {}", line);
+    }
+}
+
+// 在目标目录中执行钩子命令，退出码非0时返回错误；目标目录尚不存在时在当前目录执行
+pub fn run(command: &str, dir: &Path, dict: &LangDict) -> io::Result<()> {
+    let mut cmd = shell_command(command);
+    if dir.is_dir() {
+        cmd.current_dir(dir);
+    }
+    log::info!("执行钩子命令: {}（工作目录: {:?}）", command, dir);
+
+    let output = cmd.output()?;
+    log_output(&output);
+
+    // 被信号终止时没有退出码
+    let code = output.status.code();
+    log::info!("钩子命令退出码: {:?}", code);
+    if output.status.success() {
+        Ok(())
+    } else {
+        let code = code.map_or_else(|| "-".to_string(), |code| code.to_string());
+        Err(io::Error::other(dict.error_hook_failed(command, &code)))
+    }
+}
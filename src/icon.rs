@@ -0,0 +1,87 @@
+// 嵌入可执行文件中的窗口图标
+const ICON_PNG: &[u8] = include_bytes!("../assets/update.png");
+
+// 备用图标的边长（像素）和颜色
+const FALLBACK_SIZE: u32 = 32;
+const FALLBACK_BACKGROUND: [u8; 4] = [60, 130, 220, 255];
+const FALLBACK_ARROW: [u8; 4] = [255, 255, 255, 255];
+
+// 加载窗口图标：优先解码嵌入的PNG，失败时使用程序绘制的备用图标，因此总有可用的图标
+pub fn load_icon() -> egui::IconData {
+    icon_or_fallback(ICON_PNG)
+}
+
+fn icon_or_fallback(png: &[u8]) -> egui::IconData {
+    match decode_png(png) {
+        Ok(icon) => {
+            log::info!("成功解码嵌入的PNG图标: {}x{}", icon.width, icon.height);
+            icon
+        },
+        Err(e) => {
+            log::error!("无法解码嵌入的PNG图标，使用备用图标: {}", e);
+            fallback_icon()
+        }
+    }
+}
+
+// 将PNG数据解码为RGBA格式的图标
+fn decode_png(bytes: &[u8]) -> image::ImageResult<egui::IconData> {
+    let img = image::load_from_memory_with_format(bytes, image::ImageFormat::Png)?.into_rgba8();
+    let (width, height) = img.dimensions();
+    Ok(egui::IconData { rgba: img.into_raw(), width, height })
+}
+
+// 绘制备用图标：蓝色圆形背景上的白色向下箭头，不依赖任何图片解码器
+fn fallback_icon() -> egui::IconData {
+    let center = (FALLBACK_SIZE as f32 - 1.0) / 2.0;
+    let mut rgba = Vec::with_capacity((FALLBACK_SIZE * FALLBACK_SIZE * 4) as usize);
+    for y in 0..FALLBACK_SIZE {
+        for x in 0..FALLBACK_SIZE {
+            let (dx, dy) = (x as f32 - center, y as f32 - center);
+            let in_circle = dx * dx + dy * dy <= center * center;
+            // 箭头由竖线（y 为 7..17）和下方的三角形（y 为 16..25，越往下越窄）组成
+            let in_shaft = (7..17).contains(&y) && dx.abs() <= 2.5;
+            let in_head = (16..25).contains(&y) && dx.abs() <= (25 - y) as f32;
+            let pixel = match (in_circle, in_shaft || in_head) {
+                (true, true) => FALLBACK_ARROW,
+                (true, false) => FALLBACK_BACKGROUND,
+                (false, _) => [0, 0, 0, 0],
+            };
+            rgba.extend_from_slice(&pixel);
+        }
+    }
+    egui::IconData { rgba, width: FALLBACK_SIZE, height: FALLBACK_SIZE }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_embedded_icon() {
+        let icon = decode_png(ICON_PNG).unwrap();
+        assert_eq!((icon.width, icon.height), (200, 200));
+        assert_eq!(icon.rgba.len(), 200 * 200 * 4);
+        assert_eq!(load_icon().width, 200);
+    }
+
+    // 损坏的PNG无法解码，改用备用图标
+    #[test]
+    fn corrupt_png_falls_back() {
+        assert!(decode_png(&ICON_PNG[..100]).is_err());
+        let icon = icon_or_fallback(b"not a png");
+        assert_eq!((icon.width, icon.height), (FALLBACK_SIZE, FALLBACK_SIZE));
+        assert_eq!(icon.rgba, fallback_icon().rgba);
+    }
+
+    #[test]
+    fn fallback_icon_is_32_pixels() {
+        let icon = fallback_icon();
+        assert_eq!((icon.width, icon.height), (32, 32));
+        assert_eq!(icon.rgba.len(), 32 * 32 * 4);
+        // 中心是白色箭头，角落透明
+        let pixel = |x: usize, y: usize| &icon.rgba[(y * 32 + x) * 4..][..4];
+        assert_eq!(pixel(16, 12), FALLBACK_ARROW);
+        assert_eq!(pixel(0, 0), [0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,153 @@
+use std::io::{self, Write};
+
+use eframe::{App, Frame};
+use egui::{CentralPanel, Context};
+use serde::Serialize;
+use software_updater::language::{LangDict, get_dict};
+use software_updater::{InnerPath, Inspection, inspect_package};
+
+use crate::config::Config;
+use crate::exit_code::ExitCode;
+use crate::theme::{self, Palette};
+use crate::{apply_scale, fonts, icon};
+
+// JSON模式下输出的检查结果，每个条目一行
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Event<'a> {
+    Entry { path: &'a str, size: u64, dir: bool, inner: bool },
+    InnerPath { pattern: &'a str, matches: &'a [String] },
+    Error { message: &'a str },
+}
+
+fn emit(event: &Event) {
+    match serde_json::to_string(event) {
+        Ok(line) => {
+            let mut stdout = io::stdout().lock();
+            let _ = writeln!(stdout, "{}", line).and_then(|_| stdout.flush());
+        },
+        Err(e) => log::error!("无法序列化JSON事件: {}", e),
+    }
+}
+
+// 压缩包内路径的匹配说明，以及正常更新时能否唯一确定要复制的目录
+fn describe(inner: &InnerPath, dict: &LangDict) -> (String, bool) {
+    let pattern = if inner.pattern.is_empty() { "/" } else { inner.pattern.as_str() };
+    match inner.matches.as_slice() {
+        [] => (dict.error_inner_path_not_found(pattern), false),
+        [name] => (dict.inspect_inner_path(pattern, if name.is_empty() { "/" } else { name }), true),
+        names => (dict.error_inner_path_ambiguous(pattern, &names.join(", ")), false),
+    }
+}
+
+// 列出更新包的条目后退出，不解压也不复制；有压缩包内路径无法唯一确定时返回 Extraction
+pub fn run(config: &Config) -> ExitCode {
+    let dict = get_dict(config.update.lang);
+    let inspection = match inspect_package(&config.update) {
+        Ok(inspection) => inspection,
+        Err(err) => {
+            log::error!("无法检查更新包: {}", err);
+            if config.json {
+                emit(&Event::Error { message: &err.to_string() });
+            } else {
+                eprintln!("{}", err);
+            }
+            return ExitCode::from(err.kind());
+        },
+    };
+
+    let notes: Vec<(String, bool)> = inspection.inner_paths.iter().map(|inner| describe(inner, dict)).collect();
+    let total: u64 = inspection.entries.iter().map(|entry| entry.size).sum();
+    let summary = dict.inspect_summary(inspection.entries.len(), total);
+    log::info!("检查更新包 {}: {}", config.update.package_path, summary);
+
+    if config.json {
+        for entry in &inspection.entries {
+            emit(&Event::Entry { path: &entry.path, size: entry.size, dir: entry.is_dir, inner: entry.in_inner_path });
+        }
+        for inner in &inspection.inner_paths {
+            emit(&Event::InnerPath { pattern: &inner.pattern, matches: &inner.matches });
+        }
+    } else {
+        // 位于压缩包内路径中的条目以 * 标出
+        let mut report = format!("{}: {}\n", dict.inspect_title, config.update.package_path);
+        for entry in &inspection.entries {
+            let mark = if entry.in_inner_path { '*' } else { ' ' };
+            let size = if entry.is_dir { String::new() } else { dict.format_bytes(entry.size) };
+            report.push_str(&format!("{} {:>12}  {}\n", mark, size, entry.path));
+        }
+        for (note, _) in &notes {
+            report.push_str(&format!("{}\n", note));
+        }
+        report.push_str(&format!("{}\n", summary));
+        print!("{}", report);
+    }
+
+    let resolved = notes.iter().all(|(_, ok)| *ok);
+    if !config.json && !config.silent {
+        show_window(config, inspection, notes, summary);
+    }
+    if resolved { ExitCode::Success } else { ExitCode::Extraction }
+}
+
+// 在可以调整大小的窗口中显示检查结果
+fn show_window(config: &Config, inspection: Inspection, notes: Vec<(String, bool)>, summary: String) {
+    let dict = get_dict(config.update.lang);
+    let viewport_builder = egui::ViewportBuilder::default()
+        .with_inner_size([config.width, config.height])
+        .with_resizable(true)
+        .with_icon(icon::load_icon());
+    let options = eframe::NativeOptions {
+        viewport: viewport_builder,
+        centered: config.center,
+        ..Default::default()
+    };
+    let config = config.clone();
+    let result = eframe::run_native(
+        dict.inspect_title,
+        options,
+        Box::new(move |cc| {
+            fonts::configure_fonts(&cc.egui_ctx);
+            apply_scale(&cc.egui_ctx, &config);
+            let palette = theme::apply(&cc.egui_ctx, config.theme);
+            Ok(Box::new(InspectApp { dict, palette, inspection, notes, summary }))
+        }),
+    );
+    if let Err(e) = result {
+        log::error!("无法显示检查结果窗口: {}", e);
+    }
+}
+
+struct InspectApp {
+    dict: &'static LangDict,
+    palette: Palette,
+    inspection: Inspection,
+    notes: Vec<(String, bool)>,
+    summary: String,
+}
+
+impl App for InspectApp {
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.label(egui::RichText::new(self.dict.inspect_title).font(egui::FontId::proportional(18.0)).color(self.palette.title));
+            for (note, ok) in &self.notes {
+                ui.label(egui::RichText::new(note).color(if *ok { self.palette.success } else { self.palette.error }));
+            }
+            ui.label(egui::RichText::new(&self.summary).color(self.palette.muted));
+            ui.separator();
+
+            // 位于压缩包内路径中的条目高亮显示，其余条目不会被复制
+            egui::ScrollArea::both().id_source("entries").auto_shrink([false, false]).show(ui, |ui| {
+                egui::Grid::new("entries_grid").num_columns(2).striped(true).show(ui, |ui| {
+                    for entry in &self.inspection.entries {
+                        let color = if entry.in_inner_path { self.palette.title } else { self.palette.muted };
+                        let size = if entry.is_dir { String::new() } else { self.dict.format_bytes(entry.size) };
+                        ui.label(egui::RichText::new(size).font(egui::FontId::monospace(12.0)).color(color));
+                        ui.label(egui::RichText::new(&entry.path).font(egui::FontId::monospace(12.0)).color(color));
+                        ui.end_row();
+                    }
+                });
+            });
+        });
+    }
+}
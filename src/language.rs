@@ -1,64 +1,723 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
 // 语言类型枚举
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Language {
     Chinese,
     English,
+    Japanese,
+    Korean,
+    German,
+    French,
+}
+
+// 支持的语言数量，与 Language 的成员一一对应
+const LANGUAGE_COUNT: usize = 6;
+
+impl Language {
+    // 全部支持的语言，按界面中列出的顺序
+    pub const ALL: [Language; LANGUAGE_COUNT] = [
+        Language::Chinese,
+        Language::English,
+        Language::Japanese,
+        Language::Korean,
+        Language::German,
+        Language::French,
+    ];
+    
+    // 命令行中 --lang 使用的语言代码
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::Chinese => "zh",
+            Language::English => "en",
+            Language::Japanese => "ja",
+            Language::Korean => "ko",
+            Language::German => "de",
+            Language::French => "fr",
+        }
+    }
+    
+    // 语言自身的名称，在语言选择列表中显示
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Language::Chinese => "中文",
+            Language::English => "English",
+            Language::Japanese => "日本語",
+            Language::Korean => "한국어",
+            Language::German => "Deutsch",
+            Language::French => "Français",
+        }
+    }
+    
+    // 内嵌的翻译资源，每种语言一个文件；添加语言时只需要增加成员和对应的资源文件
+    fn resource(self) -> &'static str {
+        match self {
+            Language::Chinese => include_str!("locales/zh.ftl"),
+            Language::English => include_str!("locales/en.ftl"),
+            Language::Japanese => include_str!("locales/ja.ftl"),
+            Language::Korean => include_str!("locales/ko.ftl"),
+            Language::German => include_str!("locales/de.ftl"),
+            Language::French => include_str!("locales/fr.ftl"),
+        }
+    }
+}
+
+// 解析后的翻译资源：键到文本
+type Messages = HashMap<&'static str, String>;
+
+static MESSAGES: [OnceLock<Messages>; LANGUAGE_COUNT] = [const { OnceLock::new() }; LANGUAGE_COUNT];
+static DICTS: [OnceLock<LangDict>; LANGUAGE_COUNT] = [const { OnceLock::new() }; LANGUAGE_COUNT];
+
+// 解析翻译资源（Fluent格式的子集）：每条文本为 键 = 文本，多行文本从下一行开始并缩进，去掉共同的缩进；
+// # 开头的行是注释
+fn parse_resource(source: &'static str) -> Messages {
+    let mut messages = Messages::new();
+    let mut current: Option<(&'static str, String, Vec<&'static str>)> = None;
+    let mut finish = |current: Option<(&'static str, String, Vec<&'static str>)>| {
+        if let Some((key, first, mut lines)) = current {
+            while lines.last().is_some_and(|line| line.trim().is_empty()) {
+                lines.pop();
+            }
+            let indent = lines.iter().filter(|line| !line.trim().is_empty()).map(|line| line.len() - line.trim_start().len()).min().unwrap_or(0);
+            let mut text = first;
+            for line in lines {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(line.get(indent..).unwrap_or("").trim_end());
+            }
+            messages.insert(key, text);
+        }
+    };
+    for line in source.lines() {
+        if line.starts_with(' ') || (line.trim().is_empty() && current.is_some()) {
+            if let Some((_, _, lines)) = &mut current {
+                lines.push(line);
+            }
+        } else if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        } else if let Some((key, value)) = line.split_once('=') {
+            finish(current.take());
+            current = Some((key.trim(), value.trim().to_string(), Vec::new()));
+        } else {
+            log::warn!("无法解析的翻译资源行: {}", line);
+        }
+    }
+    finish(current);
+    messages
+}
+
+// 某种语言的翻译资源，第一次使用时解析
+fn messages(lang: Language) -> &'static Messages {
+    MESSAGES[lang as usize].get_or_init(|| parse_resource(lang.resource()))
 }
 
-// 语言字典结构体
-pub struct LangDict {
-    pub title: &'static str,
-    pub status_preparing: &'static str,
-    pub status_complete: &'static str,
-    pub status_failed: &'static str,
-    pub button_ok: &'static str,
-    pub lang: Language,
+// 查找文本：当前语言缺少该键时使用英文，英文也缺少时返回键名
+fn lookup(lang: Language, key: &'static str) -> &'static str {
+    match messages(lang).get(key).or_else(|| messages(Language::English).get(key)) {
+        Some(text) => text,
+        None => {
+            log::warn!("缺少界面文本: {}", key);
+            key
+        },
+    }
+}
+
+// 替换文本中的参数 { $名称 }，{ "文本" } 原样输出，方便保留首尾的空格
+fn render(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut result = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let placeable = rest[start + 1..end].trim();
+        if let Some(name) = placeable.strip_prefix('$') {
+            match args.iter().find(|(arg, _)| *arg == name) {
+                Some((_, value)) => result.push_str(&value.to_string()),
+                None => result.push_str(&rest[start..=end]),
+            }
+        } else if let Some(literal) = placeable.strip_prefix('"').and_then(|text| text.strip_suffix('"')) {
+            result.push_str(literal);
+        } else {
+            result.push_str(&rest[start..=end]);
+        }
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+// 声明界面中直接使用的固定文本：生成 LangDict 的同名字段，加载字典时从翻译资源中读取
+macro_rules! lang_dict {
+    ($($key:ident),* $(,)?) => {
+        // 语言字典结构体
+        pub struct LangDict {
+            $(pub $key: &'static str,)*
+            pub lang: Language,
+        }
+    
+        impl LangDict {
+            fn load(lang: Language) -> Self {
+                Self {
+                    $($key: lookup(lang, stringify!($key)),)*
+                    lang,
+                }
+            }
+        }
+    };
 }
 
-// 中文字典
-pub const CHINESE: LangDict = LangDict {
-    title: "软件更新",
-    status_preparing: "正在准备更新...",
-    status_complete: "软件更新已完成！",
-    status_failed: "软件更新失败！",
-    button_ok: "确定",
-    lang: Language::Chinese,
-};
-
-// 英文字典
-pub const ENGLISH: LangDict = LangDict {
-    title: "Software Update",
-    status_preparing: "Preparing update...",
-    status_complete: "Software update completed!",
-    status_failed: "Software update failed!",
-    button_ok: "OK",
-    lang: Language::English,
-};
+lang_dict! {
+    title,
+    status_preparing,
+    status_waiting_for_app,
+    status_complete,
+    status_failed,
+    button_ok,
+    button_update_now,
+    hint_cancel_delay,
+    button_overwrite,
+    button_skip,
+    button_overwrite_all,
+    button_skip_all,
+    confirm_title,
+    button_apply,
+    button_cancel,
+    button_browse,
+    button_start,
+    form_package,
+    form_target,
+    form_language,
+    label_details,
+    self_test_title,
+    check_package,
+    check_target_writable,
+    check_disk_space,
+    check_fonts,
+    check_pass,
+    check_warning,
+    check_fail,
+    inspect_title,
+    check_font_fallback,
+    status_dry_run_complete,
+    status_extracting,
+    status_copying,
+    status_rolling_back,
+    status_checkpoint_saved,
+    status_staging,
+    status_swapping,
+    phase_extract,
+    phase_verify,
+    phase_copy,
+    phase_finalize,
+    status_swap_fallback,
+    status_verifying_signature,
+    status_verifying_manifest,
+    status_applying_patches,
+    error_signature_missing,
+    error_signature_mismatch,
+    error_no_pubkey,
+    error_directory_unsigned,
+    error_elevation_declined,
+    error_update_declined,
+    error_no_package,
+    error_no_target,
+    error_password_required,
+    error_password_invalid,
+    usage,
+}
 
 impl LangDict {
+    // 按当前语言的文本格式化参数
+    fn format(&self, key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+        render(lookup(self.lang, key), args)
+    }
+    
+    // 按语言的习惯格式化字节数，1024及以上使用KB、MB等单位并保留一位小数；单位和小数点都来自翻译资源
+    pub fn format_bytes(&self, bytes: u64) -> String {
+        const UNITS: [&str; 6] = ["bytes_kb", "bytes_mb", "bytes_gb", "bytes_tb", "bytes_pb", "bytes_eb"];
+        if bytes < 1024 {
+            return self.format("bytes_small", &[("bytes", &bytes)]);
+        }
+        let mut value = bytes as f64 / 1024.0;
+        let mut unit = 0;
+        // 四舍五入后达到1024时进位到下一个单位，避免显示 1024.0 KB
+        while value >= 1023.95 && unit < UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        let value = format!("{:.1}", value).replace('.', lookup(self.lang, "decimal_separator"));
+        self.format(UNITS[unit], &[("value", &value)])
+    }
+    
+    // 按语言的习惯格式化时长，超过一分钟时使用分钟和小时
+    pub fn format_duration(&self, seconds: u64) -> String {
+        let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        if hours > 0 {
+            self.format("duration_hours", &[("hours", &hours), ("minutes", &minutes)])
+        } else if minutes > 0 {
+            self.format("duration_minutes", &[("minutes", &minutes), ("seconds", &seconds)])
+        } else {
+            self.format("duration_seconds", &[("seconds", &seconds)])
+        }
+    }
+    
+    // 获取检查模式下更新包条目数和总大小的摘要字符串
+    pub fn inspect_summary(&self, entries: usize, bytes: u64) -> String {
+        self.format("inspect_summary", &[("entries", &entries), ("bytes", &self.format_bytes(bytes))])
+    }
+    
+    // 获取检查模式下压缩包内路径匹配到的目录字符串
+    pub fn inspect_inner_path(&self, pattern: &str, resolved: &str) -> String {
+        self.format("inspect_inner_path", &[("pattern", &pattern), ("resolved", &resolved)])
+    }
+    
+    // 获取自检时磁盘空间足够的说明字符串
+    pub fn check_space_needed(&self, needed: u64) -> String {
+        self.format("check_space_needed", &[("needed", &self.format_bytes(needed))])
+    }
+    
     // 获取处理文件状态字符串
     pub fn status_processing(&self, file_name: &str) -> String {
-        match self.lang {
-            Language::Chinese => format!("正在处理: {}", file_name),
-            Language::English => format!("Processing: {}", file_name),
-        }
+        self.format("status_processing", &[("file_name", &file_name)])
+    }
+    
+    // 获取界面顶部显示的更新包名称字符串
+    pub fn label_package(&self, name: &str) -> String {
+        self.format("label_package", &[("name", &name)])
+    }
+    
+    // 获取界面顶部显示的目标路径字符串
+    pub fn label_target(&self, path: &str) -> String {
+        self.format("label_target", &[("path", &path)])
+    }
+    
+    // 获取已处理文件数的字符串
+    pub fn status_file_count(&self, current: usize, total: usize) -> String {
+        self.format("status_file_count", &[("current", &current), ("total", &total)])
+    }
+    
+    // 获取完成界面中复制、跳过和失败的文件数字符串
+    pub fn summary_counts(&self, copied: usize, skipped: usize, failed: usize) -> String {
+        self.format("summary_counts", &[("copied", &copied), ("skipped", &skipped), ("failed", &failed)])
+    }
+    
+    // 获取完成界面中写入的总大小和用时字符串
+    pub fn summary_bytes(&self, bytes: u64, seconds: u64) -> String {
+        self.format("summary_bytes", &[("bytes", &self.format_bytes(bytes)), ("seconds", &self.format_duration(seconds))])
     }
     
     // 获取启动延时状态字符串
     pub fn status_starting_in(&self, seconds: u64) -> String {
-        match self.lang {
-            Language::Chinese => format!("{}秒后开始更新...", seconds),
-            Language::English => format!("Starting in {} seconds...", seconds),
+        self.format("status_starting_in", &[("seconds", &self.format_duration(seconds))])
+    }
+    
+    // 获取本次更新将写入的总大小的提示字符串
+    pub fn hint_update_size(&self, bytes: u64) -> String {
+        self.format("hint_update_size", &[("bytes", &self.format_bytes(bytes))])
+    }
+    
+    // 获取演练计划摘要字符串
+    pub fn plan_summary(&self, count: usize) -> String {
+        self.format("plan_summary", &[("count", &count)])
+    }
+    
+    // 获取确认界面中将要写入的文件数、总大小和删除的文件数字符串
+    pub fn confirm_summary(&self, files: usize, bytes: u64, deleted: usize) -> String {
+        self.format("confirm_summary", &[("files", &files), ("size", &self.format_bytes(bytes)), ("deleted", &deleted)])
+    }
+    
+    // 获取计划新建文件字符串
+    pub fn plan_create(&self, path: &str) -> String {
+        self.format("plan_create", &[("path", &path)])
+    }
+    
+    // 获取计划覆盖文件字符串
+    pub fn plan_overwrite(&self, path: &str) -> String {
+        self.format("plan_overwrite", &[("path", &path)])
+    }
+    
+    // 获取计划重命名可执行文件字符串
+    pub fn plan_rename_exe(&self, from: &str, to: &str) -> String {
+        self.format("plan_rename_exe", &[("from", &from), ("to", &to)])
+    }
+    
+    // 获取更新包中未找到指定目录的错误字符串
+    pub fn error_inner_path_not_found(&self, inner_path: &str) -> String {
+        self.format("error_inner_path_not_found", &[("inner_path", &inner_path)])
+    }
+    
+    // 获取更新包（或压缩包内指定目录）中没有任何文件的错误字符串
+    pub fn error_package_empty(&self, inner_path: &str) -> String {
+        if inner_path.is_empty() {
+            self.format("error_package_empty", &[])
+        } else {
+            self.format("error_package_empty_in", &[("inner_path", &inner_path)])
         }
     }
+    
+    // 获取无法确定目标磁盘的错误字符串
+    pub fn error_volume_unknown(&self, path: &str) -> String {
+        self.format("error_volume_unknown", &[("path", &path)])
+    }
+    
+    // 获取目标目录中缺少必须存在的文件的错误字符串
+    pub fn error_expected_file_missing(&self, name: &str, target: &str) -> String {
+        self.format("error_expected_file_missing", &[("name", &name), ("target", &target)])
+    }
+    
+    // 获取目标目录不可写的错误字符串
+    pub fn error_target_not_writable(&self, path: &str, err: &str) -> String {
+        self.format("error_target_not_writable", &[("path", &path), ("err", &err)])
+    }
+    
+    // 获取目标磁盘空间不足的错误字符串
+    pub fn error_insufficient_space(&self, needed: u64, available: u64) -> String {
+        self.format("error_insufficient_space", &[("needed", &self.format_bytes(needed)), ("available", &self.format_bytes(available))])
+    }
+    
+    // 获取解压时临时目录所在磁盘已满的错误字符串
+    pub fn error_temp_space_exhausted(&self, path: &str) -> String {
+        self.format("error_temp_space_exhausted", &[("path", &path)])
+    }
+    
+    // 获取更新包不存在的错误字符串
+    pub fn error_package_not_found(&self, path: &str) -> String {
+        self.format("error_package_not_found", &[("path", &path)])
+    }
+    
+    // 获取更新包中 update.json 无效的错误字符串
+    pub fn error_invalid_package_metadata(&self, reason: &str) -> String {
+        self.format("error_invalid_package_metadata", &[("reason", &reason)])
+    }
+    
+    // 获取无法从标准输入读取参数的错误字符串
+    pub fn error_stdin_args(&self, reason: &str) -> String {
+        self.format("error_stdin_args", &[("reason", &reason)])
+    }
+    
+    // 获取更新包中的目标路径引用了未定义的环境变量的错误字符串
+    pub fn error_undefined_variable(&self, target: &str, name: &str) -> String {
+        self.format("error_undefined_variable", &[("target", &target), ("name", &name)])
+    }
+    
+    // 获取更新程序版本低于更新包要求的错误字符串
+    pub fn error_updater_too_old(&self, required: &str, current: &str) -> String {
+        self.format("error_updater_too_old", &[("required", &required), ("current", &current)])
+    }
+    
+    // 获取版本号无效的错误字符串
+    pub fn error_invalid_version(&self, value: &str) -> String {
+        self.format("error_invalid_version", &[("value", &value)])
+    }
+    
+    // 获取更新包版本不高于已安装版本的错误字符串
+    pub fn error_not_newer(&self, package: &str, current: &str) -> String {
+        self.format("error_not_newer", &[("package", &package), ("current", &current)])
+    }
+    
+    // 获取已安装版本低于更新包要求的最低版本的错误字符串
+    pub fn error_current_too_old(&self, current: &str, min: &str) -> String {
+        self.format("error_current_too_old", &[("current", &current), ("min", &min)])
+    }
+    
+    // 获取更新包不是有效压缩包的错误字符串
+    pub fn error_invalid_package(&self, err: &str) -> String {
+        self.format("error_invalid_package", &[("err", &err)])
+    }
+    
+    // 获取更新包不是有效tar.gz文件的错误字符串
+    pub fn error_invalid_tar_package(&self, err: &str) -> String {
+        self.format("error_invalid_tar_package", &[("err", &err)])
+    }
+    
+    // 获取指定按目录处理但更新包不是目录的错误字符串
+    pub fn error_package_not_directory(&self, path: &str) -> String {
+        self.format("error_package_not_directory", &[("path", &path)])
+    }
+    
+    // 获取自动关闭秒数无效的错误字符串
+    pub fn error_invalid_auto_close(&self, value: &str) -> String {
+        self.format("error_invalid_auto_close", &[("value", &value)])
+    }
+    
+    // 获取延时参数无效的错误字符串
+    pub fn error_invalid_delay(&self, value: &str) -> String {
+        self.format("error_invalid_delay", &[("value", &value)])
+    }
+    
+    // 获取多余参数的错误字符串
+    pub fn error_unexpected_argument(&self, arg: &str) -> String {
+        self.format("error_unexpected_argument", &[("arg", &arg)])
+    }
+    
+    // 获取选项缺少参数值的错误字符串
+    pub fn error_missing_value(&self, flag: &str) -> String {
+        self.format("error_missing_value", &[("flag", &flag)])
+    }
+    
+    // 获取语言选项无效的错误字符串
+    pub fn error_invalid_language(&self, value: &str) -> String {
+        self.format("error_invalid_language", &[("value", &value)])
+    }
+    
+    // 获取未知选项的错误字符串
+    pub fn error_unknown_option(&self, flag: &str) -> String {
+        self.format("error_unknown_option", &[("flag", &flag)])
+    }
+    
+    // 获取传输速度（字节/秒）和预计剩余时间字符串
+    pub fn status_transfer(&self, bytes_per_second: u64, eta_seconds: u64) -> String {
+        self.format("status_transfer", &[("bytes_per_second", &self.format_bytes(bytes_per_second)), ("eta_seconds", &self.format_duration(eta_seconds))])
+    }
+    
+    // 获取并发数参数无效的错误字符串
+    pub fn error_invalid_jobs(&self, value: &str) -> String {
+        self.format("error_invalid_jobs", &[("value", &value)])
+    }
+    
+    // 获取主题名称无效的错误字符串
+    pub fn error_invalid_theme(&self, value: &str) -> String {
+        self.format("error_invalid_theme", &[("value", &value)])
+    }
+    
+    // 获取配置文件无效的错误字符串
+    pub fn error_invalid_config(&self, path: &str, reason: &str) -> String {
+        self.format("error_invalid_config", &[("path", &path), ("reason", &reason)])
+    }
+    
+    // 获取界面缩放比例无效的错误字符串
+    pub fn error_invalid_scale(&self, value: &str) -> String {
+        self.format("error_invalid_scale", &[("value", &value)])
+    }
+    
+    // 获取窗口大小无效的错误字符串
+    pub fn error_invalid_size(&self, value: &str) -> String {
+        self.format("error_invalid_size", &[("value", &value)])
+    }
+    
+    // 获取通配符模式无效的错误字符串
+    pub fn error_invalid_glob(&self, pattern: &str, reason: &str) -> String {
+        self.format("error_invalid_glob", &[("pattern", &pattern), ("reason", &reason)])
+    }
+    
+    // 获取复制文件并跳过部分文件的状态字符串
+    pub fn status_copying_skipped(&self, excluded: usize, unchanged: usize) -> String {
+        let excluded = (excluded > 0).then(|| self.format("status_copying_excluded", &[("excluded", &excluded)]));
+        let unchanged = (unchanged > 0).then(|| self.format("status_copying_unchanged", &[("unchanged", &unchanged)]));
+        let details: Vec<String> = excluded.into_iter().chain(unchanged).collect();
+        self.format("status_copying_details", &[("details", &details.join(&self.format("list_separator", &[])))])
+    }
+    
+    // 获取文件校验失败的错误字符串
+    pub fn error_verify_failed(&self, file_name: &str) -> String {
+        self.format("error_verify_failed", &[("file_name", &file_name)])
+    }
+    
+    // 获取重试次数参数无效的错误字符串
+    pub fn error_invalid_retries(&self, value: &str) -> String {
+        self.format("error_invalid_retries", &[("value", &value)])
+    }
+    
+    // 获取平铺复制时文件名重复的错误字符串
+    pub fn error_flatten_collision(&self, name: &str, first: &str, second: &str) -> String {
+        self.format("error_flatten_collision", &[("name", &name), ("first", &first), ("second", &second)])
+    }
+    
+    // 获取两个选项不能同时使用的错误字符串
+    pub fn error_incompatible_options(&self, first: &str, second: &str) -> String {
+        self.format("error_incompatible_options", &[("first", &first), ("second", &second)])
+    }
+    
+    // 获取目标子目录无效的错误字符串
+    pub fn error_invalid_target_subdir(&self, value: &str) -> String {
+        self.format("error_invalid_target_subdir", &[("value", &value)])
+    }
+    
+    // 获取暂存后缀无效的错误字符串
+    pub fn error_invalid_suffix(&self, value: &str) -> String {
+        self.format("error_invalid_suffix", &[("value", &value)])
+    }
+    
+    // 获取超时时间参数无效的错误字符串
+    pub fn error_invalid_timeout(&self, value: &str) -> String {
+        self.format("error_invalid_timeout", &[("value", &value)])
+    }
+    
+    // 获取长时间没有进度、正在中止更新的状态字符串
+    pub fn status_timed_out(&self, seconds: u64) -> String {
+        self.format("status_timed_out", &[("seconds", &seconds)])
+    }
+    
+    // 获取更新超时的错误字符串
+    pub fn error_timed_out(&self, seconds: u64) -> String {
+        self.format("error_timed_out", &[("seconds", &seconds)])
+    }
+    
+    // 获取文件操作重试中的状态字符串
+    pub fn status_retrying(&self, file_name: &str, attempt: u32, max: u32) -> String {
+        self.format("status_retrying", &[("file_name", &file_name), ("attempt", &attempt), ("max", &max)])
+    }
+    
+    // 获取计划删除文件字符串
+    pub fn plan_delete(&self, path: &str) -> String {
+        self.format("plan_delete", &[("path", &path)])
+    }
+    
+    // 获取删除旧文件的状态字符串
+    pub fn status_pruning(&self, file_name: &str) -> String {
+        self.format("status_pruning", &[("file_name", &file_name)])
+    }
+    
+    // 获取正在更新某个目录映射的状态字符串
+    pub fn status_mapping(&self, index: usize, total: usize, inner: &str, target: &str) -> String {
+        let inner = if inner.is_empty() { "/" } else { inner };
+        self.format("status_mapping", &[("index", &index), ("total", &total), ("inner", &inner), ("target", &target)])
+    }
+    
+    // 获取目录映射参数无效的错误字符串
+    pub fn error_invalid_map(&self, value: &str) -> String {
+        self.format("error_invalid_map", &[("value", &value)])
+    }
+    
+    // 获取部分文件失败时的状态字符串
+    pub fn status_partial_complete(&self, count: usize) -> String {
+        self.format("status_partial_complete", &[("count", &count)])
+    }
+    
+    // 获取等待被占用的文件释放的状态字符串
+    pub fn status_waiting_for_locked(&self, count: usize, first: &str) -> String {
+        self.format("status_waiting_for_locked", &[("count", &count), ("first", &first)])
+    }
+    
+    // 获取文件被占用而中止更新的错误字符串
+    pub fn error_files_locked(&self, count: usize, first: &str) -> String {
+        self.format("error_files_locked", &[("count", &count), ("first", &first)])
+    }
+    
+    // 获取文件被占用处理方式无效的错误字符串
+    pub fn error_invalid_lock_policy(&self, value: &str) -> String {
+        self.format("error_invalid_lock_policy", &[("value", &value)])
+    }
+    
+    // 获取更新包格式无效的错误字符串
+    pub fn error_invalid_format(&self, value: &str) -> String {
+        self.format("error_invalid_format", &[("value", &value)])
+    }
+    
+    // 获取执行钩子命令的状态字符串
+    pub fn status_running_hook(&self, command: &str) -> String {
+        self.format("status_running_hook", &[("command", &command)])
+    }
+    
+    // 获取钩子命令执行失败的错误字符串
+    pub fn error_hook_failed(&self, command: &str, code: &str) -> String {
+        self.format("error_hook_failed", &[("command", &command), ("code", &code)])
+    }
+    
+    // 获取无效公钥文件的错误字符串
+    pub fn error_invalid_pubkey(&self, path: &str) -> String {
+        self.format("error_invalid_pubkey", &[("path", &path)])
+    }
+    
+    // 获取无效签名文件的错误字符串
+    pub fn error_invalid_signature_file(&self, path: &str) -> String {
+        self.format("error_invalid_signature_file", &[("path", &path)])
+    }
+    
+    // 获取跳过不安全压缩包条目的警告字符串
+    pub fn warning_unsafe_entries(&self, count: usize) -> String {
+        self.format("warning_unsafe_entries", &[("count", &count)])
+    }
+    
+    // 获取指定的更新包格式与检测结果不一致的警告字符串
+    pub fn warning_format_mismatch(&self, format: &str, detected: &str) -> String {
+        self.format("warning_format_mismatch", &[("format", &format), ("detected", &detected)])
+    }
+    
+    // 获取询问是否覆盖受保护文件的字符串
+    pub fn prompt_conflict(&self, path: &str) -> String {
+        self.format("prompt_conflict", &[("path", &path)])
+    }
+    
+    // 获取跳过受保护文件的警告字符串
+    pub fn warning_protected_skipped(&self, count: usize) -> String {
+        self.format("warning_protected_skipped", &[("count", &count)])
+    }
+    
+    // 获取保留了比更新包中更新的目标文件的警告字符串
+    pub fn warning_newer_kept(&self, count: usize) -> String {
+        self.format("warning_newer_kept", &[("count", &count)])
+    }
+    
+    // 获取更新包删除失败的警告字符串
+    pub fn warning_package_not_removed(&self, path: &str, reason: &str) -> String {
+        self.format("warning_package_not_removed", &[("path", &path), ("reason", &reason)])
+    }
+    
+    // 获取需要重启才能完成更新的警告字符串
+    pub fn warning_restart_required(&self, count: usize) -> String {
+        self.format("warning_restart_required", &[("count", &count)])
+    }
+    
+    // 获取无法创建审计文件的错误字符串
+    pub fn error_audit_not_opened(&self, path: &str, reason: &str) -> String {
+        self.format("error_audit_not_opened", &[("path", &path), ("reason", &reason)])
+    }
+    
+    // 获取无法写入更新回执的警告字符串
+    pub fn warning_receipt_not_written(&self, target: &str, reason: &str) -> String {
+        self.format("warning_receipt_not_written", &[("target", &target), ("reason", &reason)])
+    }
+    
+    // 获取压缩包内路径模式匹配到多个目录的错误字符串
+    pub fn error_inner_path_ambiguous(&self, pattern: &str, matches: &str) -> String {
+        self.format("error_inner_path_ambiguous", &[("pattern", &pattern), ("matches", &matches)])
+    }
+    
+    // 获取压缩包内路径不安全的错误字符串
+    pub fn error_unsafe_inner_path(&self, path: &str) -> String {
+        self.format("error_unsafe_inner_path", &[("path", &path)])
+    }
+    
+    // 获取补丁对应的目标文件不存在的错误字符串
+    pub fn error_patch_base_missing(&self, file: &str) -> String {
+        self.format("error_patch_base_missing", &[("file", &file)])
+    }
+    
+    // 获取应用补丁失败的错误字符串
+    pub fn error_patch_failed(&self, file: &str, err: &str) -> String {
+        self.format("error_patch_failed", &[("file", &file), ("err", &err)])
+    }
+    
+    // 获取校验清单中的文件缺失的错误字符串
+    pub fn error_manifest_missing(&self, file: &str) -> String {
+        self.format("error_manifest_missing", &[("file", &file)])
+    }
+    
+    // 获取文件摘要与校验清单不一致的错误字符串
+    pub fn error_manifest_mismatch(&self, file: &str) -> String {
+        self.format("error_manifest_mismatch", &[("file", &file)])
+    }
+    
+    // 获取结束进程的状态字符串
+    pub fn status_killing_process(&self, name: &str, count: usize) -> String {
+        self.format("status_killing_process", &[("name", &name), ("count", &count)])
+    }
+    
+    // 获取进程未能结束的错误字符串
+    pub fn error_kill_process_failed(&self, name: &str) -> String {
+        self.format("error_kill_process_failed", &[("name", &name)])
+    }
 }
 
-// 根据语言类型获取字典
+// 根据语言类型获取字典，第一次使用时加载
 pub fn get_dict(lang: Language) -> &'static LangDict {
-    match lang {
-        Language::Chinese => &CHINESE,
-        Language::English => &ENGLISH,
-    }
+    DICTS[lang as usize].get_or_init(|| LangDict::load(lang))
 }
 
 // 根据字符串解析语言类型
@@ -66,6 +725,209 @@ pub fn parse_language(lang_str: &str) -> Option<Language> {
     match lang_str.to_lowercase().as_str() {
         "zh" | "chinese" => Some(Language::Chinese),
         "en" | "english" => Some(Language::English),
+        "ja" | "japanese" => Some(Language::Japanese),
+        "ko" | "korean" => Some(Language::Korean),
+        "de" | "german" => Some(Language::German),
+        "fr" | "french" => Some(Language::French),
         _ => None,
     }
 }
+
+// 根据区域设置字符串映射语言类型，按语言代码前缀（zh、ja、ko、de、fr）映射，其余为英文
+pub fn language_from_locale(locale: &str) -> Language {
+    let locale = locale.to_lowercase();
+    if locale.starts_with("zh") {
+        Language::Chinese
+    } else if locale.starts_with("ja") {
+        Language::Japanese
+    } else if locale.starts_with("ko") {
+        Language::Korean
+    } else if locale.starts_with("de") {
+        Language::German
+    } else if locale.starts_with("fr") {
+        Language::French
+    } else {
+        Language::English
+    }
+}
+
+// 检测操作系统的区域设置对应的语言，无法检测时默认为中文
+pub fn detect_system_language() -> Language {
+    match sys_locale::get_locale() {
+        Some(locale) => {
+            log::info!("检测到系统区域设置: {}", locale);
+            language_from_locale(&locale)
+        },
+        None => Language::Chinese,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locale_maps_to_language() {
+        let cases = [
+            ("zh-CN", Language::Chinese),
+            ("zh_TW.UTF-8", Language::Chinese),
+            ("en-US", Language::English),
+            ("ja-JP", Language::Japanese),
+            ("ko_KR", Language::Korean),
+            ("de-AT", Language::German),
+            ("FR-ca", Language::French),
+            ("es-ES", Language::English),
+            ("C", Language::English),
+            ("", Language::English),
+        ];
+        for (locale, expected) in cases {
+            assert_eq!(language_from_locale(locale), expected, "{}", locale);
+        }
+    }
+
+    // 每种语言的每个带参数的文本都能替换全部参数，翻译中的参数名与代码不一致时会留下 { $名称 }
+    #[test]
+    fn accessors_fill_every_placeholder() {
+        for lang in Language::ALL {
+            let dict = get_dict(lang);
+            let texts = [
+                dict.format_bytes(1),
+                dict.format_duration(1),
+                dict.inspect_summary(1, 1),
+                dict.inspect_inner_path("x", "x"),
+                dict.check_space_needed(1),
+                dict.status_processing("x"),
+                dict.label_package("x"),
+                dict.label_target("x"),
+                dict.status_file_count(1, 1),
+                dict.summary_counts(1, 1, 1),
+                dict.summary_bytes(1, 1),
+                dict.status_starting_in(1),
+                dict.hint_update_size(1),
+                dict.plan_summary(1),
+                dict.confirm_summary(1, 1, 1),
+                dict.plan_create("x"),
+                dict.plan_overwrite("x"),
+                dict.plan_rename_exe("x", "x"),
+                dict.error_inner_path_not_found("x"),
+                dict.error_package_empty("x"),
+                dict.error_volume_unknown("x"),
+                dict.error_expected_file_missing("x", "x"),
+                dict.error_target_not_writable("x", "x"),
+                dict.error_insufficient_space(1, 1),
+                dict.error_temp_space_exhausted("x"),
+                dict.error_package_not_found("x"),
+                dict.error_invalid_package_metadata("x"),
+                dict.error_stdin_args("x"),
+                dict.error_undefined_variable("x", "x"),
+                dict.error_updater_too_old("x", "x"),
+                dict.error_invalid_version("x"),
+                dict.error_not_newer("x", "x"),
+                dict.error_current_too_old("x", "x"),
+                dict.error_invalid_package("x"),
+                dict.error_invalid_tar_package("x"),
+                dict.error_package_not_directory("x"),
+                dict.error_invalid_auto_close("x"),
+                dict.error_invalid_delay("x"),
+                dict.error_unexpected_argument("x"),
+                dict.error_missing_value("x"),
+                dict.error_invalid_language("x"),
+                dict.error_unknown_option("x"),
+                dict.status_transfer(1, 1),
+                dict.error_invalid_jobs("x"),
+                dict.error_invalid_theme("x"),
+                dict.error_invalid_config("x", "x"),
+                dict.error_invalid_scale("x"),
+                dict.error_invalid_size("x"),
+                dict.error_invalid_glob("x", "x"),
+                dict.status_copying_skipped(1, 1),
+                dict.error_verify_failed("x"),
+                dict.error_invalid_retries("x"),
+                dict.error_flatten_collision("x", "x", "x"),
+                dict.error_incompatible_options("x", "x"),
+                dict.error_invalid_target_subdir("x"),
+                dict.error_invalid_suffix("x"),
+                dict.error_invalid_timeout("x"),
+                dict.status_timed_out(1),
+                dict.error_timed_out(1),
+                dict.status_retrying("x", 1, 1),
+                dict.plan_delete("x"),
+                dict.status_pruning("x"),
+                dict.status_mapping(1, 1, "x", "x"),
+                dict.error_invalid_map("x"),
+                dict.status_partial_complete(1),
+                dict.status_waiting_for_locked(1, "x"),
+                dict.error_files_locked(1, "x"),
+                dict.error_invalid_lock_policy("x"),
+                dict.error_invalid_format("x"),
+                dict.status_running_hook("x"),
+                dict.error_hook_failed("x", "x"),
+                dict.error_invalid_pubkey("x"),
+                dict.error_invalid_signature_file("x"),
+                dict.warning_unsafe_entries(1),
+                dict.warning_format_mismatch("x", "x"),
+                dict.prompt_conflict("x"),
+                dict.warning_protected_skipped(1),
+                dict.warning_newer_kept(1),
+                dict.warning_package_not_removed("x", "x"),
+                dict.warning_restart_required(1),
+                dict.error_audit_not_opened("x", "x"),
+                dict.warning_receipt_not_written("x", "x"),
+                dict.error_inner_path_ambiguous("x", "x"),
+                dict.error_unsafe_inner_path("x"),
+                dict.error_patch_base_missing("x"),
+                dict.error_patch_failed("x", "x"),
+                dict.error_manifest_missing("x"),
+                dict.error_manifest_mismatch("x"),
+                dict.status_killing_process("x", 1),
+                dict.error_kill_process_failed("x"),
+            ];
+            for text in texts {
+                assert!(!text.contains("{ $") && !text.contains("{$"), "{:?}: {}", lang, text);
+            }
+        }
+    }
+
+    #[test]
+    fn formats_bytes_per_language() {
+        let cases = [
+            (Language::Chinese, ["0 字节", "1023 字节", "1.0 KB", "1.0 MB", "1.5 GB", "16.0 EB"]),
+            (Language::English, ["0 B", "1023 B", "1.0 KB", "1.0 MB", "1.5 GB", "16.0 EB"]),
+            (Language::Japanese, ["0 バイト", "1023 バイト", "1.0 KB", "1.0 MB", "1.5 GB", "16.0 EB"]),
+            (Language::Korean, ["0바이트", "1023바이트", "1.0 KB", "1.0 MB", "1.5 GB", "16.0 EB"]),
+            (Language::German, ["0 B", "1023 B", "1,0 KB", "1,0 MB", "1,5 GB", "16,0 EB"]),
+            (Language::French, ["0 o", "1023 o", "1,0 Ko", "1,0 Mo", "1,5 Go", "16,0 Eo"]),
+        ];
+        let values = [0, 1023, 1024, 1024 * 1024, 3 << 29, u64::MAX];
+        for (lang, expected) in cases {
+            let dict = get_dict(lang);
+            for (bytes, text) in values.into_iter().zip(expected) {
+                assert_eq!(dict.format_bytes(bytes), text, "{:?} {}", lang, bytes);
+            }
+        }
+        // 四舍五入后达到1024时进位到下一个单位
+        assert_eq!(get_dict(Language::English).format_bytes(1024 * 1024 - 1), "1.0 MB");
+    }
+    
+    #[test]
+    fn formats_duration_boundaries() {
+        let dict = get_dict(Language::English);
+        assert_eq!(dict.format_duration(0), "0 s");
+        assert_eq!(dict.format_duration(59), "59 s");
+        assert_eq!(dict.format_duration(60), "1 min 0 s");
+        assert_eq!(dict.format_duration(3600), "1 h 0 min");
+        assert_eq!(get_dict(Language::Chinese).format_duration(3725), "1小时2分");
+    }
+
+    // 英文资源中的每个键在其他语言中都有翻译，缺少的键会在界面中回退为英文
+    #[test]
+    fn every_locale_has_every_english_key() {
+        let english = parse_resource(Language::English.resource());
+        for lang in Language::ALL {
+            let messages = parse_resource(lang.resource());
+            let mut missing: Vec<_> = english.keys().filter(|key| !messages.contains_key(*key)).collect();
+            missing.sort();
+            assert!(missing.is_empty(), "{} 缺少: {:?}", lang.code(), missing);
+        }
+    }
+}
@@ -0,0 +1,445 @@
+// 软件更新核心库：解压更新包并复制到目标目录，不依赖GUI。
+//
+// 其他程序可以直接调用 run_update 嵌入更新功能，通过回调接收进度事件：
+//
+//     let config = UpdateConfig {
+//         package_path: "update.zip".to_string(),
+//         target_path: Some("C:\\target".to_string()),
+//         ..Default::default()
+//     };
+//     match software_updater::run_update(&config, |event| println!("{:?}", event)) {
+//         Ok(summary) => println!("已复制 {} 个文件", summary.files_copied),
+//         Err(e) => eprintln!("更新失败: {}", e),
+//     }
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use thiserror::Error;
+
+mod audit;
+mod checkpoint;
+mod conflict;
+mod dedup;
+mod delta;
+mod filter;
+mod hooks;
+pub mod language;
+mod locks;
+mod long_path;
+pub mod logging;
+mod manifest;
+mod process;
+#[cfg(windows)]
+mod reboot;
+mod receipt;
+mod rollback;
+pub mod self_update;
+mod signature;
+#[cfg(test)]
+mod test_util;
+mod update;
+mod watchdog;
+
+pub use filter::validate_glob;
+use language::{Language, get_dict};
+
+// 更新包根目录下描述默认路径和启动程序的元数据文件，不会被复制到目标目录
+pub const PACKAGE_METADATA_NAME: &str = "update.json";
+// 更新包根目录下只记录目标路径的文本文件，update.json 中没有目标路径时读取，同样不会被复制
+pub const PACKAGE_TARGET_NAME: &str = "target.txt";
+
+// 更新操作的配置
+#[derive(Debug, Clone)]
+pub struct UpdateConfig {
+    // 更新包（ZIP文件）路径
+    pub package_path: String,
+    // 压缩包内要复制的目录，为空时复制整个压缩包
+    pub zip_inner_path: String,
+    pub target_path: Option<String>,
+    // 压缩包内目录到目标目录的映射，按顺序复制；不为空时代替 zip_inner_path 和 target_path
+    pub mappings: Vec<(String, String)>,
+    // 状态和错误信息使用的语言
+    pub lang: Language,
+    pub dry_run: bool,
+    // 解压并确定要执行的操作后暂停，调用方确认后才修改目标目录
+    pub confirm: bool,
+    pub verify: bool,
+    pub prune: bool,
+    // 在临时目录中组装新版本后整体交换目录
+    pub atomic: bool,
+    // 复制和解压时保留文件的修改时间
+    pub preserve_mtime: bool,
+    // 跳过与目标目录中内容相同的文件
+    pub skip_unchanged: bool,
+    // 目标文件的修改时间晚于更新包中的文件时不覆盖，避免降级本地修补过的文件
+    pub skip_newer: bool,
+    // 把更新包中的 .patch 文件作为目标目录中现有文件的二进制补丁（bsdiff格式）应用
+    pub delta: bool,
+    // 不保留子目录，把压缩包内路径中的全部文件直接复制到目标目录顶层，文件名重复时中止
+    pub flatten: bool,
+    // 要覆盖的文件正被其他进程占用时的处理方式
+    pub on_locked: LockPolicy,
+    // 更新成功后保留源更新包（如位于只读介质或多台机器共用的位置）
+    pub keep_package: bool,
+    // 每个写入的文件和所在目录都同步到磁盘后才报告完成，防止断电后文件损坏
+    pub fsync: bool,
+    // 更新成功后在每个目标目录中写入记录已安装文件的回执（update-receipt.json）
+    pub receipt: bool,
+    // 把每个文件操作（复制、跳过、删除、回滚等）逐行写入的CSV审计文件
+    pub audit_csv: Option<String>,
+    // 解压更新包和备份被覆盖文件的临时目录所在位置，默认为系统临时目录；系统盘空间不足时可以改到其他磁盘
+    pub temp_dir: Option<String>,
+    // 强制指定的更新包格式，为None时自动检测
+    pub format: Option<PackageFormat>,
+    // Unix上更新包中互为硬链接的文件只复制一次，其余路径在目标目录中创建硬链接
+    pub preserve_hardlinks: bool,
+    // 同一次更新中内容相同（大小和SHA-256摘要一致）的文件只从更新包复制一次，后续的目标位置从第一个副本复制
+    pub dedup: bool,
+    // 在目标目录中记录已复制的文件（update-checkpoint.json），失败时不回滚，重新运行时跳过已完成的文件
+    pub resumable: bool,
+    // Windows上覆盖正在运行的更新程序时改为写入的暂存后缀，以及是否启用暂存（更新程序不在目标目录中时可以关闭）
+    pub self_suffix: String,
+    pub self_stage: bool,
+    // 复制到每个目标目录下的子目录（如 versions/1.2.0），不存在时创建，用于并存安装多个版本
+    pub target_subdir: Option<String>,
+    // 并发复制数和文件操作失败时的重试次数
+    pub jobs: usize,
+    pub retries: u32,
+    // 单个文件复制失败时继续复制其余文件，最后汇总报告失败的文件
+    pub continue_on_error: bool,
+    // 超过该时间没有新的进度时中止更新并回滚
+    pub timeout: Option<Duration>,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    // 覆盖只读文件或匹配 --protect 模式的文件前询问调用方，未启用时直接覆盖
+    pub confirm_overwrite: bool,
+    pub protect: Vec<String>,
+    // 更新前目标目录（有多个时为第一个）中必须已存在的文件，防止更新到错误的目录
+    pub expect_files: Vec<String>,
+    // 解压前和复制完成后在目标目录中执行的命令
+    pub pre_hook: Option<String>,
+    pub post_hook: Option<String>,
+    // ed25519公钥文件和更新包的分离签名文件
+    pub pubkey_path: Option<String>,
+    pub sig_path: Option<String>,
+    // 复制前要结束的进程名称
+    pub kill_process: Option<String>,
+    // 加密更新包的解压密码
+    pub password: Option<String>,
+}
+
+// 文件操作失败时的默认重试次数
+const DEFAULT_RETRIES: u32 = 3;
+
+impl Default for UpdateConfig {
+    fn default() -> Self {
+        Self {
+            package_path: String::new(),
+            zip_inner_path: String::new(),
+            target_path: None,
+            mappings: Vec::new(),
+            lang: Language::Chinese,
+            dry_run: false,
+            confirm: false,
+            verify: false,
+            prune: false,
+            atomic: false,
+            preserve_mtime: true,
+            skip_unchanged: false,
+            skip_newer: false,
+            delta: false,
+            flatten: false,
+            on_locked: LockPolicy::default(),
+            keep_package: false,
+            fsync: false,
+            receipt: false,
+            audit_csv: None,
+            temp_dir: None,
+            format: None,
+            preserve_hardlinks: false,
+            dedup: false,
+            resumable: false,
+            self_suffix: self_update::DEFAULT_SUFFIX.to_string(),
+            self_stage: true,
+            target_subdir: None,
+            // 默认并发复制数为CPU核心数
+            jobs: thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            retries: DEFAULT_RETRIES,
+            continue_on_error: false,
+            timeout: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            confirm_overwrite: false,
+            protect: Vec::new(),
+            expect_files: Vec::new(),
+            pre_hook: None,
+            post_hook: None,
+            pubkey_path: None,
+            sig_path: None,
+            kill_process: None,
+            password: None,
+        }
+    }
+}
+
+// 更新过程中产生的事件
+#[derive(Debug, Clone)]
+pub enum UpdateEvent {
+    Status(String),
+    TotalFiles(usize),
+    // 正在处理的条目：所属的阶段、当前序号、总数和文件名
+    Progress { phase: Phase, current: usize, total: usize, file: String },
+    // 已复制字节数和总字节数
+    Bytes(u64, u64),
+    // 当前阶段的完成比例（0到1），复制阶段按所有映射的总字节数计算，只增不减
+    PhaseProgress { phase: Phase, fraction: f32 },
+    // 演练模式下计划执行的操作
+    Plan(Vec<PlannedAction>),
+    Complete,
+    // 继续执行模式下部分文件失败：相对路径和错误信息
+    PartialComplete(Vec<(PathBuf, String)>),
+    Warning(String),
+    // 更新失败的类别和错误信息
+    Error(FailureKind, String),
+    // 要覆盖受保护的文件：复制会暂停，直到通过附带的发送端回复如何处理；发送端被丢弃时按覆盖处理
+    Conflict(String, mpsc::Sender<ConflictChoice>),
+    // 确认模式下即将执行的操作和要写入的总字节数：修改目标目录之前暂停，直到通过附带的发送端回复是否应用；
+    // 发送端被丢弃时按取消处理
+    Confirm { actions: Vec<PlannedAction>, total_bytes: u64, reply: mpsc::Sender<bool> },
+    // 正在等待被占用的文件释放（如等待目标程序退出）：等待的详情，通过附带的发送端发送即可取消更新；每次重新检查时都会发送一次
+    Waiting { message: String, cancel: mpsc::Sender<()> },
+}
+
+// 受保护文件的处理方式，"全部"表示之后的受保护文件不再询问
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictChoice {
+    Overwrite,
+    Skip,
+    OverwriteAll,
+    SkipAll,
+}
+
+// 要覆盖的文件正被其他进程占用时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LockPolicy {
+    // 等待文件被释放
+    Wait,
+    // 在修改任何文件之前中止更新
+    Abort,
+    // 写入暂存文件，Windows上登记为下次重启时替换（其他系统上可以直接覆盖正在使用的文件）
+    #[default]
+    Stage,
+}
+
+// 更新包的格式，未指定时按文件开头的魔数检测，无法识别时再按扩展名判断
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageFormat {
+    Zip,
+    TarGz,
+    // 已经解压好的目录
+    Dir,
+}
+
+impl PackageFormat {
+    // 命令行参数中使用的格式名称
+    pub fn name(self) -> &'static str {
+        match self {
+            PackageFormat::Zip => "zip",
+            PackageFormat::TarGz => "tar.gz",
+            PackageFormat::Dir => "dir",
+        }
+    }
+}
+
+// 更新的阶段，按顺序依次进行，界面可以按各阶段的权重把进度合并为一个连续的进度条
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    // 解压更新包
+    Extract,
+    // 按更新包中的校验清单验证解压出的文件
+    Verify,
+    // 复制文件到目标目录（包括 --prune 删除旧文件）
+    Copy,
+    // 全部复制完成后写入回执等收尾工作
+    Finalize,
+}
+
+impl Phase {
+    // JSON事件中使用的阶段名称
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Extract => "extract",
+            Phase::Verify => "verify",
+            Phase::Copy => "copy",
+            Phase::Finalize => "finalize",
+        }
+    }
+}
+
+// 更新失败的类别，调用方可以据此区分失败原因（如设置不同的退出码）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    // 缺少更新包路径、目标路径或过滤模式无效等配置错误
+    InvalidConfig,
+    // 更新包文件不存在
+    PackageMissing,
+    // 更新包无法解压或其中缺少指定的目录
+    Extraction,
+    // 复制、删除文件失败或目标磁盘空间不足
+    Copy,
+    // 签名、校验清单或复制后的文件校验失败
+    Verification,
+    // 超时后中止了更新
+    Cancelled,
+    // 钩子命令失败、无法结束进程等其他错误
+    Other,
+}
+
+// 更新失败的原因，错误信息已按配置的语言本地化
+#[derive(Debug, Clone, Error)]
+pub enum UpdateError {
+    #[error("{0}")]
+    InvalidConfig(String),
+    #[error("{0}")]
+    PackageMissing(String),
+    #[error("{0}")]
+    Extraction(String),
+    #[error("{0}")]
+    Copy(String),
+    #[error("{0}")]
+    Verification(String),
+    #[error("{0}")]
+    Cancelled(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl UpdateError {
+    pub(crate) fn new(kind: FailureKind, message: String) -> Self {
+        match kind {
+            FailureKind::InvalidConfig => UpdateError::InvalidConfig(message),
+            FailureKind::PackageMissing => UpdateError::PackageMissing(message),
+            FailureKind::Extraction => UpdateError::Extraction(message),
+            FailureKind::Copy => UpdateError::Copy(message),
+            FailureKind::Verification => UpdateError::Verification(message),
+            FailureKind::Cancelled => UpdateError::Cancelled(message),
+            FailureKind::Other => UpdateError::Other(message),
+        }
+    }
+
+    // 失败的类别，与 UpdateEvent::Error 中的类别相同
+    pub fn kind(&self) -> FailureKind {
+        match self {
+            UpdateError::InvalidConfig(_) => FailureKind::InvalidConfig,
+            UpdateError::PackageMissing(_) => FailureKind::PackageMissing,
+            UpdateError::Extraction(_) => FailureKind::Extraction,
+            UpdateError::Copy(_) => FailureKind::Copy,
+            UpdateError::Verification(_) => FailureKind::Verification,
+            UpdateError::Cancelled(_) => FailureKind::Cancelled,
+            UpdateError::Other(_) => FailureKind::Other,
+        }
+    }
+}
+
+// 更新结束时的统计，演练模式下不修改任何文件，复制和删除的数量为0
+#[derive(Debug, Clone, Default)]
+pub struct UpdateSummary {
+    // 写入目标目录的文件数及其字节数
+    pub files_copied: usize,
+    pub bytes_copied: u64,
+    // 内容未变化、被过滤或用户选择不覆盖而没有复制的文件数
+    pub files_skipped: usize,
+    // --prune 删除的旧文件数
+    pub files_deleted: usize,
+    // Windows上被占用而登记为下次重启时替换的文件数
+    pub files_pending_reboot: usize,
+    // 继续执行模式下失败的文件（路径和错误信息），不为空时对应 UpdateEvent::PartialComplete
+    pub failed_files: Vec<(PathBuf, String)>,
+    pub duration: Duration,
+}
+
+// 检查模式下列出的更新包条目，路径使用 / 分隔，目录以 / 结尾
+#[derive(Debug, Clone)]
+pub struct PackageEntry {
+    pub path: String,
+    // 解压后的字节数
+    pub size: u64,
+    pub is_dir: bool,
+    // 是否位于要复制的压缩包内路径中
+    pub in_inner_path: bool,
+}
+
+// 配置的压缩包内路径（可能包含通配符）及其在更新包中匹配到的目录或文件，正常更新要求恰好匹配一个
+#[derive(Debug, Clone)]
+pub struct InnerPath {
+    pub pattern: String,
+    pub matches: Vec<String>,
+}
+
+// 检查模式的结果：更新包中的全部条目和每个压缩包内路径的匹配情况
+#[derive(Debug, Clone)]
+pub struct Inspection {
+    pub entries: Vec<PackageEntry>,
+    pub inner_paths: Vec<InnerPath>,
+}
+
+// 演练模式下计划执行的操作
+#[derive(Debug, Clone)]
+pub enum PlannedAction {
+    Create(String),
+    Overwrite(String),
+    RenameExe(String, String),
+    Delete(String),
+}
+
+// 执行更新并在调用线程上回调每个事件，更新结束（Complete、PartialComplete、Plan或Error之后）时返回统计或失败原因
+pub fn run_update(config: &UpdateConfig, mut progress: impl FnMut(UpdateEvent)) -> Result<UpdateSummary, UpdateError> {
+    let dict = get_dict(config.lang);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        let handle = scope.spawn(|| update::perform_update(config, dict, sender));
+        for event in receiver {
+            progress(event);
+        }
+        handle.join().unwrap()
+    })
+}
+
+// 快速检查更新包是否存在且是有效的压缩包（只读取中央目录，不解压），以及目标目录中是否有 expect_files 指定的文件，
+// 用于在显示界面前尽早报告错误
+pub fn check_package(config: &UpdateConfig) -> Result<(), UpdateError> {
+    let dict = get_dict(config.lang);
+    update::check_package(config, dict)
+        .and_then(|_| update::check_expected_files(config, dict))
+        .map_err(|(kind, e)| UpdateError::new(kind, e.to_string()))
+}
+
+// 确认每个目标目录可以写入（尚未创建的目录检查最近的已存在上级目录），用于自检
+pub fn check_targets_writable(config: &UpdateConfig) -> Result<(), UpdateError> {
+    update::check_targets_writable(config, get_dict(config.lang)).map_err(|(kind, e)| UpdateError::new(kind, e.to_string()))
+}
+
+// 不解压、不修改任何文件，列出更新包中的条目并解析配置的压缩包内路径，用于排查更新包的目录结构
+pub fn inspect_package(config: &UpdateConfig) -> Result<Inspection, UpdateError> {
+    update::inspect_package(config, get_dict(config.lang)).map_err(|(kind, e)| UpdateError::new(kind, e.to_string()))
+}
+
+// 更新包中全部文件解压后的总字节数，只读取压缩包的中央目录，用于在开始更新前显示
+pub fn update_size(config: &UpdateConfig) -> Result<u64, UpdateError> {
+    update::update_size(config, get_dict(config.lang)).map_err(|(kind, e)| UpdateError::new(kind, e.to_string()))
+}
+
+// 读取更新包中指定相对路径的文本文件（如 update.json 或 CHANGELOG.md），按 format 或检测到的格式打开更新包，
+// 加密的ZIP条目使用指定的密码；更新包无法打开或没有该文件时返回None，读取或解密该文件失败时返回错误
+pub fn read_package_file(package_path: &str, format: Option<PackageFormat>, password: Option<&str>, name: &str) -> io::Result<Option<String>> {
+    update::read_package_file(Path::new(package_path), format, password.map(str::as_bytes), name)
+}
+
+// 确认每个目标磁盘的剩余空间足以容纳更新包中的全部文件，返回需要的字节数，用于自检
+pub fn check_free_space(config: &UpdateConfig) -> Result<u64, UpdateError> {
+    update::check_targets_space(config, get_dict(config.lang)).map_err(|(kind, e)| UpdateError::new(kind, e.to_string()))
+}
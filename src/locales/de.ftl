@@ -0,0 +1,245 @@
+# 软件更新程序的德文界面文本
+# 格式为 Fluent 的子集：键 = 文本，{ $名称 } 为参数，{ "文本" } 为原样输出的文本，
+# 多行文本从下一行开始并缩进，缺少的键使用英文文本
+
+title = Softwareaktualisierung
+status_preparing = Aktualisierung wird vorbereitet...
+status_waiting_for_app = Warte, bis die Anwendung geschlossen wird...
+status_complete = Softwareaktualisierung abgeschlossen!
+status_failed = Softwareaktualisierung fehlgeschlagen!
+button_ok = OK
+button_update_now = Jetzt aktualisieren
+hint_cancel_delay = Esc drücken, um die Aktualisierung abzubrechen
+button_overwrite = Überschreiben
+button_skip = Überspringen
+button_overwrite_all = Alle überschreiben
+button_skip_all = Alle überspringen
+confirm_title = Aktualisierung anwenden?
+button_apply = Anwenden
+button_cancel = Abbrechen
+button_browse = Durchsuchen...
+button_start = Starten
+form_package = Paket:
+form_target = Ziel:
+form_language = Sprache:
+label_details = Details
+self_test_title = Ergebnisse des Selbsttests
+check_package = Aktualisierungspaket
+check_target_writable = Ziel beschreibbar
+check_disk_space = Speicherplatz
+check_fonts = Schriftarten der Oberfläche
+check_pass = OK
+check_warning = WARNUNG
+check_fail = FEHLER
+inspect_title = Paketinhalt
+check_font_fallback = Keine chinesische Systemschriftart gefunden, die integrierte Ersatzschriftart wird verwendet
+status_dry_run_complete = Probelauf abgeschlossen, es wurden keine Dateien geändert
+status_extracting = Aktualisierungspaket wird entpackt...
+status_copying = Dateien werden kopiert...
+status_rolling_back = Aktualisierung fehlgeschlagen, ursprüngliche Dateien werden wiederhergestellt...
+status_checkpoint_saved = Aktualisierung fehlgeschlagen; der Fortschritt wurde gespeichert und der nächste Lauf setzt an dieser Stelle fort
+status_staging = Die neue Version wird in einem temporären Verzeichnis vorbereitet...
+status_swapping = Das neue Zielverzeichnis wird eingesetzt...
+phase_extract = Phase: Entpacken
+phase_verify = Phase: Überprüfen
+phase_copy = Phase: Kopieren
+phase_finalize = Phase: Abschließen
+status_swap_fallback = Das Zielverzeichnis kann nicht ausgetauscht werden, die Dateien werden direkt aktualisiert...
+status_verifying_signature = Paketsignatur wird geprüft...
+status_verifying_manifest = Dateien werden anhand des Prüfsummenmanifests geprüft...
+status_applying_patches = Delta-Patches werden angewendet...
+error_signature_missing = Das Paket muss signiert sein, bitte die Signaturdatei mit --sig angeben
+error_signature_mismatch = Die Prüfung der Paketsignatur ist fehlgeschlagen, das Paket wurde möglicherweise manipuliert
+error_no_pubkey = Eine Signatur wurde angegeben, aber kein öffentlicher Schlüssel ist verfügbar, bitte mit --pubkey angeben
+error_directory_unsigned = Das Paket ist ein Verzeichnis und kann nicht per Signatur geprüft werden, bei erforderlicher Signatur ein Archiv verwenden
+error_elevation_declined = Administratorrechte wurden nicht erteilt, die Aktualisierung wurde abgebrochen
+error_update_declined = Die Aktualisierung wurde abgebrochen, das Zielverzeichnis wurde nicht verändert
+error_no_package = Es wurde kein Pfad zum Aktualisierungspaket angegeben
+error_no_target = Ein Zielpfad muss angegeben werden
+error_password_required = Das Paket ist verschlüsselt, bitte das Passwort mit --password angeben
+error_password_invalid = Falsches Passwort für das Aktualisierungspaket
+bytes_small = { $bytes } B
+decimal_separator = ,
+bytes_kb = { $value } KB
+bytes_mb = { $value } MB
+bytes_gb = { $value } GB
+bytes_tb = { $value } TB
+bytes_pb = { $value } PB
+bytes_eb = { $value } EB
+duration_hours = { $hours } h { $minutes } min
+duration_minutes = { $minutes } min { $seconds } s
+duration_seconds = { $seconds } s
+inspect_summary = { $entries } Einträge, { $bytes } unkomprimiert
+inspect_inner_path = Innerer Pfad { $pattern } entspricht: { $resolved }
+check_space_needed = { $needed } erforderlich
+status_processing = Verarbeite: { $file_name }
+label_package = Paket: { $name }
+label_target = Ziel: { $path }
+status_file_count = { $current } / { $total } Dateien
+summary_counts = { $copied } Dateien kopiert, { $skipped } übersprungen, { $failed } fehlgeschlagen
+summary_bytes = { $bytes } in { $seconds } geschrieben
+status_starting_in = Start in { $seconds }...
+hint_update_size = Diese Aktualisierung schreibt etwa { $bytes }
+plan_summary = { $count } geplante Vorgänge:
+confirm_summary = { $files } Dateien ({ $size }) werden geschrieben und { $deleted } gelöscht
+plan_create = Erstellen: { $path }
+plan_overwrite = Überschreiben: { $path }
+plan_rename_exe = Umbenennen: { $from } -> { $to }
+error_inner_path_not_found = Verzeichnis im Aktualisierungspaket nicht gefunden: { $inner_path }
+error_package_empty = Das Aktualisierungspaket scheint leer zu sein, es enthält keine Dateien
+error_package_empty_in = Das Aktualisierungspaket scheint leer zu sein, { $inner_path } im Archiv enthält keine Dateien
+error_volume_unknown = Das Laufwerk des Zielpfads kann nicht ermittelt werden: { $path }
+error_expected_file_missing = { $name } wurde im Zielverzeichnis { $target } nicht gefunden; bitte prüfen, ob der Zielpfad stimmt
+error_target_not_writable = In das Zielverzeichnis { $path } kann nicht geschrieben werden: { $err }
+error_insufficient_space = Nicht genug Speicherplatz auf dem Ziellaufwerk: { $needed } erforderlich, { $available } verfügbar
+error_temp_space_exhausted = Beim Entpacken ist der temporäre Speicherplatz ausgegangen, die temporären Dateien wurden entfernt: { $path }; Speicher freigeben oder mit --temp-dir auf ein anderes Laufwerk entpacken
+error_package_not_found = Aktualisierungspaket nicht gefunden: { $path }
+error_invalid_package_metadata = Ungültige update.json im Aktualisierungspaket: { $reason }
+error_stdin_args = Argumente können nicht von der Standardeingabe gelesen werden: { $reason }
+error_undefined_variable = Der Zielpfad { $target } im Aktualisierungspaket verwendet die nicht definierte Umgebungsvariable { $name }
+error_updater_too_old = Das Aktualisierungspaket erfordert die Updater-Version { $required } oder neuer, dies ist Version { $current }
+error_invalid_version = Ungültige Version: { $value } (erwartet wird eine semantische Version wie 1.2.3)
+error_not_newer = Die Paketversion { $package } ist nicht neuer als die installierte Version { $current }, mit --allow-downgrade herabstufen oder neu installieren
+error_current_too_old = Die installierte Version { $current } ist zu alt, dieses Paket kann nur Version { $min } oder neuer aktualisieren
+error_invalid_package = Das Aktualisierungspaket ist keine gültige ZIP-Datei: { $err }
+error_invalid_tar_package = Das Update-Paket ist keine gültige tar.gz-Datei: { $err }
+error_package_not_directory = --format dir wurde angegeben, aber das Update-Paket ist kein Verzeichnis: { $path }
+error_invalid_auto_close = Ungültige Verzögerung für automatisches Schließen: { $value }
+error_invalid_delay = Ungültige Verzögerung: { $value }
+error_unexpected_argument = Unerwartetes Argument: { $arg }
+error_missing_value = Fehlender Wert für Option: { $flag }
+error_invalid_language = Ungültige Sprache: { $value }
+error_unknown_option = Unbekannte Option: { $flag }
+status_transfer = Geschwindigkeit: { $bytes_per_second }/s, noch etwa { $eta_seconds }
+error_invalid_jobs = Ungültige Anzahl von Threads: { $value }
+error_invalid_theme = Ungültiges Design: { $value } (erwartet light, dark oder system)
+error_invalid_config = Konfigurationsdatei { $path } konnte nicht gelesen werden: { $reason }
+error_invalid_scale = Ungültige Skalierung (erwartet eine Zahl von 0.5 bis 4 oder auto): { $value }
+error_invalid_size = Ungültige Fenstergröße: { $value }
+error_invalid_glob = Ungültiges Glob-Muster { $pattern }: { $reason }
+status_copying_excluded = { $excluded } Dateien ausgeschlossen
+status_copying_unchanged = { $unchanged } unveränderte Dateien übersprungen
+status_copying_details = Dateien werden kopiert... ({ $details })
+list_separator = { ", " }
+error_verify_failed = Prüfung fehlgeschlagen, die kopierte Datei weicht vom Paket ab: { $file_name }
+error_invalid_retries = Ungültige Anzahl von Wiederholungen: { $value }
+error_flatten_collision = Beim Abflachen wird mehr als eine Datei auf { $name } abgebildet: { $first } und { $second }
+error_incompatible_options = { $first } kann nicht zusammen mit { $second } verwendet werden
+error_invalid_target_subdir = Der Zielunterordner muss ein relativer Pfad ohne .. sein: { $value }
+error_invalid_suffix = Ungültige Endung für die bereitgestellte Kopie: { $value }
+error_invalid_timeout = Ungültiges Zeitlimit: { $value }
+status_timed_out = Seit { $seconds } Sekunden kein Fortschritt, die Aktualisierung wird abgebrochen...
+error_timed_out = Zeitüberschreitung: seit { $seconds } Sekunden kein Fortschritt, die Aktualisierung wurde abgebrochen
+status_retrying = Datei ist belegt, neuer Versuch ({ $attempt }/{ $max }): { $file_name }
+plan_delete = Löschen: { $path }
+status_pruning = Datei, die in der neuen Version fehlt, wird entfernt: { $file_name }
+status_mapping = Aktualisiere ({ $index }/{ $total }): { $inner } -> { $target }
+error_invalid_map = Ungültige Zuordnung: { $value } (erwartet <innerer Pfad>:<Zielpfad>)
+status_partial_complete = Aktualisierung unvollständig, { $count } Dateien fehlgeschlagen:
+status_waiting_for_locked = Warte auf die Freigabe von { $count } belegten Dateien: { $first }...
+error_files_locked = { $count } Dateien werden von einem anderen Programm verwendet; bitte schließen und erneut versuchen: { $first }
+error_invalid_lock_policy = Ungültige Richtlinie für belegte Dateien (erwartet wait, abort oder stage): { $value }
+error_invalid_format = Ungültiges Paketformat (erwartet zip, tar.gz oder dir): { $value }
+status_running_hook = Befehl wird ausgeführt: { $command }
+error_hook_failed = Befehl fehlgeschlagen (Exitcode { $code }): { $command }
+error_invalid_pubkey = Ungültiger öffentlicher ed25519-Schlüssel: { $path }
+error_invalid_signature_file = Ungültige ed25519-Signaturdatei: { $path }
+warning_unsafe_entries = Warnung: { $count } Paketeinträge mit unsicheren Pfaden übersprungen (absolut, mit .. oder über einen symbolischen Link außerhalb)
+warning_format_mismatch = Warnung: Als Paketformat wurde { $format } angegeben, der Inhalt sieht jedoch nach { $detected } aus; es wird als { $format } behandelt
+prompt_conflict =
+    Die Zieldatei ist schreibgeschützt oder geschützt. Überschreiben?
+    { $path }
+warning_protected_skipped = Warnung: { $count } geschützte Dateien wie gewählt übersprungen, sie wurden nicht verändert
+warning_newer_kept = Warnung: { $count } Dateien im Ziel sind neuer als im Paket und wurden beibehalten
+warning_package_not_removed = Aktualisierung erfolgreich, aber die Paketdatei konnte nicht entfernt werden: { $path }: { $reason }
+warning_restart_required = Ein Neustart ist erforderlich, um die Aktualisierung von { $count } Dateien abzuschließen.
+error_audit_not_opened = Die Prüfprotokolldatei { $path } kann nicht erstellt werden: { $reason }
+warning_receipt_not_written = Aktualisierung erfolgreich, aber der Aktualisierungsbeleg konnte nicht in { $target } geschrieben werden: { $reason }
+error_inner_path_ambiguous = Mehrere Verzeichnisse im Aktualisierungspaket entsprechen { $pattern }: { $matches }
+error_unsafe_inner_path = Der Pfad im Paket darf nicht absolut sein oder .. enthalten: { $path }
+error_patch_base_missing = Die zu patchende Datei fehlt im Ziel, die Delta-Aktualisierung kann nicht angewendet werden: { $file }
+error_patch_failed = Patch für { $file } konnte nicht angewendet werden: { $err }
+error_manifest_missing = Im Prüfsummenmanifest aufgeführte Datei fehlt im Paket: { $file }
+error_manifest_mismatch = Die SHA-256-Prüfsumme der Datei stimmt nicht mit dem Manifest überein, das Paket ist möglicherweise beschädigt: { $file }
+status_killing_process = { $count } laufende { $name }-Prozesse werden beendet...
+error_kill_process_failed = Der laufende Prozess konnte nicht beendet werden: { $name }
+usage =
+    Verwendung: software_updater --package <Pfad> --target <Pfad> [Optionen]
+
+      --package <path>      Pfad zum Aktualisierungspaket, eine ZIP- oder tar.gz-Datei oder ein entpacktes Verzeichnis (erforderlich)
+      --inner-path <path>   Zu kopierender Ordner oder einzelne Datei im Archiv, standardmäßig das Stammverzeichnis; ein Glob (z. B. myapp-*) wählt den einzigen passenden Ordner
+      --target <path>       Zielverzeichnis (erforderlich, außer --map, --self-dir oder update.json/target.txt im Paket geben es an)
+      --self-dir            Das eigene Verzeichnis des Updaters aktualisieren, wenn --target fehlt
+      --map <inner>:<target>
+                            Einen Ordner aus dem Paket in ein Zielverzeichnis kopieren; wiederholbar, ersetzt --inner-path/--target
+      --expect-file <name>  Datei, die im Ziel bereits vorhanden sein muss (z. B. die Haupt-EXE), sonst Abbruch; wiederholbar
+      --current-version <version>
+                            Installierte Version, Abbruch, wenn die Paketversion nicht neuer ist
+      --allow-downgrade     Pakete zulassen, die nicht neuer als die installierte Version sind
+      --delay <seconds>     Verzögerung in Sekunden vor dem Start, standardmäßig 0
+      --start-exe <path>    Programm, das nach erfolgreicher Aktualisierung gestartet wird
+      --auto-close <seconds>
+                            Fenster so viele Sekunden nach erfolgreicher Aktualisierung schließen (nicht bei Fehlern)
+      --kill-process <name> Prozesse mit diesem Namen vor dem Kopieren beenden (erst regulär, dann erzwungen)
+      --pre-hook <command>  Shell-Befehl, der vor dem Entpacken im Ziel ausgeführt wird; bei Exit-Code ungleich 0 wird abgebrochen
+      --post-hook <command> Shell-Befehl, der nach dem Kopieren im Ziel ausgeführt wird; bei Fehler bleibt das Paket erhalten
+      --elevate             (Nur Windows) Mit Administratorrechten neu starten, falls nicht erhöht
+      --pubkey <path>       Öffentlicher ed25519-Schlüssel zur Prüfung der Paketsignatur
+      --sig <path>          Separate ed25519-Signatur des Pakets, vor dem Entpacken geprüft
+      --password <password> Passwort für ein verschlüsseltes Paket
+      --lang <zh|en|ja|ko|de|fr>
+                            Sprache der Oberfläche, standardmäßig nach Systemgebietsschema
+      --dry-run             Geplante Vorgänge auflisten, ohne Dateien zu ändern
+      --confirm             Geplante Vorgänge nach dem Entpacken auflisten und auf Bestätigung warten
+      --verify              SHA-256 jeder kopierten Datei prüfen und bei Abweichung zurücksetzen
+      --prune               Dateien im Ziel löschen, die in der neuen Version fehlen (außer --exclude-Treffern)
+      --continue-on-error   Bei fehlgeschlagenen Dateien weiterkopieren und sie am Ende auflisten
+      --skip-unchanged      Dateien überspringen, die mit denen im Ziel identisch sind
+      --skip-newer          Dateien im Ziel, die neuer als im Paket sind, nicht überschreiben
+      --delta               .patch-Dateien im Paket als binäre Patches auf vorhandene Dateien anwenden
+      --flatten             Alle Dateien ohne Unterordner direkt ins Ziel kopieren, Abbruch bei doppelten Namen
+      --keep-package        Quellpaket nach erfolgreicher Aktualisierung behalten (standardmäßig gelöscht)
+      --fsync               Jede geschriebene Datei auf die Festplatte schreiben, damit ein Stromausfall sie nicht beschädigt (langsamer)
+      --receipt             update-receipt.json mit den installierten Dateien ins Ziel schreiben
+      --audit-csv <path>    Pro Dateivorgang eine CSV-Zeile schreiben (Zeit, Aktion, Quelle, Ziel, Bytes, Ergebnis)
+      --temp-dir <path>     Verzeichnis für Entpacken und Sicherungen, standardmäßig das temporäre Systemverzeichnis
+      --format <Format>     Paketformat (zip, tar.gz oder dir), standardmäßig anhand von Inhalt und Endung erkannt
+      --resumable           Kopierte Dateien merken, bei Fehlern nicht zurücksetzen und beim nächsten Lauf fortsetzen
+      --target-subdir <name>
+                            In diesen Unterordner des Ziels installieren (z. B. versions/1.2.0), bei Bedarf anlegen
+      --self-suffix <ext>   Endung der bereitgestellten Kopie des laufenden Updaters unter Windows, standardmäßig .new
+      --on-locked <wait|abort|stage>
+                            Wenn eine zu überschreibende Datei belegt ist: warten, abbrechen oder für den Neustart bereitstellen (Standard)
+      --no-self-stage       Den laufenden Updater nie bereitstellen, sondern direkt überschreiben (wenn er nicht im Ziel liegt)
+      --self-test           Paket, Zielberechtigungen, Speicherplatz und Schriftarten prüfen, berichten und beenden
+      --inspect             Paketeinträge auflisten, das --inner-path-Verzeichnis hervorheben und beenden
+      --atomic              Die neue Version in einem benachbarten temporären Verzeichnis erstellen und dann austauschen
+      --no-preserve-mtime   Änderungszeiten nicht übernehmen; kopierte Dateien erhalten die aktuelle Zeit
+      --preserve-hardlinks  (Nur Unix) Hart verlinkte Dateien im Paket nur einmal kopieren und die übrigen Pfade im Ziel hart verlinken
+      --dedup               Dateien mit identischem Inhalt nur einmal aus dem Paket kopieren und die übrigen Ziele von der ersten Kopie kopieren
+      --json                Ohne Fenster ausführen und den Fortschritt als ein JSON-Objekt pro Zeile auf stdout ausgeben
+      --silent              Ohne Fenster ausführen, das Ergebnis protokollieren und eine Systembenachrichtigung anzeigen
+      --config <path>       Konfigurationsdatei, standardmäßig updater.toml neben der EXE; Optionen haben Vorrang
+      --args-from-stdin     Weitere Argumente von der Standardeingabe lesen, eines pro Zeile oder durch NUL getrennt
+      --log <path>          Pfad der Protokolldatei, standardmäßig updater.log neben der EXE
+      --changelog <path>    Versionshinweise, die während der Verzögerung angezeigt werden, standardmäßig CHANGELOG.md im Paket
+      --show-size           Während der Verzögerung die Gesamtgröße anzeigen, die geschrieben wird
+      --jobs <count>        Anzahl paralleler Kopier-Threads, standardmäßig die Anzahl der CPUs
+      --retries <count>     Wiederholungen, wenn eine Datei belegt ist, standardmäßig 3
+      --timeout <seconds>   Abbrechen und zurücksetzen, wenn so viele Sekunden kein Fortschritt erfolgt
+      --include <glob>      Nur Dateien kopieren, die dem Glob entsprechen, wiederholbar
+      --exclude <glob>      Dateien überspringen, die dem Glob entsprechen, wiederholbar, hat Vorrang vor --include
+      --confirm-overwrite   Vor dem Überschreiben schreibgeschützter oder geschützter Dateien nachfragen
+      --protect <glob>      Mit --confirm-overwrite passende Dateien (z. B. Konfigurationen) als geschützt behandeln, wiederholbar
+      --width <pixels>      Fensterbreite, standardmäßig 450
+      --height <pixels>     Fensterhöhe, standardmäßig 250
+      --resizable           Größenänderung des Fensters erlauben
+      --no-topmost          Fenster nicht über anderen Fenstern halten
+      --no-center           Fenster nicht auf dem Bildschirm zentrieren
+      --scale <factor|auto>
+                            Skalierungsfaktor der Oberfläche (0.5 bis 4), standardmäßig nach System-DPI
+      --theme <light|dark|system> Fensterdesign, standardmäßig light; system folgt dem Betriebssystem
+      -h, --help            Diese Hilfe anzeigen
+
+    Die alte Form mit Positionsargumenten wird weiterhin akzeptiert, ist aber veraltet:
+      software_updater <package_path> [zip_inner_path] <target_path> [delay_seconds] [start_exe_path] [zh|en|ja|ko|de|fr]
@@ -0,0 +1,245 @@
+# 软件更新程序的英文界面文本
+# 格式为 Fluent 的子集：键 = 文本，{ $名称 } 为参数，{ "文本" } 为原样输出的文本，
+# 多行文本从下一行开始并缩进，缺少的键使用英文文本
+
+title = Software Update
+status_preparing = Preparing update...
+status_waiting_for_app = Waiting for the application to close...
+status_complete = Software update completed!
+status_failed = Software update failed!
+button_ok = OK
+button_update_now = Update now
+hint_cancel_delay = Press Esc to cancel the update
+button_overwrite = Overwrite
+button_skip = Skip
+button_overwrite_all = Overwrite all
+button_skip_all = Skip all
+confirm_title = Apply the update?
+button_apply = Apply
+button_cancel = Cancel
+button_browse = Browse...
+button_start = Start
+form_package = Package:
+form_target = Target:
+form_language = Language:
+label_details = Details
+self_test_title = Self-test results
+check_package = Update package
+check_target_writable = Target writable
+check_disk_space = Disk space
+check_fonts = UI fonts
+check_pass = PASS
+check_warning = WARN
+check_fail = FAIL
+inspect_title = Package contents
+check_font_fallback = No Chinese system font found, the built-in fallback font will be used
+status_dry_run_complete = Dry run complete, no files were changed
+status_extracting = Extracting update package...
+status_copying = Copying files...
+status_rolling_back = Update failed, restoring original files...
+status_checkpoint_saved = Update failed; progress was saved and the next run will resume where it stopped
+status_staging = Preparing the new version in a temporary directory...
+status_swapping = Swapping in the new target directory...
+phase_extract = Phase: extracting
+phase_verify = Phase: verifying
+phase_copy = Phase: copying
+phase_finalize = Phase: finishing
+status_swap_fallback = Cannot swap the target directory, updating files in place...
+status_verifying_signature = Verifying package signature...
+status_verifying_manifest = Verifying files against the checksum manifest...
+status_applying_patches = Applying delta patches...
+error_signature_missing = The package must be signed, please specify the signature file with --sig
+error_signature_mismatch = Package signature verification failed, the package may have been tampered with
+error_no_pubkey = A signature was given but no public key is available, please specify one with --pubkey
+error_directory_unsigned = The package is a directory and cannot be signature-verified, use an archive when signatures are required
+error_elevation_declined = Administrator privileges were not granted, the update was cancelled
+error_update_declined = The update was cancelled, the target directory was not modified
+error_no_package = No update package path was provided
+error_no_target = A target path must be provided
+error_password_required = The package is encrypted, please specify the password with --password
+error_password_invalid = Incorrect password for the update package
+bytes_small = { $bytes } B
+decimal_separator = .
+bytes_kb = { $value } KB
+bytes_mb = { $value } MB
+bytes_gb = { $value } GB
+bytes_tb = { $value } TB
+bytes_pb = { $value } PB
+bytes_eb = { $value } EB
+duration_hours = { $hours } h { $minutes } min
+duration_minutes = { $minutes } min { $seconds } s
+duration_seconds = { $seconds } s
+inspect_summary = { $entries } entries, { $bytes } uncompressed
+inspect_inner_path = Inner path { $pattern } resolves to: { $resolved }
+check_space_needed = { $needed } required
+status_processing = Processing: { $file_name }
+label_package = Package: { $name }
+label_target = Target: { $path }
+status_file_count = { $current } / { $total } files
+summary_counts = Copied { $copied } files, skipped { $skipped }, failed { $failed }
+summary_bytes = Wrote { $bytes } in { $seconds }
+status_starting_in = Starting in { $seconds }...
+hint_update_size = This update will write ~{ $bytes }
+plan_summary = { $count } planned operations:
+confirm_summary = { $files } files ({ $size }) will be written and { $deleted } deleted
+plan_create = Create: { $path }
+plan_overwrite = Overwrite: { $path }
+plan_rename_exe = Rename: { $from } -> { $to }
+error_inner_path_not_found = Directory not found in update package: { $inner_path }
+error_package_empty = Update package appears to be empty, it contains no files
+error_package_empty_in = Update package appears to be empty, { $inner_path } in the archive contains no files
+error_volume_unknown = Cannot determine the volume of target path: { $path }
+error_expected_file_missing = { $name } was not found in target directory { $target }; check that the target path is correct
+error_target_not_writable = Cannot write to target directory { $path }: { $err }
+error_insufficient_space = Not enough space on target volume: { $needed } required, { $available } available
+error_temp_space_exhausted = Ran out of temp disk space while extracting, the temporary files were removed: { $path }; free up space or use --temp-dir to extract to another volume
+error_package_not_found = Update package not found: { $path }
+error_invalid_package_metadata = Invalid update.json in the update package: { $reason }
+error_stdin_args = Cannot read arguments from standard input: { $reason }
+error_undefined_variable = The target path { $target } in the update package uses the undefined environment variable { $name }
+error_updater_too_old = The update package requires updater version { $required } or later, this is version { $current }
+error_invalid_version = Invalid version: { $value } (expected a semantic version such as 1.2.3)
+error_not_newer = Package version { $package } is not newer than the installed version { $current }, use --allow-downgrade to downgrade or reinstall
+error_current_too_old = The installed version { $current } is too old, this package can only update version { $min } or later
+error_invalid_package = The update package is not a valid ZIP file: { $err }
+error_invalid_tar_package = The update package is not a valid tar.gz file: { $err }
+error_package_not_directory = --format dir was specified, but the update package is not a directory: { $path }
+error_invalid_auto_close = Invalid auto-close delay: { $value }
+error_invalid_delay = Invalid delay value: { $value }
+error_unexpected_argument = Unexpected argument: { $arg }
+error_missing_value = Missing value for option: { $flag }
+error_invalid_language = Invalid language: { $value }
+error_unknown_option = Unknown option: { $flag }
+status_transfer = Speed: { $bytes_per_second }/s, about { $eta_seconds } remaining
+error_invalid_jobs = Invalid number of jobs: { $value }
+error_invalid_theme = Invalid theme: { $value } (expected light, dark or system)
+error_invalid_config = Failed to read config file { $path }: { $reason }
+error_invalid_scale = Invalid UI scale (expected a number from 0.5 to 4, or auto): { $value }
+error_invalid_size = Invalid window size: { $value }
+error_invalid_glob = Invalid glob pattern { $pattern }: { $reason }
+status_copying_excluded = { $excluded } files excluded
+status_copying_unchanged = { $unchanged } unchanged files skipped
+status_copying_details = Copying files... ({ $details })
+list_separator = { ", " }
+error_verify_failed = Verification failed, copied file differs from the package: { $file_name }
+error_invalid_retries = Invalid number of retries: { $value }
+error_flatten_collision = Flattening maps more than one file to { $name }: { $first } and { $second }
+error_incompatible_options = { $first } cannot be used together with { $second }
+error_invalid_target_subdir = The target subfolder must be a relative path without ..: { $value }
+error_invalid_suffix = Invalid staging suffix: { $value }
+error_invalid_timeout = Invalid timeout: { $value }
+status_timed_out = No progress for { $seconds } seconds, aborting the update...
+error_timed_out = Update timed out: no progress for { $seconds } seconds, the update was aborted
+status_retrying = File is busy, retrying ({ $attempt }/{ $max }): { $file_name }
+plan_delete = Delete: { $path }
+status_pruning = Removing file no longer in the new version: { $file_name }
+status_mapping = Updating ({ $index }/{ $total }): { $inner } -> { $target }
+error_invalid_map = Invalid mapping: { $value } (expected <inner path>:<target path>)
+status_partial_complete = Update incomplete, { $count } files failed:
+status_waiting_for_locked = Waiting for { $count } files in use to be released: { $first }...
+error_files_locked = { $count } files are in use by another program; close it and try again: { $first }
+error_invalid_lock_policy = Invalid locked-file policy (expected wait, abort or stage): { $value }
+error_invalid_format = Invalid package format (expected zip, tar.gz or dir): { $value }
+status_running_hook = Running command: { $command }
+error_hook_failed = Command failed (exit code { $code }): { $command }
+error_invalid_pubkey = Invalid ed25519 public key: { $path }
+error_invalid_signature_file = Invalid ed25519 signature file: { $path }
+warning_unsafe_entries = Warning: skipped { $count } package entries with unsafe paths (absolute, containing .. or escaping through a symlink)
+warning_format_mismatch = Warning: the package format was specified as { $format }, but its content looks like { $detected }; treating it as { $format }
+prompt_conflict =
+    The target file is read-only or protected. Overwrite it?
+    { $path }
+warning_protected_skipped = Warning: skipped { $count } protected files as chosen, they were left unchanged
+warning_newer_kept = Warning: { $count } files in the target are newer than in the package and were kept
+warning_package_not_removed = Update succeeded but the package file could not be removed: { $path }: { $reason }
+warning_restart_required = A restart is required to finish updating { $count } files.
+error_audit_not_opened = Cannot create the audit file { $path }: { $reason }
+warning_receipt_not_written = Update succeeded but the update receipt could not be written in { $target }: { $reason }
+error_inner_path_ambiguous = Multiple directories in the update package match { $pattern }: { $matches }
+error_unsafe_inner_path = The path inside the package must not be absolute or contain ..: { $path }
+error_patch_base_missing = The file to patch does not exist in the target, cannot apply the delta update: { $file }
+error_patch_failed = Failed to apply patch to { $file }: { $err }
+error_manifest_missing = File listed in the checksum manifest is missing from the package: { $file }
+error_manifest_mismatch = SHA-256 of the file does not match the checksum manifest, the package may be corrupt: { $file }
+status_killing_process = Stopping { $count } running { $name } process(es)...
+error_kill_process_failed = Could not stop the running process: { $name }
+usage =
+    Usage: software_updater --package <path> --target <path> [options]
+
+      --package <path>      Path to the update package, a ZIP or tar.gz file or an extracted directory (required)
+      --inner-path <path>   Folder or single file inside the archive to copy, defaults to the root; a glob (e.g. myapp-*) selects the single matching folder
+      --target <path>       Target directory (required unless --map, --self-dir or update.json/target.txt in the package gives one)
+      --self-dir            Update the updater's own directory when --target is not given
+      --map <inner>:<target>
+                            Copy a folder from the package to a target directory; repeatable, replaces --inner-path/--target
+      --expect-file <name>  File that must already exist in the target (e.g. the main exe), aborts if missing; may be repeated
+      --current-version <version>
+                            Installed version, aborts unless the package version is newer
+      --allow-downgrade     Allow packages that are not newer than the installed version
+      --delay <seconds>     Delay in seconds before starting, defaults to 0
+      --start-exe <path>    Executable to launch after a successful update
+      --auto-close <seconds>
+                            Close the window this many seconds after a successful update (not on failure)
+      --kill-process <name> Stop processes with this name before copying (graceful, then forced)
+      --pre-hook <command>  Shell command run in the target before extraction, aborts the update on a non-zero exit code
+      --post-hook <command> Shell command run in the target after copying; on failure the package is kept
+      --elevate             (Windows only) Relaunch with administrator privileges if not elevated
+      --pubkey <path>       ed25519 public key used to verify the package signature
+      --sig <path>          Detached ed25519 signature of the package, checked before extraction
+      --password <password> Password for an encrypted package
+      --lang <zh|en|ja|ko|de|fr>
+                            UI language, defaults to the system locale
+      --dry-run             List the planned operations without changing files
+      --confirm             List the planned operations after extraction and wait for confirmation
+      --verify              Check each copied file's SHA-256 and roll back on mismatch
+      --prune               Delete target files missing from the new version (except --exclude matches)
+      --continue-on-error   Keep copying when a file fails and list the failed files at the end
+      --skip-unchanged      Skip files that are identical to the ones in the target
+      --skip-newer          Do not overwrite files in the target that are newer than in the package
+      --delta               Apply .patch files in the package as binary patches to existing files
+      --flatten             Copy every file straight into the target without subfolders, abort on duplicate names
+      --keep-package        Keep the source package after a successful update (deleted by default)
+      --fsync               Flush every written file to disk so a power loss cannot corrupt it (slower)
+      --receipt             Write update-receipt.json listing the installed files into the target
+      --audit-csv <path>    Write one CSV row per file operation (time, action, source, destination, bytes, result)
+      --temp-dir <path>     Directory for extraction and backups, defaults to the system temp directory
+      --format <format>     Package format (zip, tar.gz or dir), detected from the content and extension by default
+      --resumable           Record copied files, skip rollback on failure and resume on the next run
+      --target-subdir <name>
+                            Install into this subfolder of the target (e.g. versions/1.2.0), creating it if needed
+      --self-suffix <ext>   Suffix for the staged copy of the running updater on Windows, defaults to .new
+      --on-locked <wait|abort|stage>
+                            When a file to overwrite is in use: wait, abort, or stage it for reboot (default)
+      --no-self-stage       Never stage the running updater; overwrite it directly (when it is not in the target)
+      --self-test           Check the package, target permissions, disk space and fonts, report and exit
+      --inspect             List the package entries and highlight the --inner-path directory, then exit
+      --atomic              Build the new version in a sibling temp directory, then swap it in
+      --no-preserve-mtime   Don't keep file modification times; copied files get the current time
+      --preserve-hardlinks  (Unix only) Copy hard-linked files in the package once and hard-link the other paths in the target
+      --dedup               Copy files with identical content from the package only once and fill the other destinations from the first copy
+      --json                Run without a window and report progress as one JSON object per line on stdout
+      --silent              Run without a window, log the outcome and show a system notification
+      --config <path>       Config file, defaults to updater.toml next to the executable; flags take precedence
+      --args-from-stdin     Read more arguments from standard input, one per line or separated by NUL
+      --log <path>          Log file path, defaults to updater.log next to the executable
+      --changelog <path>    Release notes shown during the delay, defaults to CHANGELOG.md in the package
+      --show-size           Show the total size the update will write during the delay
+      --jobs <count>        Number of parallel copy threads, defaults to the CPU count
+      --retries <count>     Retries when a file is busy, defaults to 3
+      --timeout <seconds>   Abort and roll back when there is no progress for this many seconds
+      --include <glob>      Only copy files matching the glob, may be repeated
+      --exclude <glob>      Skip files matching the glob, may be repeated, wins over --include
+      --confirm-overwrite   Ask before overwriting read-only or protected files
+      --protect <glob>      With --confirm-overwrite, treat matching files (e.g. configs) as protected, may be repeated
+      --width <pixels>      Window width, defaults to 450
+      --height <pixels>     Window height, defaults to 250
+      --resizable           Allow resizing the window
+      --no-topmost          Do not keep the window on top of other windows
+      --no-center           Do not center the window on the screen
+      --scale <factor|auto>
+                            UI scale factor (0.5 to 4), defaults to the system DPI
+      --theme <light|dark|system> Window theme, defaults to light; system follows the OS
+      -h, --help            Show this help message
+
+    The old positional form is still accepted but deprecated:
+      software_updater <package_path> [zip_inner_path] <target_path> [delay_seconds] [start_exe_path] [zh|en|ja|ko|de|fr]
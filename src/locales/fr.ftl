@@ -0,0 +1,245 @@
+# 软件更新程序的法文界面文本
+# 格式为 Fluent 的子集：键 = 文本，{ $名称 } 为参数，{ "文本" } 为原样输出的文本，
+# 多行文本从下一行开始并缩进，缺少的键使用英文文本
+
+title = Mise à jour du logiciel
+status_preparing = Préparation de la mise à jour...
+status_waiting_for_app = En attente de la fermeture de l'application...
+status_complete = Mise à jour du logiciel terminée !
+status_failed = Échec de la mise à jour du logiciel !
+button_ok = OK
+button_update_now = Mettre à jour maintenant
+hint_cancel_delay = Appuyez sur Échap pour annuler la mise à jour
+button_overwrite = Remplacer
+button_skip = Ignorer
+button_overwrite_all = Tout remplacer
+button_skip_all = Tout ignorer
+confirm_title = Appliquer la mise à jour ?
+button_apply = Appliquer
+button_cancel = Annuler
+button_browse = Parcourir...
+button_start = Démarrer
+form_package = Paquet :
+form_target = Cible :
+form_language = Langue :
+label_details = Détails
+self_test_title = Résultats de l'autotest
+check_package = Paquet de mise à jour
+check_target_writable = Cible accessible en écriture
+check_disk_space = Espace disque
+check_fonts = Polices de l'interface
+check_pass = OK
+check_warning = AVERT.
+check_fail = ÉCHEC
+inspect_title = Contenu du paquet
+check_font_fallback = Aucune police chinoise trouvée sur le système, la police de secours intégrée sera utilisée
+status_dry_run_complete = Simulation terminée, aucun fichier n'a été modifié
+status_extracting = Extraction du paquet de mise à jour...
+status_copying = Copie des fichiers...
+status_rolling_back = Échec de la mise à jour, restauration des fichiers d'origine...
+status_checkpoint_saved = Échec de la mise à jour ; la progression a été enregistrée et la prochaine exécution reprendra là où elle s'est arrêtée
+status_staging = Préparation de la nouvelle version dans un répertoire temporaire...
+status_swapping = Remplacement du répertoire cible par le nouveau...
+phase_extract = Étape : extraction
+phase_verify = Étape : vérification
+phase_copy = Étape : copie
+phase_finalize = Étape : finalisation
+status_swap_fallback = Impossible de remplacer le répertoire cible, mise à jour des fichiers sur place...
+status_verifying_signature = Vérification de la signature du paquet...
+status_verifying_manifest = Vérification des fichiers avec le manifeste des sommes de contrôle...
+status_applying_patches = Application des correctifs delta...
+error_signature_missing = Le paquet doit être signé, indiquez le fichier de signature avec --sig
+error_signature_mismatch = La vérification de la signature du paquet a échoué, le paquet a peut-être été altéré
+error_no_pubkey = Une signature a été fournie mais aucune clé publique n'est disponible, indiquez-en une avec --pubkey
+error_directory_unsigned = Le paquet est un répertoire et sa signature ne peut pas être vérifiée, utilisez une archive lorsque la signature est exigée
+error_elevation_declined = Les droits d'administrateur n'ont pas été accordés, la mise à jour a été annulée
+error_update_declined = La mise à jour a été annulée, le répertoire cible n'a pas été modifié
+error_no_package = Aucun chemin de paquet de mise à jour n'a été fourni
+error_no_target = Un chemin cible doit être fourni
+error_password_required = Le paquet est chiffré, indiquez le mot de passe avec --password
+error_password_invalid = Mot de passe incorrect pour le paquet de mise à jour
+bytes_small = { $bytes } o
+decimal_separator = ,
+bytes_kb = { $value } Ko
+bytes_mb = { $value } Mo
+bytes_gb = { $value } Go
+bytes_tb = { $value } To
+bytes_pb = { $value } Po
+bytes_eb = { $value } Eo
+duration_hours = { $hours } h { $minutes } min
+duration_minutes = { $minutes } min { $seconds } s
+duration_seconds = { $seconds } s
+inspect_summary = { $entries } entrées, { $bytes } non compressés
+inspect_inner_path = Le chemin interne { $pattern } correspond à : { $resolved }
+check_space_needed = { $needed } nécessaires
+status_processing = Traitement : { $file_name }
+label_package = Paquet : { $name }
+label_target = Cible : { $path }
+status_file_count = { $current } / { $total } fichiers
+summary_counts = { $copied } fichiers copiés, { $skipped } ignorés, { $failed } en échec
+summary_bytes = { $bytes } écrits en { $seconds }
+status_starting_in = Démarrage dans { $seconds }...
+hint_update_size = Cette mise à jour écrira environ { $bytes }
+plan_summary = { $count } opérations prévues :
+confirm_summary = { $files } fichiers ({ $size }) seront écrits et { $deleted } supprimés
+plan_create = Créer : { $path }
+plan_overwrite = Remplacer : { $path }
+plan_rename_exe = Renommer : { $from } -> { $to }
+error_inner_path_not_found = Répertoire introuvable dans le paquet de mise à jour : { $inner_path }
+error_package_empty = Le paquet de mise à jour semble vide, il ne contient aucun fichier
+error_package_empty_in = Le paquet de mise à jour semble vide, { $inner_path } dans l'archive ne contient aucun fichier
+error_volume_unknown = Impossible de déterminer le volume du chemin cible : { $path }
+error_expected_file_missing = { $name } est introuvable dans le répertoire cible { $target } ; vérifiez que le chemin cible est correct
+error_target_not_writable = Impossible d'écrire dans le répertoire cible { $path } : { $err }
+error_insufficient_space = Espace insuffisant sur le volume cible : { $needed } nécessaires, { $available } disponibles
+error_temp_space_exhausted = Espace disque temporaire épuisé pendant l'extraction, les fichiers temporaires ont été supprimés : { $path } ; libérez de l'espace ou utilisez --temp-dir pour extraire sur un autre volume
+error_package_not_found = Paquet de mise à jour introuvable : { $path }
+error_invalid_package_metadata = update.json invalide dans le paquet de mise à jour : { $reason }
+error_stdin_args = Impossible de lire les arguments depuis l'entrée standard : { $reason }
+error_undefined_variable = Le chemin cible { $target } du paquet de mise à jour utilise la variable d'environnement non définie { $name }
+error_updater_too_old = Le paquet de mise à jour nécessite la version { $required } ou ultérieure du programme de mise à jour, celle-ci est la version { $current }
+error_invalid_version = Version invalide : { $value } (version sémantique attendue, par exemple 1.2.3)
+error_not_newer = La version du paquet { $package } n'est pas plus récente que la version installée { $current }, utilisez --allow-downgrade pour rétrograder ou réinstaller
+error_current_too_old = La version installée { $current } est trop ancienne, ce paquet ne peut mettre à jour que la version { $min } ou ultérieure
+error_invalid_package = Le paquet de mise à jour n'est pas un fichier ZIP valide : { $err }
+error_invalid_tar_package = Le paquet de mise à jour n'est pas un fichier tar.gz valide : { $err }
+error_package_not_directory = --format dir a été indiqué, mais le paquet de mise à jour n'est pas un répertoire : { $path }
+error_invalid_auto_close = Délai de fermeture automatique invalide : { $value }
+error_invalid_delay = Valeur de délai invalide : { $value }
+error_unexpected_argument = Argument inattendu : { $arg }
+error_missing_value = Valeur manquante pour l'option : { $flag }
+error_invalid_language = Langue invalide : { $value }
+error_unknown_option = Option inconnue : { $flag }
+status_transfer = Vitesse : { $bytes_per_second }/s, environ { $eta_seconds } restantes
+error_invalid_jobs = Nombre de threads invalide : { $value }
+error_invalid_theme = Thème invalide : { $value } (light, dark ou system attendu)
+error_invalid_config = Échec de la lecture du fichier de configuration { $path } : { $reason }
+error_invalid_scale = Échelle de l'interface invalide (nombre de 0.5 à 4 ou auto attendu) : { $value }
+error_invalid_size = Taille de fenêtre invalide : { $value }
+error_invalid_glob = Motif glob invalide { $pattern } : { $reason }
+status_copying_excluded = { $excluded } fichiers exclus
+status_copying_unchanged = { $unchanged } fichiers inchangés ignorés
+status_copying_details = Copie des fichiers... ({ $details })
+list_separator = { ", " }
+error_verify_failed = Échec de la vérification, le fichier copié diffère du paquet : { $file_name }
+error_invalid_retries = Nombre de tentatives invalide : { $value }
+error_flatten_collision = L'aplatissement associe plusieurs fichiers à { $name } : { $first } et { $second }
+error_incompatible_options = { $first } ne peut pas être utilisé avec { $second }
+error_invalid_target_subdir = Le sous-dossier cible doit être un chemin relatif sans .. : { $value }
+error_invalid_suffix = Suffixe de copie préparée invalide : { $value }
+error_invalid_timeout = Délai d'expiration invalide : { $value }
+status_timed_out = Aucune progression depuis { $seconds } secondes, abandon de la mise à jour...
+error_timed_out = Délai dépassé : aucune progression depuis { $seconds } secondes, la mise à jour a été abandonnée
+status_retrying = Fichier occupé, nouvelle tentative ({ $attempt }/{ $max }) : { $file_name }
+plan_delete = Supprimer : { $path }
+status_pruning = Suppression d'un fichier absent de la nouvelle version : { $file_name }
+status_mapping = Mise à jour ({ $index }/{ $total }) : { $inner } -> { $target }
+error_invalid_map = Correspondance invalide : { $value } (<chemin interne>:<chemin cible> attendu)
+status_partial_complete = Mise à jour incomplète, { $count } fichiers en échec :
+status_waiting_for_locked = En attente de la libération de { $count } fichiers en cours d'utilisation : { $first }...
+error_files_locked = { $count } fichiers sont utilisés par un autre programme ; fermez-le et réessayez : { $first }
+error_invalid_lock_policy = Stratégie pour les fichiers verrouillés invalide (wait, abort ou stage attendu) : { $value }
+error_invalid_format = Format de paquet invalide (zip, tar.gz ou dir attendu) : { $value }
+status_running_hook = Exécution de la commande : { $command }
+error_hook_failed = Échec de la commande (code de sortie { $code }) : { $command }
+error_invalid_pubkey = Clé publique ed25519 invalide : { $path }
+error_invalid_signature_file = Fichier de signature ed25519 invalide : { $path }
+warning_unsafe_entries = Avertissement : { $count } entrées du paquet avec des chemins dangereux ont été ignorées (absolus, contenant .. ou sortant par un lien symbolique)
+warning_format_mismatch = Avertissement : le format du paquet est { $format }, mais son contenu ressemble à { $detected } ; il est traité comme { $format }
+prompt_conflict =
+    Le fichier cible est en lecture seule ou protégé. Le remplacer ?
+    { $path }
+warning_protected_skipped = Avertissement : { $count } fichiers protégés ont été ignorés comme demandé, ils n'ont pas été modifiés
+warning_newer_kept = Avertissement : { $count } fichiers de la cible sont plus récents que dans le paquet et ont été conservés
+warning_package_not_removed = Mise à jour réussie, mais le fichier du paquet n'a pas pu être supprimé : { $path } : { $reason }
+warning_restart_required = Un redémarrage est nécessaire pour terminer la mise à jour de { $count } fichiers.
+error_audit_not_opened = Impossible de créer le fichier d'audit { $path } : { $reason }
+warning_receipt_not_written = Mise à jour réussie, mais le reçu de mise à jour n'a pas pu être écrit dans { $target } : { $reason }
+error_inner_path_ambiguous = Plusieurs répertoires du paquet de mise à jour correspondent à { $pattern } : { $matches }
+error_unsafe_inner_path = Le chemin dans le paquet ne doit pas être absolu ni contenir .. : { $path }
+error_patch_base_missing = Le fichier à corriger n'existe pas dans la cible, impossible d'appliquer la mise à jour delta : { $file }
+error_patch_failed = Échec de l'application du correctif à { $file } : { $err }
+error_manifest_missing = Un fichier listé dans le manifeste des sommes de contrôle est absent du paquet : { $file }
+error_manifest_mismatch = Le SHA-256 du fichier ne correspond pas au manifeste, le paquet est peut-être corrompu : { $file }
+status_killing_process = Arrêt de { $count } processus { $name } en cours d'exécution...
+error_kill_process_failed = Impossible d'arrêter le processus en cours d'exécution : { $name }
+usage =
+    Utilisation : software_updater --package <chemin> --target <chemin> [options]
+
+      --package <path>      Chemin du paquet de mise à jour, un fichier ZIP ou tar.gz ou un répertoire extrait (obligatoire)
+      --inner-path <path>   Dossier ou fichier de l'archive à copier, la racine par défaut ; un glob (par ex. myapp-*) sélectionne l'unique dossier correspondant
+      --target <path>       Répertoire cible (obligatoire sauf si --map, --self-dir ou update.json/target.txt dans le paquet en fournit un)
+      --self-dir            Mettre à jour le répertoire du programme de mise à jour lui-même lorsque --target est absent
+      --map <inner>:<target>
+                            Copier un dossier du paquet vers un répertoire cible ; répétable, remplace --inner-path/--target
+      --expect-file <name>  Fichier qui doit déjà exister dans la cible (par ex. l'exécutable principal), abandon s'il manque ; répétable
+      --current-version <version>
+                            Version installée, abandon si la version du paquet n'est pas plus récente
+      --allow-downgrade     Autoriser les paquets qui ne sont pas plus récents que la version installée
+      --delay <seconds>     Délai en secondes avant le démarrage, 0 par défaut
+      --start-exe <path>    Exécutable à lancer après une mise à jour réussie
+      --auto-close <seconds>
+                            Fermer la fenêtre ce nombre de secondes après une mise à jour réussie (pas en cas d'échec)
+      --kill-process <name> Arrêter les processus portant ce nom avant la copie (proprement, puis de force)
+      --pre-hook <command>  Commande shell exécutée dans la cible avant l'extraction, annule la mise à jour si le code de sortie n'est pas 0
+      --post-hook <command> Commande shell exécutée dans la cible après la copie ; en cas d'échec le paquet est conservé
+      --elevate             (Windows uniquement) Relancer avec les droits d'administrateur si nécessaire
+      --pubkey <path>       Clé publique ed25519 servant à vérifier la signature du paquet
+      --sig <path>          Signature ed25519 détachée du paquet, vérifiée avant l'extraction
+      --password <password> Mot de passe d'un paquet chiffré
+      --lang <zh|en|ja|ko|de|fr>
+                            Langue de l'interface, selon les paramètres régionaux du système par défaut
+      --dry-run             Lister les opérations prévues sans modifier de fichiers
+      --confirm             Lister les opérations prévues après l'extraction et attendre une confirmation
+      --verify              Vérifier le SHA-256 de chaque fichier copié et annuler en cas de différence
+      --prune               Supprimer les fichiers de la cible absents de la nouvelle version (sauf ceux de --exclude)
+      --continue-on-error   Poursuivre la copie quand un fichier échoue et lister les fichiers en échec à la fin
+      --skip-unchanged      Ignorer les fichiers identiques à ceux de la cible
+      --skip-newer          Ne pas écraser les fichiers de la cible plus récents que ceux du paquet
+      --delta               Appliquer les fichiers .patch du paquet comme correctifs binaires aux fichiers existants
+      --flatten             Copier tous les fichiers directement dans la cible sans sous-dossiers, abandon en cas de noms en double
+      --keep-package        Conserver le paquet source après une mise à jour réussie (supprimé par défaut)
+      --fsync               Écrire chaque fichier sur le disque pour qu'une coupure de courant ne puisse pas le corrompre (plus lent)
+      --receipt             Écrire dans la cible update-receipt.json listant les fichiers installés
+      --audit-csv <path>    Écrire une ligne CSV par opération sur un fichier (heure, action, source, destination, octets, résultat)
+      --temp-dir <path>     Répertoire pour l'extraction et les sauvegardes, le répertoire temporaire du système par défaut
+      --format <format>     Format du paquet (zip, tar.gz ou dir), détecté par défaut d'après le contenu et l'extension
+      --resumable           Enregistrer les fichiers copiés, ne pas annuler en cas d'échec et reprendre à l'exécution suivante
+      --target-subdir <name>
+                            Installer dans ce sous-dossier de la cible (par ex. versions/1.2.0), créé si nécessaire
+      --self-suffix <ext>   Suffixe de la copie préparée du programme en cours sous Windows, .new par défaut
+      --on-locked <wait|abort|stage>
+                            Quand un fichier à remplacer est utilisé : attendre, abandonner ou le préparer pour le redémarrage (par défaut)
+      --no-self-stage       Ne jamais préparer le programme en cours ; le remplacer directement (s'il n'est pas dans la cible)
+      --self-test           Vérifier le paquet, les droits sur la cible, l'espace disque et les polices, afficher le rapport et quitter
+      --inspect             Lister les entrées du paquet en surlignant le répertoire --inner-path, puis quitter
+      --atomic              Construire la nouvelle version dans un répertoire temporaire voisin, puis l'échanger
+      --no-preserve-mtime   Ne pas conserver les dates de modification ; les fichiers copiés reçoivent l'heure actuelle
+      --preserve-hardlinks  (Unix uniquement) Copier une seule fois les fichiers liés physiquement dans le paquet et créer des liens physiques pour les autres chemins
+      --dedup               Ne copier qu'une fois depuis le paquet les fichiers au contenu identique et copier les autres destinations depuis la première copie
+      --json                Fonctionner sans fenêtre et indiquer la progression par un objet JSON par ligne sur stdout
+      --silent              Fonctionner sans fenêtre, journaliser le résultat et afficher une notification système
+      --config <path>       Fichier de configuration, updater.toml à côté de l'exécutable par défaut ; les options sont prioritaires
+      --args-from-stdin     Lire d'autres arguments depuis l'entrée standard, un par ligne ou séparés par NUL
+      --log <path>          Chemin du fichier journal, updater.log à côté de l'exécutable par défaut
+      --changelog <path>    Notes de version affichées pendant le délai, CHANGELOG.md du paquet par défaut
+      --show-size           Afficher pendant le délai la taille totale que la mise à jour écrira
+      --jobs <count>        Nombre de threads de copie parallèles, le nombre de processeurs par défaut
+      --retries <count>     Nombre de tentatives quand un fichier est occupé, 3 par défaut
+      --timeout <seconds>   Abandonner et annuler sans progression pendant ce nombre de secondes
+      --include <glob>      Ne copier que les fichiers correspondant au glob, répétable
+      --exclude <glob>      Ignorer les fichiers correspondant au glob, répétable, prioritaire sur --include
+      --confirm-overwrite   Demander avant de remplacer des fichiers en lecture seule ou protégés
+      --protect <glob>      Avec --confirm-overwrite, traiter les fichiers correspondants (par ex. les configurations) comme protégés, répétable
+      --width <pixels>      Largeur de la fenêtre, 450 par défaut
+      --height <pixels>     Hauteur de la fenêtre, 250 par défaut
+      --resizable           Autoriser le redimensionnement de la fenêtre
+      --no-topmost          Ne pas garder la fenêtre au-dessus des autres fenêtres
+      --no-center           Ne pas centrer la fenêtre sur l'écran
+      --scale <factor|auto>
+                            Facteur d'échelle de l'interface (0.5 à 4), selon le DPI du système par défaut
+      --theme <light|dark|system> Thème de la fenêtre, light par défaut ; system suit le système d'exploitation
+      -h, --help            Afficher ce message d'aide
+
+    L'ancienne forme à arguments positionnels est toujours acceptée mais obsolète :
+      software_updater <package_path> [zip_inner_path] <target_path> [delay_seconds] [start_exe_path] [zh|en|ja|ko|de|fr]
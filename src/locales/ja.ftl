@@ -0,0 +1,245 @@
+# 软件更新程序的日文界面文本
+# 格式为 Fluent 的子集：键 = 文本，{ $名称 } 为参数，{ "文本" } 为原样输出的文本，
+# 多行文本从下一行开始并缩进，缺少的键使用英文文本
+
+title = ソフトウェア更新
+status_preparing = 更新を準備しています...
+status_waiting_for_app = アプリケーションが終了するのを待っています...
+status_complete = ソフトウェアの更新が完了しました！
+status_failed = ソフトウェアの更新に失敗しました！
+button_ok = OK
+button_update_now = 今すぐ更新
+hint_cancel_delay = Esc キーで更新をキャンセル
+button_overwrite = 上書き
+button_skip = スキップ
+button_overwrite_all = すべて上書き
+button_skip_all = すべてスキップ
+confirm_title = 更新を適用しますか？
+button_apply = 適用
+button_cancel = キャンセル
+button_browse = 参照...
+button_start = 更新を開始
+form_package = 更新パッケージ:
+form_target = ターゲット:
+form_language = 表示言語:
+label_details = 詳細
+self_test_title = 自己診断の結果
+check_package = 更新パッケージ
+check_target_writable = ターゲットへの書き込み
+check_disk_space = ディスク容量
+check_fonts = 画面フォント
+check_pass = 合格
+check_warning = 警告
+check_fail = 不合格
+inspect_title = パッケージの内容
+check_font_fallback = 中国語のシステムフォントが見つからないため、内蔵の代替フォントを使用します
+status_dry_run_complete = ドライラン完了、ファイルは変更されていません
+status_extracting = 更新パッケージを展開しています...
+status_copying = ファイルをコピーしています...
+status_rolling_back = 更新に失敗しました。元のファイルを復元しています...
+status_checkpoint_saved = 更新に失敗しました。進捗を保存したため、次回の実行で中断箇所から再開します
+status_staging = 一時ディレクトリに新しいバージョンを準備しています...
+status_swapping = ターゲットディレクトリを入れ替えています...
+phase_extract = 現在の段階: 展開
+phase_verify = 現在の段階: 検証
+phase_copy = 現在の段階: コピー
+phase_finalize = 現在の段階: 仕上げ
+status_swap_fallback = ターゲットディレクトリを入れ替えられないため、ファイルを直接更新しています...
+status_verifying_signature = 更新パッケージの署名を検証しています...
+status_verifying_manifest = チェックサム一覧でファイルを検証しています...
+status_applying_patches = 差分パッチを適用しています...
+error_signature_missing = 更新パッケージには署名が必要です。--sig で署名ファイルを指定してください
+error_signature_mismatch = 更新パッケージの署名検証に失敗しました。パッケージが改ざんされている可能性があります
+error_no_pubkey = 署名ファイルが指定されましたが公開鍵がありません。--pubkey で公開鍵を指定してください
+error_directory_unsigned = 更新パッケージがディレクトリのため署名を検証できません。署名が必要な場合はアーカイブを使用してください
+error_elevation_declined = 管理者権限が得られなかったため、更新を中止しました
+error_update_declined = 更新をキャンセルしました。ターゲットは変更されていません
+error_no_package = 更新パッケージのパスが指定されていません
+error_no_target = ターゲットパスを指定する必要があります
+error_password_required = 更新パッケージは暗号化されています。--password でパスワードを指定してください
+error_password_invalid = 更新パッケージのパスワードが正しくありません
+bytes_small = { $bytes } バイト
+decimal_separator = .
+bytes_kb = { $value } KB
+bytes_mb = { $value } MB
+bytes_gb = { $value } GB
+bytes_tb = { $value } TB
+bytes_pb = { $value } PB
+bytes_eb = { $value } EB
+duration_hours = { $hours }時間{ $minutes }分
+duration_minutes = { $minutes }分{ $seconds }秒
+duration_seconds = { $seconds }秒
+inspect_summary = { $entries } 個のエントリ、展開後 { $bytes }
+inspect_inner_path = パッケージ内のパス { $pattern } の対象: { $resolved }
+check_space_needed = 必要 { $needed }
+status_processing = 処理中: { $file_name }
+label_package = 更新パッケージ: { $name }
+label_target = ターゲット: { $path }
+status_file_count = { $current } / { $total } ファイル
+summary_counts = コピー { $copied } 個、スキップ { $skipped } 個、失敗 { $failed } 個
+summary_bytes = 書き込み { $bytes }、所要時間 { $seconds }
+status_starting_in = { $seconds }後に更新を開始します...
+hint_update_size = この更新で約 { $bytes } を書き込みます
+plan_summary = { $count } 件の操作を予定:
+confirm_summary = { $files } 個のファイル（{ $size }）を書き込み、{ $deleted } 個を削除します
+plan_create = 作成: { $path }
+plan_overwrite = 上書き: { $path }
+plan_rename_exe = 名前変更: { $from } -> { $to }
+error_inner_path_not_found = 更新パッケージに指定されたディレクトリがありません: { $inner_path }
+error_package_empty = 更新パッケージが空のようです。ファイルが含まれていません
+error_package_empty_in = 更新パッケージが空のようです。アーカイブ内の { $inner_path } にファイルがありません
+error_volume_unknown = ターゲットパスのボリュームを特定できません: { $path }
+error_expected_file_missing = ターゲットディレクトリ { $target } に { $name } が見つかりません。ターゲットパスが正しいか確認してください
+error_target_not_writable = ターゲットディレクトリに書き込めません { $path }: { $err }
+error_insufficient_space = ターゲットのディスク容量が不足しています: 必要 { $needed }、空き { $available }
+error_temp_space_exhausted = 一時ディレクトリのディスク容量が不足したため展開を中止し、一時ファイルを削除しました: { $path }。空き容量を増やすか、--temp-dir で別のドライブに展開してください
+error_package_not_found = 更新パッケージが見つかりません: { $path }
+error_invalid_package_metadata = 更新パッケージの update.json が無効です: { $reason }
+error_stdin_args = 標準入力から引数を読み取れません: { $reason }
+error_undefined_variable = 更新パッケージのターゲットパス { $target } が未定義の環境変数 { $name } を参照しています
+error_updater_too_old = 更新パッケージにはバージョン { $required } 以降の更新プログラムが必要です（現在のバージョン: { $current }）
+error_invalid_version = 無効なバージョン: { $value }（1.2.3 のようなセマンティックバージョンを指定してください）
+error_not_newer = 更新パッケージのバージョン { $package } はインストール済みのバージョン { $current } より新しくありません。ダウングレードや再インストールには --allow-downgrade を使用してください
+error_current_too_old = インストール済みのバージョン { $current } は古すぎます。この更新パッケージはバージョン { $min } 以降からのみ更新できます
+error_invalid_package = 更新パッケージは有効な ZIP ファイルではありません: { $err }
+error_invalid_tar_package = 更新パッケージは有効な tar.gz ファイルではありません: { $err }
+error_package_not_directory = --format dir が指定されましたが、更新パッケージはディレクトリではありません: { $path }
+error_invalid_auto_close = 無効な自動終了の秒数: { $value }
+error_invalid_delay = 無効な待機秒数: { $value }
+error_unexpected_argument = 認識できない引数: { $arg }
+error_missing_value = オプションの値がありません: { $flag }
+error_invalid_language = 無効な言語: { $value }
+error_unknown_option = 不明なオプション: { $flag }
+status_transfer = 速度: { $bytes_per_second }/秒、残り約 { $eta_seconds }
+error_invalid_jobs = 無効な並列数: { $value }
+error_invalid_theme = 無効なテーマ: { $value }（light、dark、system のいずれか）
+error_invalid_config = 設定ファイル { $path } を読み込めません: { $reason }
+error_invalid_scale = 無効な表示倍率（0.5～4 の数値または auto を指定してください）: { $value }
+error_invalid_size = 無効なウィンドウサイズ: { $value }
+error_invalid_glob = 無効なワイルドカードパターン { $pattern }: { $reason }
+status_copying_excluded = { $excluded } 個のファイルを除外
+status_copying_unchanged = 変更のない { $unchanged } 個のファイルをスキップ
+status_copying_details = ファイルをコピーしています...（{ $details }）
+list_separator = 、
+error_verify_failed = 検証に失敗しました。コピーしたファイルがパッケージと一致しません: { $file_name }
+error_invalid_retries = 無効な再試行回数: { $value }
+error_flatten_collision = フラットにコピーすると複数のファイルが同じ名前 { $name } になります: { $first } と { $second }
+error_incompatible_options = { $first } と { $second } は同時に指定できません
+error_invalid_target_subdir = ターゲットのサブフォルダーは .. を含まない相対パスである必要があります: { $value }
+error_invalid_suffix = 無効なステージング用サフィックス: { $value }
+error_invalid_timeout = 無効なタイムアウト: { $value }
+status_timed_out = { $seconds } 秒間進捗がないため、更新を中止しています...
+error_timed_out = 更新がタイムアウトしました: { $seconds } 秒間進捗がなかったため中止しました
+status_retrying = ファイルが使用中のため再試行しています ({ $attempt }/{ $max }): { $file_name }
+plan_delete = 削除: { $path }
+status_pruning = 新しいバージョンに存在しないファイルを削除しています: { $file_name }
+status_mapping = 更新しています ({ $index }/{ $total }): { $inner } -> { $target }
+error_invalid_map = 無効なディレクトリの対応付け: { $value }（<パッケージ内のパス>:<ターゲットパス> の形式）
+status_partial_complete = 更新は完了しませんでした。{ $count } 件のファイルが失敗しました:
+status_waiting_for_locked = 使用中の { $count } 個のファイルが解放されるのを待っています: { $first }...
+error_files_locked = { $count } 個のファイルが他のプログラムで使用中です。プログラムを閉じてから再試行してください: { $first }
+error_invalid_lock_policy = 無効な使用中ファイルの処理方法（wait、abort、stage のいずれか）: { $value }
+error_invalid_format = 無効なパッケージ形式です（zip、tar.gz、dir のいずれか）: { $value }
+status_running_hook = コマンドを実行しています: { $command }
+error_hook_failed = コマンドが失敗しました（終了コード { $code }）: { $command }
+error_invalid_pubkey = 無効な ed25519 公開鍵です: { $path }
+error_invalid_signature_file = 無効な ed25519 署名ファイルです: { $path }
+warning_unsafe_entries = 警告: 安全でないパス（絶対パス、.. を含む、またはシンボリックリンク経由でパッケージ外を指す）のエントリ { $count } 件をスキップしました
+warning_format_mismatch = 警告: パッケージ形式に { $format } が指定されましたが、内容からは { $detected } と判定されました。{ $format } として処理します
+prompt_conflict =
+    ターゲットのファイルは読み取り専用または保護されています。上書きしますか？
+    { $path }
+warning_protected_skipped = 警告: 選択に従い保護されたファイル { $count } 件をスキップしました（変更されていません）
+warning_newer_kept = 警告: ターゲット内の { $count } 個のファイルがパッケージより新しいため、上書きせずに保持しました
+warning_package_not_removed = 更新は成功しましたが、更新パッケージ { $path } を削除できませんでした: { $reason }
+warning_restart_required = { $count } 個のファイルが使用中のため、更新を完了するにはコンピューターの再起動が必要です
+error_audit_not_opened = 監査ファイル { $path } を作成できません: { $reason }
+warning_receipt_not_written = 更新は成功しましたが、{ $target } に更新レシートを書き込めませんでした: { $reason }
+error_inner_path_ambiguous = 更新パッケージ内の複数のディレクトリが { $pattern } に一致します: { $matches }
+error_unsafe_inner_path = パッケージ内のパスに絶対パスや .. は使用できません: { $path }
+error_patch_base_missing = パッチ対象のファイルがターゲットにないため、差分更新を適用できません: { $file }
+error_patch_failed = パッチを適用できません { $file }: { $err }
+error_manifest_missing = チェックサム一覧にあるファイルがパッケージにありません: { $file }
+error_manifest_mismatch = ファイルの SHA-256 がチェックサム一覧と一致しません。パッケージが破損している可能性があります: { $file }
+status_killing_process = 実行中の { $name } プロセス { $count } 件を終了しています...
+error_kill_process_failed = 実行中のプロセスを終了できません: { $name }
+usage =
+    使い方: software_updater --package <パス> --target <パス> [オプション]
+
+      --package <パス>      更新パッケージのパス、ZIP/tar.gz ファイルまたは展開済みのディレクトリ（必須）
+      --inner-path <パス>   コピーするアーカイブ内のフォルダーまたは単一ファイル、既定はルート（ワイルドカード（例: myapp-*）で一致する唯一のフォルダーを選択可）
+      --target <パス>       ターゲットディレクトリ（--map、--self-dir、パッケージ内の update.json・target.txt で指定しない場合は必須）
+      --self-dir            --target を指定しない場合はアップデーター自身のディレクトリを更新
+      --map <内部パス>:<ターゲット>
+                            パッケージ内のフォルダーを指定したディレクトリにコピー（複数指定可、--inner-path/--target の代わり）
+      --expect-file <名前>  更新前にターゲットに存在する必要があるファイル（メインの exe など）、ない場合は中止、複数指定可
+      --current-version <バージョン>
+                            インストール済みのバージョン、パッケージの方が新しくない場合は中止
+      --allow-downgrade     インストール済みより新しくないパッケージの適用を許可
+      --delay <秒>          開始前の待機秒数、既定値は0
+      --start-exe <パス>    更新成功後に起動する実行ファイル
+      --auto-close <秒>     更新成功後、指定した秒数でウィンドウを自動的に閉じる（失敗時は閉じない）
+      --kill-process <名前> コピー前に指定した名前のプロセスを終了（通常終了後、必要なら強制終了）
+      --pre-hook <コマンド> 展開前にターゲットで実行するコマンド、終了コードが0以外なら更新を中止
+      --post-hook <コマンド>
+                            コピー完了後にターゲットで実行するコマンド、失敗時はパッケージを残す
+      --elevate             （Windows のみ）管理者権限がない場合は昇格して再起動
+      --pubkey <パス>       パッケージ署名の検証に使う ed25519 公開鍵ファイル
+      --sig <パス>          パッケージの ed25519 分離署名ファイル（展開前に検証）
+      --password <パスワード> 暗号化されたパッケージのパスワード
+      --lang <zh|en|ja|ko|de|fr>
+                            表示言語、既定はシステムのロケール
+      --dry-run             ファイルを変更せずに予定の操作を一覧表示
+      --confirm             展開後に予定の操作を表示し、確認してからターゲットを変更する
+      --verify              コピー後に各ファイルの SHA-256 を検証し、不一致ならロールバック
+      --prune               新しいバージョンにないターゲットのファイルを削除（--exclude に一致するものを除く）
+      --continue-on-error   ファイルが失敗しても残りのコピーを続け、最後に失敗したファイルを一覧表示
+      --skip-unchanged      ターゲットと内容が同じファイルをスキップ
+      --skip-newer          パッケージより新しいターゲット内のファイルは上書きしない
+      --delta               パッケージ内の .patch ファイルを既存ファイルへのバイナリパッチとして適用
+      --flatten             サブフォルダーを作らずすべてのファイルをターゲット直下にコピー、名前が重複する場合は中止
+      --keep-package        更新成功後も元の更新パッケージを残す（既定では削除）
+      --fsync               書き込んだファイルをディスクに同期し、電源断でも破損しないようにする（低速）
+      --receipt             更新成功後、インストールしたファイルを記録した update-receipt.json をターゲットに書き込む
+      --audit-csv <パス>    ファイル操作ごとに1行（時刻、操作、元パス、先パス、バイト数、結果）をCSVファイルに書き込む
+      --temp-dir <パス>     展開とバックアップに使う一時ディレクトリ、既定はシステムの一時ディレクトリ
+      --format <形式>       パッケージ形式（zip、tar.gz、dir）。既定では内容と拡張子から判定
+      --resumable           コピー済みのファイルを記録し、失敗時はロールバックせず次回の実行で中断箇所から再開
+      --target-subdir <名前>
+                            ターゲット内のサブフォルダー（例: versions/1.2.0）にインストールする（なければ作成）
+      --self-suffix <拡張子> Windows で実行中の更新プログラムを上書きする際に書き込むファイルのサフィックス、既定値は .new
+      --on-locked <wait|abort|stage>
+                            上書きするファイルが使用中の場合に解放を待つ・中止する・再起動時の置き換えを予約する（既定は stage）
+      --no-self-stage       実行中の更新プログラムをステージングせず直接上書きする（ターゲットに含まれない場合）
+      --self-test           更新せずに、パッケージ・ターゲットの権限・ディスク容量・フォントを確認して結果を表示
+      --inspect             更新せずに、パッケージ内のエントリを一覧表示し --inner-path のディレクトリを強調
+      --atomic              隣の一時ディレクトリに新しいバージョンを用意し、成功後にまとめて入れ替え
+      --no-preserve-mtime   ファイルの更新日時を保持せず、コピーしたファイルは現在時刻になる
+      --preserve-hardlinks  （Unixのみ）パッケージ内でハードリンクされたファイルを一度だけコピーし、他のパスはターゲットでハードリンクにする
+      --dedup               内容が同じファイルはパッケージから一度だけコピーし、他の場所には最初のコピーから複製する
+      --json                ウィンドウを表示せず、進捗を 1 行 1 つの JSON オブジェクトとして標準出力に出力
+      --silent              ウィンドウを表示せず、結果をログに記録してシステム通知で知らせる
+      --config <パス>       設定ファイル、既定は実行ファイルと同じ場所の updater.toml（コマンドラインが優先）
+      --args-from-stdin     残りの引数を標準入力から読み取る（1行に1つ、または NUL 区切り）
+      --log <パス>          ログファイルのパス、既定は実行ファイルと同じ場所の updater.log
+      --changelog <パス>    待機中に表示する更新内容、既定はパッケージ内の CHANGELOG.md
+      --show-size           待機中に今回の更新で書き込む合計サイズを表示
+      --jobs <数>           並列コピーのスレッド数、既定は CPU コア数
+      --retries <回数>      ファイル使用中の再試行回数、既定は 3
+      --timeout <秒数>      指定した秒数進捗がない場合は更新を中止してロールバック
+      --include <パターン>  一致するファイルのみコピー、複数指定可
+      --exclude <パターン>  一致するファイルをコピーしない、複数指定可、--include より優先
+      --confirm-overwrite   読み取り専用または保護されたファイルを上書きする前に確認
+      --protect <パターン>  --confirm-overwrite と併用し、一致するファイル（設定ファイルなど）を保護対象とする、複数指定可
+      --width <ピクセル>    ウィンドウの幅、既定は 450
+      --height <ピクセル>   ウィンドウの高さ、既定は 250
+      --resizable           ウィンドウのサイズ変更を許可
+      --no-topmost          ウィンドウを最前面に表示しない
+      --no-center           ウィンドウを画面中央に表示しない
+      --scale <倍率|auto>
+                            表示倍率（0.5～4）、既定はシステムの DPI に合わせて自動調整
+      --theme <light|dark|system> 画面のテーマ、既定は light、system は OS の設定に従う
+      -h, --help            このヘルプを表示
+
+    従来の位置引数形式も使用できますが、非推奨です:
+      software_updater <package_path> [zip_inner_path] <target_path> [delay_seconds] [start_exe_path] [zh|en|ja|ko|de|fr]
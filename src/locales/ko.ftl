@@ -0,0 +1,245 @@
+# 软件更新程序的韩文界面文本
+# 格式为 Fluent 的子集：键 = 文本，{ $名称 } 为参数，{ "文本" } 为原样输出的文本，
+# 多行文本从下一行开始并缩进，缺少的键使用英文文本
+
+title = 소프트웨어 업데이트
+status_preparing = 업데이트를 준비하는 중...
+status_waiting_for_app = 애플리케이션이 종료되기를 기다리는 중...
+status_complete = 소프트웨어 업데이트가 완료되었습니다!
+status_failed = 소프트웨어 업데이트에 실패했습니다!
+button_ok = 확인
+button_update_now = 지금 업데이트
+hint_cancel_delay = Esc 키를 누르면 업데이트를 취소합니다
+button_overwrite = 덮어쓰기
+button_skip = 건너뛰기
+button_overwrite_all = 모두 덮어쓰기
+button_skip_all = 모두 건너뛰기
+confirm_title = 업데이트를 적용하시겠습니까?
+button_apply = 적용
+button_cancel = 취소
+button_browse = 찾아보기...
+button_start = 업데이트 시작
+form_package = 업데이트 패키지:
+form_target = 대상 디렉터리:
+form_language = 언어:
+label_details = 자세히
+self_test_title = 자체 테스트 결과
+check_package = 업데이트 패키지
+check_target_writable = 대상 쓰기 가능
+check_disk_space = 디스크 공간
+check_fonts = UI 글꼴
+check_pass = 통과
+check_warning = 경고
+check_fail = 실패
+inspect_title = 패키지 내용
+check_font_fallback = 시스템에 중국어 글꼴이 없어 내장 대체 글꼴을 사용합니다
+status_dry_run_complete = 시험 실행이 완료되었습니다. 변경된 파일이 없습니다
+status_extracting = 업데이트 패키지의 압축을 푸는 중...
+status_copying = 파일을 복사하는 중...
+status_rolling_back = 업데이트에 실패하여 원래 파일을 복원하는 중...
+status_checkpoint_saved = 업데이트에 실패했습니다. 진행 상황이 저장되었으며 다음 실행 시 중단된 곳부터 계속합니다
+status_staging = 임시 디렉터리에서 새 버전을 준비하는 중...
+status_swapping = 새 대상 디렉터리로 교체하는 중...
+phase_extract = 현재 단계: 압축 해제
+phase_verify = 현재 단계: 검증
+phase_copy = 현재 단계: 복사
+phase_finalize = 현재 단계: 마무리
+status_swap_fallback = 대상 디렉터리를 교체할 수 없어 파일을 직접 업데이트하는 중...
+status_verifying_signature = 패키지 서명을 확인하는 중...
+status_verifying_manifest = 체크섬 목록으로 파일을 확인하는 중...
+status_applying_patches = 델타 패치를 적용하는 중...
+error_signature_missing = 패키지에 서명이 필요합니다. --sig로 서명 파일을 지정하세요
+error_signature_mismatch = 패키지 서명 확인에 실패했습니다. 패키지가 변조되었을 수 있습니다
+error_no_pubkey = 서명이 지정되었지만 공개 키가 없습니다. --pubkey로 지정하세요
+error_directory_unsigned = 패키지가 디렉터리라서 서명을 확인할 수 없습니다. 서명이 필요한 경우 압축 파일을 사용하세요
+error_elevation_declined = 관리자 권한이 허용되지 않아 업데이트가 취소되었습니다
+error_update_declined = 업데이트가 취소되었습니다. 대상 디렉터리는 변경되지 않았습니다
+error_no_package = 업데이트 패키지 경로가 지정되지 않았습니다
+error_no_target = 대상 경로를 지정해야 합니다
+error_password_required = 패키지가 암호화되어 있습니다. --password로 암호를 지정하세요
+error_password_invalid = 업데이트 패키지의 암호가 올바르지 않습니다
+bytes_small = { $bytes }바이트
+decimal_separator = .
+bytes_kb = { $value } KB
+bytes_mb = { $value } MB
+bytes_gb = { $value } GB
+bytes_tb = { $value } TB
+bytes_pb = { $value } PB
+bytes_eb = { $value } EB
+duration_hours = { $hours }시간 { $minutes }분
+duration_minutes = { $minutes }분 { $seconds }초
+duration_seconds = { $seconds }초
+inspect_summary = 항목 { $entries }개, 압축 해제 시 { $bytes }
+inspect_inner_path = 내부 경로 { $pattern }에 해당하는 항목: { $resolved }
+check_space_needed = { $needed } 필요
+status_processing = 처리 중: { $file_name }
+label_package = 패키지: { $name }
+label_target = 대상: { $path }
+status_file_count = { $current } / { $total }개 파일
+summary_counts = 파일 { $copied }개 복사, { $skipped }개 건너뜀, { $failed }개 실패
+summary_bytes = { $seconds } 동안 { $bytes } 기록
+status_starting_in = { $seconds } 후 시작합니다...
+hint_update_size = 이 업데이트는 약 { $bytes }를 기록합니다
+plan_summary = 예정된 작업 { $count }개:
+confirm_summary = 파일 { $files }개({ $size })를 기록하고 { $deleted }개를 삭제합니다
+plan_create = 생성: { $path }
+plan_overwrite = 덮어쓰기: { $path }
+plan_rename_exe = 이름 변경: { $from } -> { $to }
+error_inner_path_not_found = 업데이트 패키지에서 디렉터리를 찾을 수 없습니다: { $inner_path }
+error_package_empty = 업데이트 패키지가 비어 있는 것 같습니다. 파일이 없습니다
+error_package_empty_in = 업데이트 패키지가 비어 있는 것 같습니다. 압축 파일 안의 { $inner_path }에 파일이 없습니다
+error_volume_unknown = 대상 경로의 볼륨을 확인할 수 없습니다: { $path }
+error_expected_file_missing = 대상 디렉터리 { $target }에서 { $name }을(를) 찾을 수 없습니다. 대상 경로가 올바른지 확인하세요
+error_target_not_writable = 대상 디렉터리 { $path }에 쓸 수 없습니다: { $err }
+error_insufficient_space = 대상 볼륨의 공간이 부족합니다: { $needed } 필요, { $available } 사용 가능
+error_temp_space_exhausted = 압축을 푸는 중 임시 디스크 공간이 부족하여 임시 파일을 삭제했습니다: { $path }. 공간을 확보하거나 --temp-dir로 다른 볼륨에 압축을 푸세요
+error_package_not_found = 업데이트 패키지를 찾을 수 없습니다: { $path }
+error_invalid_package_metadata = 업데이트 패키지의 update.json이 올바르지 않습니다: { $reason }
+error_stdin_args = 표준 입력에서 인수를 읽을 수 없습니다: { $reason }
+error_undefined_variable = 업데이트 패키지의 대상 경로 { $target }에 정의되지 않은 환경 변수 { $name }이(가) 사용되었습니다
+error_updater_too_old = 이 업데이트 패키지에는 업데이터 버전 { $required } 이상이 필요합니다. 현재 버전은 { $current }입니다
+error_invalid_version = 잘못된 버전: { $value } (1.2.3과 같은 시맨틱 버전이어야 합니다)
+error_not_newer = 패키지 버전 { $package }이(가) 설치된 버전 { $current }보다 새롭지 않습니다. 다운그레이드하거나 다시 설치하려면 --allow-downgrade를 사용하세요
+error_current_too_old = 설치된 버전 { $current }이(가) 너무 오래되었습니다. 이 패키지는 버전 { $min } 이상만 업데이트할 수 있습니다
+error_invalid_package = 업데이트 패키지가 올바른 ZIP 파일이 아닙니다: { $err }
+error_invalid_tar_package = 업데이트 패키지가 올바른 tar.gz 파일이 아닙니다: { $err }
+error_package_not_directory = --format dir 이 지정되었지만 업데이트 패키지가 디렉터리가 아닙니다: { $path }
+error_invalid_auto_close = 잘못된 자동 닫기 지연 시간: { $value }
+error_invalid_delay = 잘못된 지연 시간: { $value }
+error_unexpected_argument = 예상하지 못한 인수: { $arg }
+error_missing_value = 옵션에 값이 없습니다: { $flag }
+error_invalid_language = 잘못된 언어: { $value }
+error_unknown_option = 알 수 없는 옵션: { $flag }
+status_transfer = 속도: { $bytes_per_second }/s, 약 { $eta_seconds } 남음
+error_invalid_jobs = 잘못된 스레드 수: { $value }
+error_invalid_theme = 잘못된 테마: { $value } (light, dark 또는 system이어야 합니다)
+error_invalid_config = 구성 파일 { $path }을(를) 읽지 못했습니다: { $reason }
+error_invalid_scale = 잘못된 UI 배율 (0.5에서 4 사이의 숫자 또는 auto여야 합니다): { $value }
+error_invalid_size = 잘못된 창 크기: { $value }
+error_invalid_glob = 잘못된 glob 패턴 { $pattern }: { $reason }
+status_copying_excluded = 파일 { $excluded }개 제외
+status_copying_unchanged = 변경되지 않은 파일 { $unchanged }개 건너뜀
+status_copying_details = 파일을 복사하는 중... ({ $details })
+list_separator = { ", " }
+error_verify_failed = 확인에 실패했습니다. 복사된 파일이 패키지와 다릅니다: { $file_name }
+error_invalid_retries = 잘못된 재시도 횟수: { $value }
+error_flatten_collision = 평면화하면 여러 파일이 { $name }에 해당합니다: { $first } 및 { $second }
+error_incompatible_options = { $first }은(는) { $second }와(과) 함께 사용할 수 없습니다
+error_invalid_target_subdir = 대상 하위 폴더는 ..이 없는 상대 경로여야 합니다: { $value }
+error_invalid_suffix = 잘못된 준비 파일 접미사: { $value }
+error_invalid_timeout = 잘못된 시간 제한: { $value }
+status_timed_out = { $seconds }초 동안 진행이 없어 업데이트를 중단하는 중...
+error_timed_out = 업데이트 시간이 초과되었습니다: { $seconds }초 동안 진행이 없어 업데이트가 중단되었습니다
+status_retrying = 파일이 사용 중입니다. 다시 시도하는 중 ({ $attempt }/{ $max }): { $file_name }
+plan_delete = 삭제: { $path }
+status_pruning = 새 버전에 없는 파일을 삭제하는 중: { $file_name }
+status_mapping = 업데이트하는 중 ({ $index }/{ $total }): { $inner } -> { $target }
+error_invalid_map = 잘못된 매핑: { $value } (<내부 경로>:<대상 경로> 형식이어야 합니다)
+status_partial_complete = 업데이트가 완료되지 않았습니다. 파일 { $count }개가 실패했습니다:
+status_waiting_for_locked = 사용 중인 파일 { $count }개가 해제되기를 기다리는 중: { $first }...
+error_files_locked = 파일 { $count }개를 다른 프로그램이 사용 중입니다. 프로그램을 닫고 다시 시도하세요: { $first }
+error_invalid_lock_policy = 잘못된 사용 중인 파일 처리 방식 (wait, abort 또는 stage여야 합니다): { $value }
+error_invalid_format = 잘못된 패키지 형식입니다(zip, tar.gz 또는 dir): { $value }
+status_running_hook = 명령을 실행하는 중: { $command }
+error_hook_failed = 명령이 실패했습니다 (종료 코드 { $code }): { $command }
+error_invalid_pubkey = 잘못된 ed25519 공개 키: { $path }
+error_invalid_signature_file = 잘못된 ed25519 서명 파일: { $path }
+warning_unsafe_entries = 경고: 안전하지 않은 경로(절대 경로, .. 포함 또는 심볼릭 링크를 통한 외부 경로)를 가진 패키지 항목 { $count }개를 건너뛰었습니다
+warning_format_mismatch = 경고: 패키지 형식이 { $format }(으)로 지정되었지만 내용은 { $detected }(으)로 보입니다. { $format }(으)로 처리합니다
+prompt_conflict =
+    대상 파일이 읽기 전용이거나 보호되어 있습니다. 덮어쓰시겠습니까?
+    { $path }
+warning_protected_skipped = 경고: 선택에 따라 보호된 파일 { $count }개를 건너뛰었으며 변경되지 않았습니다
+warning_newer_kept = 경고: 대상의 파일 { $count }개가 패키지보다 최신이어서 덮어쓰지 않고 유지했습니다
+warning_package_not_removed = 업데이트에 성공했지만 패키지 파일을 삭제할 수 없습니다: { $path }: { $reason }
+warning_restart_required = 파일 { $count }개의 업데이트를 완료하려면 다시 시작해야 합니다.
+error_audit_not_opened = 감사 파일 { $path }을(를) 만들 수 없습니다: { $reason }
+warning_receipt_not_written = 업데이트에 성공했지만 { $target }에 업데이트 영수증을 쓸 수 없습니다: { $reason }
+error_inner_path_ambiguous = 업데이트 패키지에서 { $pattern }에 해당하는 디렉터리가 여러 개입니다: { $matches }
+error_unsafe_inner_path = 패키지 내부 경로는 절대 경로이거나 ..을 포함할 수 없습니다: { $path }
+error_patch_base_missing = 패치할 파일이 대상에 없어 델타 업데이트를 적용할 수 없습니다: { $file }
+error_patch_failed = { $file }에 패치를 적용하지 못했습니다: { $err }
+error_manifest_missing = 체크섬 목록에 있는 파일이 패키지에 없습니다: { $file }
+error_manifest_mismatch = 파일의 SHA-256이 체크섬 목록과 일치하지 않습니다. 패키지가 손상되었을 수 있습니다: { $file }
+status_killing_process = 실행 중인 { $name } 프로세스 { $count }개를 종료하는 중...
+error_kill_process_failed = 실행 중인 프로세스를 종료할 수 없습니다: { $name }
+usage =
+    사용법: software_updater --package <경로> --target <경로> [옵션]
+
+      --package <path>      업데이트 패키지 경로, ZIP/tar.gz 파일 또는 압축을 푼 디렉터리 (필수)
+      --inner-path <path>   압축 파일에서 복사할 폴더 또는 단일 파일, 기본값은 루트; glob(예: myapp-*)은 일치하는 유일한 폴더를 선택
+      --target <path>       대상 디렉터리 (--map, --self-dir 또는 패키지의 update.json/target.txt로 지정하지 않으면 필수)
+      --self-dir            --target이 없을 때 업데이터 자신의 디렉터리를 업데이트
+      --map <inner>:<target>
+                            패키지의 폴더를 대상 디렉터리로 복사; 반복 가능하며 --inner-path/--target을 대체
+      --expect-file <name>  대상에 이미 있어야 하는 파일 (예: 주 실행 파일), 없으면 중단; 반복 가능
+      --current-version <version>
+                            설치된 버전, 패키지 버전이 더 새롭지 않으면 중단
+      --allow-downgrade     설치된 버전보다 새롭지 않은 패키지도 허용
+      --delay <seconds>     시작 전 지연 시간(초), 기본값은 0
+      --start-exe <path>    업데이트 성공 후 실행할 프로그램
+      --auto-close <seconds>
+                            업데이트 성공 후 지정한 초가 지나면 창을 닫음 (실패 시에는 닫지 않음)
+      --kill-process <name> 복사 전에 이 이름의 프로세스를 종료 (정상 종료 후 강제 종료)
+      --pre-hook <command>  압축 해제 전에 대상 디렉터리에서 실행할 명령, 종료 코드가 0이 아니면 업데이트 중단
+      --post-hook <command> 복사 완료 후 대상 디렉터리에서 실행할 명령, 실패하면 패키지를 유지
+      --elevate             (Windows 전용) 관리자 권한이 없으면 관리자 권한으로 다시 실행
+      --pubkey <path>       패키지 서명 확인에 사용할 ed25519 공개 키
+      --sig <path>          패키지의 분리된 ed25519 서명, 압축을 풀기 전에 확인
+      --password <password> 암호화된 패키지의 암호
+      --lang <zh|en|ja|ko|de|fr>
+                            UI 언어, 기본값은 시스템 로캘에 따름
+      --dry-run             파일을 변경하지 않고 예정된 작업만 표시
+      --confirm             압축을 푼 후 예정된 작업을 표시하고 확인을 기다림
+      --verify              복사한 각 파일의 SHA-256을 확인하고 일치하지 않으면 롤백
+      --prune               새 버전에 없는 대상 파일을 삭제 (--exclude에 일치하는 파일 제외)
+      --continue-on-error   파일이 실패해도 계속 복사하고 마지막에 실패한 파일을 표시
+      --skip-unchanged      대상의 파일과 동일한 파일은 건너뜀
+      --skip-newer          패키지보다 최신인 대상 파일은 덮어쓰지 않음
+      --delta               패키지의 .patch 파일을 기존 파일에 바이너리 패치로 적용
+      --flatten             모든 파일을 하위 폴더 없이 대상에 바로 복사, 이름이 중복되면 중단
+      --keep-package        업데이트 성공 후 원본 패키지를 유지 (기본적으로 삭제)
+      --fsync               정전 시 손상되지 않도록 기록한 모든 파일을 디스크에 플러시 (더 느림)
+      --receipt             설치된 파일 목록을 담은 update-receipt.json을 대상에 기록
+      --audit-csv <path>    파일 작업마다 CSV 행을 하나씩 기록 (시간, 작업, 원본, 대상, 바이트, 결과)
+      --temp-dir <path>     압축 해제와 백업에 사용할 임시 디렉터리, 기본값은 시스템 임시 디렉터리
+      --format <형식>       패키지 형식(zip, tar.gz 또는 dir), 기본값은 내용과 확장자로 판별
+      --resumable           복사한 파일을 기록하고 실패 시 롤백하지 않으며 다음 실행 때 이어서 진행
+      --target-subdir <name>
+                            대상의 이 하위 폴더에 설치 (예: versions/1.2.0), 필요하면 생성
+      --self-suffix <ext>   Windows에서 실행 중인 업데이터의 준비 복사본 접미사, 기본값은 .new
+      --on-locked <wait|abort|stage>
+                            덮어쓸 파일이 사용 중일 때: 대기, 중단 또는 다시 시작 시 교체하도록 준비 (기본값)
+      --no-self-stage       실행 중인 업데이터를 준비하지 않고 직접 덮어씀 (대상에 있지 않은 경우)
+      --self-test           패키지, 대상 권한, 디스크 공간과 글꼴을 확인하고 결과를 보고한 후 종료
+      --inspect             패키지 항목을 표시하고 --inner-path 디렉터리를 강조한 후 종료
+      --atomic              옆의 임시 디렉터리에 새 버전을 만든 후 교체
+      --no-preserve-mtime   파일 수정 시간을 유지하지 않음; 복사한 파일은 현재 시간을 가짐
+      --preserve-hardlinks  (Unix 전용) 패키지에서 하드 링크된 파일은 한 번만 복사하고 나머지 경로는 대상에 하드 링크로 생성
+      --dedup               내용이 같은 파일은 패키지에서 한 번만 복사하고 나머지 위치에는 첫 번째 사본에서 복사
+      --json                창 없이 실행하고 진행 상황을 stdout에 한 줄에 하나의 JSON 객체로 출력
+      --silent              창 없이 실행하고 결과를 로그에 기록한 후 시스템 알림을 표시
+      --config <path>       구성 파일, 기본값은 실행 파일 옆의 updater.toml; 명령줄 옵션이 우선
+      --args-from-stdin     표준 입력에서 인수를 더 읽음, 한 줄에 하나 또는 NUL로 구분
+      --log <path>          로그 파일 경로, 기본값은 실행 파일 옆의 updater.log
+      --changelog <path>    지연 중에 표시할 릴리스 노트, 기본값은 패키지의 CHANGELOG.md
+      --show-size           지연 중에 업데이트가 기록할 전체 크기를 표시
+      --jobs <count>        병렬 복사 스레드 수, 기본값은 CPU 수
+      --retries <count>     파일이 사용 중일 때의 재시도 횟수, 기본값은 3
+      --timeout <seconds>   지정한 초 동안 진행이 없으면 중단하고 롤백
+      --include <glob>      glob에 일치하는 파일만 복사, 반복 가능
+      --exclude <glob>      glob에 일치하는 파일은 건너뜀, 반복 가능하며 --include보다 우선
+      --confirm-overwrite   읽기 전용이거나 보호된 파일을 덮어쓰기 전에 확인
+      --protect <glob>      --confirm-overwrite와 함께 일치하는 파일(예: 구성 파일)을 보호된 파일로 취급, 반복 가능
+      --width <pixels>      창 너비, 기본값은 450
+      --height <pixels>     창 높이, 기본값은 250
+      --resizable           창 크기 조정을 허용
+      --no-topmost          창을 다른 창 위에 유지하지 않음
+      --no-center           창을 화면 가운데에 두지 않음
+      --scale <factor|auto>
+                            UI 배율 (0.5에서 4), 기본값은 시스템 DPI에 따름
+      --theme <light|dark|system> 창 테마, 기본값은 light; system은 운영 체제 설정을 따름
+      -h, --help            이 도움말을 표시
+
+    이전의 위치 인수 형식도 사용할 수 있지만 더 이상 권장되지 않습니다:
+      software_updater <package_path> [zip_inner_path] <target_path> [delay_seconds] [start_exe_path] [zh|en|ja|ko|de|fr]
@@ -0,0 +1,244 @@
+# 软件更新程序的中文界面文本
+# 格式为 Fluent 的子集：键 = 文本，{ $名称 } 为参数，{ "文本" } 为原样输出的文本，
+# 多行文本从下一行开始并缩进，缺少的键使用英文文本
+
+title = 软件更新
+status_preparing = 正在准备更新...
+status_waiting_for_app = 正在等待应用程序关闭...
+status_complete = 软件更新已完成！
+status_failed = 软件更新失败！
+button_ok = 确定
+button_update_now = 立即更新
+hint_cancel_delay = 按 Esc 取消更新
+button_overwrite = 覆盖
+button_skip = 跳过
+button_overwrite_all = 全部覆盖
+button_skip_all = 全部跳过
+confirm_title = 确认应用更新
+button_apply = 应用
+button_cancel = 取消
+button_browse = 浏览...
+button_start = 开始更新
+form_package = 更新包:
+form_target = 目标目录:
+form_language = 界面语言:
+label_details = 详细信息
+self_test_title = 自检结果
+check_package = 更新包
+check_target_writable = 目标目录可写
+check_disk_space = 磁盘空间
+check_fonts = 界面字体
+check_pass = 通过
+check_warning = 警告
+check_fail = 失败
+inspect_title = 更新包内容
+check_font_fallback = 未找到中文系统字体，将使用内置的后备字体
+status_dry_run_complete = 演练完成，未修改任何文件
+status_extracting = 正在解压更新包...
+status_copying = 正在复制文件...
+status_rolling_back = 更新失败，正在恢复原有文件...
+status_checkpoint_saved = 更新失败，已保存进度，重新运行时将从中断处继续
+status_staging = 正在临时目录中准备新版本...
+status_swapping = 正在替换目标目录...
+phase_extract = 当前阶段：解压
+phase_verify = 当前阶段：校验
+phase_copy = 当前阶段：复制
+phase_finalize = 当前阶段：收尾
+status_swap_fallback = 无法替换目标目录，改为直接更新文件...
+status_verifying_signature = 正在验证更新包签名...
+status_verifying_manifest = 正在按校验清单验证文件...
+status_applying_patches = 正在应用增量补丁...
+error_signature_missing = 更新包必须签名，请使用 --sig 指定签名文件
+error_signature_mismatch = 更新包签名验证失败，更新包可能已被篡改
+error_no_pubkey = 指定了签名文件但没有可用的公钥，请使用 --pubkey 指定公钥文件
+error_directory_unsigned = 更新包是目录，无法验证签名，需要签名验证时请使用压缩包
+error_elevation_declined = 未获得管理员权限，更新已取消
+error_update_declined = 已取消更新，目标目录未被修改
+error_no_package = 未提供更新包路径
+error_no_target = 必须提供目标路径
+error_password_required = 更新包已加密，请使用 --password 指定解压密码
+error_password_invalid = 更新包的解压密码错误
+bytes_small = { $bytes } 字节
+decimal_separator = .
+bytes_kb = { $value } KB
+bytes_mb = { $value } MB
+bytes_gb = { $value } GB
+bytes_tb = { $value } TB
+bytes_pb = { $value } PB
+bytes_eb = { $value } EB
+duration_hours = { $hours }小时{ $minutes }分
+duration_minutes = { $minutes }分{ $seconds }秒
+duration_seconds = { $seconds }秒
+inspect_summary = 共 { $entries } 个条目，解压后 { $bytes }
+inspect_inner_path = 压缩包内路径 { $pattern } 对应: { $resolved }
+check_space_needed = 需要 { $needed }
+status_processing = 正在处理: { $file_name }
+label_package = 更新包: { $name }
+label_target = 目标路径: { $path }
+status_file_count = { $current } / { $total } 个文件
+summary_counts = 已复制 { $copied } 个文件，跳过 { $skipped } 个，失败 { $failed } 个
+summary_bytes = 共写入 { $bytes }，用时 { $seconds }
+status_starting_in = { $seconds }后开始更新...
+hint_update_size = 本次更新将写入约 { $bytes }
+plan_summary = 计划执行 { $count } 项操作:
+confirm_summary = 将写入 { $files } 个文件（{ $size }），删除 { $deleted } 个文件
+plan_create = 新建: { $path }
+plan_overwrite = 覆盖: { $path }
+plan_rename_exe = 重命名: { $from } -> { $to }
+error_inner_path_not_found = 更新包中未找到指定目录: { $inner_path }
+error_package_empty = 更新包似乎是空的，其中没有任何文件
+error_package_empty_in = 更新包似乎是空的，压缩包内的 { $inner_path } 中没有任何文件
+error_volume_unknown = 无法确定目标路径所在磁盘: { $path }
+error_expected_file_missing = 目标目录 { $target } 中没有找到 { $name }，请确认目标路径是否正确
+error_target_not_writable = 无法写入目标目录 { $path }: { $err }
+error_insufficient_space = 目标磁盘空间不足: 需要 { $needed }, 可用 { $available }
+error_temp_space_exhausted = 临时目录所在磁盘空间不足，解压中止，已删除临时文件: { $path }；请释放空间，或用 --temp-dir 解压到其他磁盘
+error_package_not_found = 更新包不存在: { $path }
+error_invalid_package_metadata = 更新包中的 update.json 无效: { $reason }
+error_stdin_args = 无法从标准输入读取参数: { $reason }
+error_undefined_variable = 更新包中的目标路径 { $target } 引用了未定义的环境变量 { $name }
+error_updater_too_old = 更新包要求更新程序的版本至少为 { $required }，当前版本为 { $current }
+error_invalid_version = 无效的版本号: { $value }（应为语义化版本，如 1.2.3）
+error_not_newer = 更新包的版本 { $package } 不高于已安装的版本 { $current }，如需降级或重新安装请使用 --allow-downgrade
+error_current_too_old = 已安装的版本 { $current } 过旧，该更新包只能从 { $min } 或更高版本更新
+error_invalid_package = 更新包不是有效的ZIP文件: { $err }
+error_invalid_tar_package = 更新包不是有效的tar.gz文件: { $err }
+error_package_not_directory = 指定了 --format dir，但更新包不是目录: { $path }
+error_invalid_auto_close = 无效的自动关闭秒数: { $value }
+error_invalid_delay = 无效的延时参数: { $value }
+error_unexpected_argument = 无法识别的参数: { $arg }
+error_missing_value = 选项缺少参数值: { $flag }
+error_invalid_language = 无效的语言选项: { $value }
+error_unknown_option = 未知的选项: { $flag }
+status_transfer = 速度: { $bytes_per_second }/秒，预计剩余 { $eta_seconds }
+error_invalid_jobs = 无效的并发数: { $value }
+error_invalid_theme = 无效的主题: { $value }（可选值: light、dark、system）
+error_invalid_config = 无法读取配置文件 { $path }: { $reason }
+error_invalid_scale = 无效的界面缩放比例（应为0.5到4之间的数字或auto）: { $value }
+error_invalid_size = 无效的窗口大小: { $value }
+error_invalid_glob = 无效的通配符模式 { $pattern }: { $reason }
+status_copying_excluded = 已排除 { $excluded } 个文件
+status_copying_unchanged = 跳过 { $unchanged } 个未变化的文件
+status_copying_details = 正在复制文件...（{ $details }）
+list_separator = ，
+error_verify_failed = 文件校验失败，复制后的内容与更新包不一致: { $file_name }
+error_invalid_retries = 无效的重试次数: { $value }
+error_flatten_collision = 平铺复制时多个文件对应同一个文件名 { $name }: { $first } 和 { $second }
+error_incompatible_options = { $first } 不能与 { $second } 同时使用
+error_invalid_target_subdir = 目标子目录必须是相对路径且不能包含..: { $value }
+error_invalid_suffix = 无效的暂存后缀: { $value }
+error_invalid_timeout = 无效的超时时间: { $value }
+status_timed_out = 超过 { $seconds } 秒没有进度，正在中止更新...
+error_timed_out = 更新超时：超过 { $seconds } 秒没有进度，已中止更新
+status_retrying = 文件被占用，正在重试 ({ $attempt }/{ $max }): { $file_name }
+plan_delete = 删除: { $path }
+status_pruning = 正在删除新版本中已不存在的文件: { $file_name }
+status_mapping = 正在更新 ({ $index }/{ $total }): { $inner } -> { $target }
+error_invalid_map = 无效的目录映射: { $value }（格式为 <压缩包内路径>:<目标路径>）
+status_partial_complete = 更新未全部完成，{ $count } 个文件失败:
+status_waiting_for_locked = 等待 { $count } 个被占用的文件释放: { $first }...
+error_files_locked = 有 { $count } 个文件正被其他程序使用，请关闭相关程序后重试: { $first }
+error_invalid_lock_policy = 无效的文件占用处理方式（应为wait、abort或stage）: { $value }
+error_invalid_format = 无效的更新包格式（应为zip、tar.gz或dir）: { $value }
+status_running_hook = 正在执行命令: { $command }
+error_hook_failed = 命令执行失败（退出码 { $code }）: { $command }
+error_invalid_pubkey = 无效的ed25519公钥: { $path }
+error_invalid_signature_file = 无效的ed25519签名文件: { $path }
+warning_unsafe_entries = 警告: 更新包中有 { $count } 个条目的路径不安全（绝对路径、包含..或经由符号链接指向更新包之外），已跳过
+warning_format_mismatch = 警告: 指定的更新包格式为 { $format }，但按文件内容检测为 { $detected }，仍按 { $format } 处理
+prompt_conflict =
+    目标文件为只读或受保护，是否覆盖？
+    { $path }
+warning_protected_skipped = 警告: 已按选择跳过 { $count } 个受保护的文件，这些文件保持原样
+warning_newer_kept = 警告: 目标目录中有 { $count } 个文件比更新包中的新，已保留未覆盖
+warning_package_not_removed = 更新成功，但无法删除更新包 { $path }: { $reason }
+warning_restart_required = 有 { $count } 个文件正被占用，需要重新启动计算机才能完成更新
+error_audit_not_opened = 无法创建审计文件 { $path }: { $reason }
+warning_receipt_not_written = 更新成功，但无法在 { $target } 中写入更新回执: { $reason }
+error_inner_path_ambiguous = 更新包中有多个目录与 { $pattern } 匹配: { $matches }
+error_unsafe_inner_path = 更新包内路径不能是绝对路径或包含..: { $path }
+error_patch_base_missing = 目标目录中没有补丁对应的文件，无法应用增量更新: { $file }
+error_patch_failed = 应用补丁失败 { $file }: { $err }
+error_manifest_missing = 更新包中缺少校验清单列出的文件: { $file }
+error_manifest_mismatch = 文件的SHA-256与校验清单不一致，更新包可能已损坏: { $file }
+status_killing_process = 正在结束 { $count } 个正在运行的 { $name } 进程...
+error_kill_process_failed = 无法结束正在运行的进程: { $name }
+usage =
+    用法: software_updater --package <路径> --target <路径> [选项]
+
+      --package <路径>      更新包路径，可以是ZIP、tar.gz文件或已解压的目录（必填）
+      --inner-path <路径>   压缩包内要复制的目录或单个文件，默认为根目录，可使用通配符（如 myapp-*）匹配唯一的目录
+      --target <路径>       目标路径（未指定 --map、--self-dir 或更新包中的 update.json、target.txt 时必填）
+      --self-dir            未指定 --target 时更新程序所在的目录
+      --map <内部路径>:<目标路径>
+                            将压缩包内的目录复制到指定目标目录，可重复指定，代替 --inner-path 和 --target
+      --expect-file <名称>  更新前目标目录中必须已存在的文件（如主程序），缺少时中止，可重复指定
+      --current-version <版本>
+                            已安装的版本，更新包版本不高于该版本时中止
+      --allow-downgrade     允许安装不高于已安装版本的更新包
+      --delay <秒>          启动前延时秒数，默认为0
+      --start-exe <路径>    更新成功后要启动的exe路径
+      --auto-close <秒>     更新成功后经过指定秒数自动关闭窗口（失败时不关闭）
+      --kill-process <名称> 复制前结束指定名称的进程（先正常退出，超时后强制结束）
+      --pre-hook <命令>     解压前在目标目录中执行的命令，退出码非0时中止更新
+      --post-hook <命令>    复制完成后在目标目录中执行的命令，失败时保留更新包
+      --elevate             （仅Windows）没有管理员权限时请求提升权限并重新启动
+      --pubkey <路径>       用于验证更新包签名的ed25519公钥文件
+      --sig <路径>          更新包的ed25519分离签名文件，解压前验证
+      --password <密码>     加密更新包的解压密码
+      --lang <zh|en|ja|ko|de|fr>
+                            界面语言，默认根据系统区域设置
+      --dry-run             只列出将要执行的操作，不修改文件
+      --confirm             解压后列出将要执行的操作，确认后才修改目标目录
+      --verify              复制后校验每个文件的SHA-256，不一致时回滚
+      --prune               删除目标目录中新版本已不存在的文件（--exclude匹配的文件除外）
+      --continue-on-error   单个文件失败时继续复制其余文件，最后列出失败的文件
+      --skip-unchanged      跳过与目标目录中内容相同的文件
+      --skip-newer          目标目录中的文件比更新包中的新时不覆盖
+      --delta               把更新包中的 .patch 文件作为现有文件的二进制补丁应用
+      --flatten             不保留子目录，把所有文件直接复制到目标目录中，文件名重复时中止
+      --keep-package        更新成功后保留源更新包，默认删除
+      --fsync               每个文件写入后同步到磁盘，断电后也不会损坏（较慢）
+      --receipt             更新成功后在目标目录中写入记录已安装文件的 update-receipt.json
+      --audit-csv <路径>    把每个文件操作（时间、操作、源路径、目标路径、字节数、结果）逐行写入CSV文件
+      --temp-dir <路径>     解压更新包和备份文件使用的临时目录，默认为系统临时目录
+      --format <格式>       更新包格式（zip、tar.gz或dir），默认按文件内容和扩展名检测
+      --resumable           记录已复制的文件，失败时不回滚，重新运行时从中断处继续
+      --target-subdir <名称>
+                            复制到目标目录下的子目录（如 versions/1.2.0），不存在时创建
+      --self-suffix <后缀>  Windows上覆盖正在运行的更新程序时改为写入的文件后缀，默认为 .new
+      --on-locked <wait|abort|stage>
+                            要覆盖的文件被占用时等待释放、中止更新或暂存后在重启时替换，默认为 stage
+      --no-self-stage       不暂存正在运行的更新程序，直接覆盖（更新程序不在目标目录中时使用）
+      --self-test           不执行更新，检查更新包、目标目录权限、磁盘空间和字体后输出结果
+      --inspect             不执行更新，列出更新包中的条目并标出 --inner-path 对应的目录
+      --atomic              在目标目录旁的临时目录中组装新版本，成功后整体替换目标目录
+      --no-preserve-mtime   不保留文件的修改时间，复制后的文件使用当前时间
+      --preserve-hardlinks  （仅Unix）更新包中互为硬链接的文件只复制一次，其余路径在目标目录中创建硬链接
+      --dedup               内容相同的文件只从更新包复制一次，其余目标位置从第一个副本复制
+      --json                不显示界面，以每行一个JSON对象的形式向标准输出报告进度
+      --silent              不显示界面，结果写入日志并以系统通知提示
+      --config <路径>       配置文件路径，默认为程序目录下的updater.toml，命令行参数优先
+      --args-from-stdin     从标准输入读取其余参数，每行一个或以空字符分隔
+      --log <路径>          日志文件路径，默认为程序目录下的updater.log
+      --changelog <路径>    延时期间显示的更新说明，默认读取更新包中的CHANGELOG.md
+      --show-size           延时期间显示本次更新将写入的总大小
+      --jobs <数量>         并发复制的线程数，默认为CPU核心数
+      --retries <次数>      文件被占用时的重试次数，默认为3
+      --timeout <秒数>      超过指定秒数没有进度时中止更新并回滚
+      --include <模式>      只复制匹配该通配符的文件，可重复指定
+      --exclude <模式>      不复制匹配该通配符的文件，可重复指定，优先于--include
+      --confirm-overwrite   覆盖只读文件或受保护的文件前询问
+      --protect <模式>      配合--confirm-overwrite，把匹配该通配符的文件（如配置文件）视为受保护，可重复指定
+      --width <像素>        窗口宽度，默认为450
+      --height <像素>       窗口高度，默认为250
+      --resizable           允许调整窗口大小
+      --no-topmost          不将窗口置顶显示
+      --no-center           不在屏幕中央显示窗口
+      --scale <倍数|auto>
+                            界面缩放比例（0.5到4），默认按系统DPI自动缩放
+      --theme <light|dark|system> 界面主题，默认为light，system跟随系统设置
+      -h, --help            显示此帮助信息
+
+    旧的位置参数用法仍然可用，但已弃用:
+      software_updater <package_path> [zip_inner_path] <target_path> [delay_seconds] [start_exe_path] [zh|en|ja|ko|de|fr]
@@ -0,0 +1,142 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use fs2::FileExt;
+
+use crate::language::LangDict;
+use crate::update::{EventSender, emit};
+use crate::watchdog::Watchdog;
+use crate::{LockPolicy, UpdateEvent, long_path};
+
+// 等待被占用的文件释放时重新检查的间隔
+const WAIT_INTERVAL: Duration = Duration::from_secs(1);
+
+// 打开或加锁失败是否因为文件正被其他进程使用：Windows上是共享冲突或锁冲突，其他系统上是已被加锁
+fn is_lock_error(e: &io::Error) -> bool {
+    #[cfg(windows)]
+    if crate::reboot::is_locked(e) {
+        return true;
+    }
+    e.kind() == io::ErrorKind::WouldBlock || e.raw_os_error() == fs2::lock_contended_error().raw_os_error()
+}
+
+// 文件是否正被其他进程占用：以写方式打开并尝试加独占锁，检查后立即释放，不修改文件内容。
+// 不存在或没有写权限的文件不算被占用，留给复制时报告
+pub(crate) fn is_locked(path: &Path) -> bool {
+    let file = match fs::OpenOptions::new().write(true).open(path) {
+        Ok(file) => file,
+        Err(e) => return is_lock_error(&e),
+    };
+    match file.try_lock_exclusive() {
+        Ok(()) => {
+            let _ = FileExt::unlock(&file);
+            false
+        },
+        Err(e) => is_lock_error(&e),
+    }
+}
+
+// 按策略处理即将被覆盖的文件中正被占用的文件：abort 时在修改任何文件之前中止，
+// wait 时每秒重新检查直到全部释放（设置了 --timeout 时超时中止），stage 时不检查，复制时再暂存
+pub(crate) fn check(policy: LockPolicy, files: Vec<PathBuf>, dict: &LangDict, sender: &EventSender, watchdog: &Watchdog) -> io::Result<()> {
+    if policy == LockPolicy::Stage {
+        return Ok(());
+    }
+    let mut locked: Vec<PathBuf> = files.into_iter().filter(|path| is_locked(path)).collect();
+    if locked.is_empty() {
+        return Ok(());
+    }
+    for path in &locked {
+        log::warn!("文件正被其他进程占用: {:?}", path);
+    }
+    if policy == LockPolicy::Abort {
+        return Err(io::Error::new(io::ErrorKind::ResourceBusy, dict.error_files_locked(locked.len(), &long_path::display(&locked[0]))));
+    }
+
+    // 调用方可以在等待期间取消更新，此时尚未修改任何文件
+    let (cancel, cancelled) = mpsc::channel();
+    while !locked.is_empty() {
+        // 等待期间没有进度，超时后由监视线程标记，此时返回超时错误
+        if watchdog.expired() {
+            return watchdog.progress(dict);
+        }
+        emit(sender, UpdateEvent::Waiting { message: dict.status_waiting_for_locked(locked.len(), &long_path::display(&locked[0])), cancel: cancel.clone() });
+        if cancelled.recv_timeout(WAIT_INTERVAL).is_ok() {
+            log::info!("用户在等待被占用的文件释放时取消了更新");
+            return Err(io::Error::new(io::ErrorKind::Interrupted, dict.error_update_declined));
+        }
+        locked.retain(|path| is_locked(path));
+    }
+    log::info!("被占用的文件均已释放");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::{Language, get_dict};
+
+    // 在另一个文件句柄上持有独占锁，模拟其他进程正在使用该文件
+    fn hold_lock(path: &Path) -> fs::File {
+        let file = fs::OpenOptions::new().write(true).open(path).unwrap();
+        file.lock_exclusive().unwrap();
+        file
+    }
+
+    #[test]
+    fn detects_locked_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.dll");
+        fs::write(&path, "dll").unwrap();
+        assert!(!is_locked(&path));
+        assert!(!is_locked(&dir.path().join("missing.dll")));
+
+        let lock = hold_lock(&path);
+        assert!(is_locked(&path));
+        drop(lock);
+        assert!(!is_locked(&path));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "dll");
+    }
+
+    #[test]
+    fn abort_policy_fails_on_locked_file() {
+        let dict = get_dict(Language::English);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.dll");
+        fs::write(&path, "dll").unwrap();
+        let (sender, _receiver) = mpsc::channel();
+        let sender = EventSender::new(sender);
+        let watchdog = Watchdog::new(None);
+
+        check(LockPolicy::Abort, vec![path.clone()], dict, &sender, &watchdog).unwrap();
+        let _lock = hold_lock(&path);
+        let error = check(LockPolicy::Abort, vec![path.clone()], dict, &sender, &watchdog).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::ResourceBusy);
+        assert_eq!(error.to_string(), dict.error_files_locked(1, &path.display().to_string()));
+        // stage 策略不检查
+        check(LockPolicy::Stage, vec![path], dict, &sender, &watchdog).unwrap();
+    }
+
+    #[test]
+    fn wait_policy_can_be_cancelled() {
+        let dict = get_dict(Language::English);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.dll");
+        fs::write(&path, "dll").unwrap();
+        let _lock = hold_lock(&path);
+        let (sender, receiver) = mpsc::channel();
+        let sender = EventSender::new(sender);
+        let watchdog = Watchdog::new(None);
+
+        let canceller = std::thread::spawn(move || match receiver.recv().unwrap() {
+            UpdateEvent::Waiting { cancel, .. } => cancel.send(()).unwrap(),
+            event => panic!("unexpected event: {:?}", event),
+        });
+        let error = check(LockPolicy::Wait, vec![path], dict, &sender, &watchdog).unwrap_err();
+        canceller.join().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::Interrupted);
+    }
+}
@@ -0,0 +1,141 @@
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+
+// 单个日志文件的最大字节数，超过后轮转
+const MAX_LOG_SIZE: u64 = 1024 * 1024;
+// 保留的历史日志文件数量（updater.log.1 ~ updater.log.N）
+const MAX_LOG_FILES: usize = 3;
+// 默认日志文件名
+const DEFAULT_LOG_NAME: &str = "updater.log";
+// 在内存中保留的最近日志行数，用于界面中的详细信息
+const MAX_RECENT_LINES: usize = 500;
+
+// 当前使用的日志文件路径
+static LOG_PATH: OnceLock<PathBuf> = OnceLock::new();
+// 最近的日志行
+static RECENT_LINES: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+// 按大小轮转的日志文件写入器
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { path, file, size })
+    }
+
+    // 将 updater.log 依次重命名为 updater.log.1、updater.log.2 ...，并重新创建日志文件
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        for i in (1..MAX_LOG_FILES).rev() {
+            let from = rotated_path(&self.path, i);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, i + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > MAX_LOG_SIZE {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+// 同时写入标准错误、日志文件和内存中的最近日志
+struct TeeWriter {
+    file: Option<RotatingFile>,
+}
+
+impl Write for TeeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let _ = io::stderr().write_all(buf);
+        if let Some(file) = &mut self.file {
+            // 日志文件写入失败时不影响更新流程
+            let _ = file.write_all(buf).and_then(|_| file.flush());
+        }
+        let mut recent = RECENT_LINES.lock().unwrap();
+        for line in String::from_utf8_lossy(buf).lines().filter(|line| !line.is_empty()) {
+            if recent.len() == MAX_RECENT_LINES {
+                recent.pop_front();
+            }
+            recent.push_back(line.to_string());
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stderr().flush()
+    }
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+// 默认日志路径：可执行文件所在目录下的 updater.log
+fn default_log_path() -> Option<PathBuf> {
+    env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(DEFAULT_LOG_NAME)))
+}
+
+// 初始化日志系统，日志同时输出到标准错误和轮转日志文件，默认级别为info
+pub fn init(log_path: Option<&str>) {
+    let path = log_path.map(PathBuf::from).or_else(default_log_path);
+    let file = path.as_ref().and_then(|path| match RotatingFile::open(path.clone()) {
+        Ok(file) => Some(file),
+        Err(e) => {
+            eprintln!("无法打开日志文件 {:?}: {}", path, e);
+            None
+        }
+    });
+    let path = path.filter(|_| file.is_some());
+
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+        .target(env_logger::Target::Pipe(Box::new(TeeWriter { file })))
+        .write_style(env_logger::WriteStyle::Never)
+        .init();
+
+    if let Some(path) = path {
+        log::info!("日志文件: {:?}", path);
+        let _ = LOG_PATH.set(path);
+    }
+}
+
+// 获取当前使用的日志文件路径，未写入日志文件时返回None
+pub fn log_file() -> Option<&'static Path> {
+    LOG_PATH.get().map(PathBuf::as_path)
+}
+
+// 获取最近的日志行（最多 MAX_RECENT_LINES 行），按时间顺序排列
+pub fn recent_lines() -> Vec<String> {
+    RECENT_LINES.lock().unwrap().iter().cloned().collect()
+}
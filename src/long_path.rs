@@ -0,0 +1,74 @@
+use std::path::{Path, PathBuf};
+
+// 扩展长度路径的前缀，网络路径使用 \\?\UNC\ 代替开头的 \\
+const VERBATIM_PREFIX: &str = r"\\?\";
+const VERBATIM_UNC_PREFIX: &str = r"\\?\UNC\";
+
+// Windows上超过MAX_PATH（260个字符）的路径需要转换为带 \\?\ 前缀的绝对路径才能访问。
+// 这种路径不再解析 . 和 ..，所以先转换为绝对路径；已有前缀或无法转换的路径保持不变
+#[cfg(windows)]
+pub(crate) fn extend(path: &Path) -> PathBuf {
+    let Ok(absolute) = std::path::absolute(path) else {
+        return path.to_path_buf();
+    };
+    match absolute.to_str() {
+        Some(text) => PathBuf::from(with_prefix(text)),
+        None => absolute,
+    }
+}
+
+// 其他系统没有路径长度限制，保持不变
+#[cfg(not(windows))]
+pub(crate) fn extend(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+// 为绝对路径添加扩展长度前缀：C:\dir 变为 \\?\C:\dir，\\server\share 变为 \\?\UNC\server\share
+// 只处理字符串，测试在所有系统上运行
+#[cfg(any(windows, test))]
+fn with_prefix(path: &str) -> String {
+    if path.starts_with(VERBATIM_PREFIX) || path.starts_with(r"\\.\") {
+        path.to_string()
+    } else if let Some(rest) = path.strip_prefix(r"\\") {
+        format!("{}{}", VERBATIM_UNC_PREFIX, rest)
+    } else {
+        format!("{}{}", VERBATIM_PREFIX, path)
+    }
+}
+
+// 去掉扩展长度前缀后用于界面和事件中显示的路径
+pub(crate) fn display(path: &Path) -> String {
+    let text = path.display().to_string();
+    if let Some(rest) = text.strip_prefix(VERBATIM_UNC_PREFIX) {
+        format!(r"\\{}", rest)
+    } else if let Some(rest) = text.strip_prefix(VERBATIM_PREFIX) {
+        rest.to_string()
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_verbatim_prefix() {
+        assert_eq!(with_prefix(r"C:\Program Files\App"), r"\\?\C:\Program Files\App");
+        assert_eq!(with_prefix(r"\\server\share\app"), r"\\?\UNC\server\share\app");
+        // 已有前缀和设备路径保持不变
+        assert_eq!(with_prefix(r"\\?\C:\app"), r"\\?\C:\app");
+        assert_eq!(with_prefix(r"\\?\UNC\server\share"), r"\\?\UNC\server\share");
+        assert_eq!(with_prefix(r"\\.\pipe\name"), r"\\.\pipe\name");
+    }
+
+    #[test]
+    fn display_strips_prefix() {
+        assert_eq!(display(Path::new(r"\\?\C:\app")), r"C:\app");
+        assert_eq!(display(Path::new(r"\\?\UNC\server\share\app")), r"\\server\share\app");
+        assert_eq!(display(Path::new("relative/dir")), "relative/dir");
+        for path in [r"C:\long\path", r"\\server\share\app"] {
+            assert_eq!(display(Path::new(&with_prefix(path))), path);
+        }
+    }
+}
@@ -1,35 +1,39 @@
-#![windows_subsystem = "windows"]
+#![cfg_attr(windows, windows_subsystem = "windows")]
 
+use std::collections::VecDeque;
 use std::env;
-use std::fs;
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 use std::thread;
-use tempfile::tempdir;
-use walkdir::WalkDir;
-use zip::ZipArchive;
 
 use eframe::{App, Frame};
-use egui::{CentralPanel, Context, ProgressBar, Visuals};
+use egui::{CentralPanel, Context, ProgressBar};
 
-mod language;
-use language::{Language, LangDict, get_dict, parse_language};
+use software_updater::language::{LangDict, get_dict};
+use software_updater::{ConflictChoice, Phase, PlannedAction, UpdateEvent, UpdateSummary, check_package, logging, run_update, self_update, update_size};
 
-// 更新消息类型
-enum UpdateMsg {
-    Status(String),
-    TotalFiles(usize),
-    Progress(usize, usize, String),
-    Complete,
-    Error(String),
-}
+mod changelog;
+mod config;
+#[cfg(windows)]
+mod elevate;
+mod exit_code;
+mod fonts;
+mod headless;
+mod icon;
+mod inspect;
+mod package_meta;
+mod self_test;
+mod start_form;
+mod theme;
+use config::{Config, parse_args, with_stdin_args};
+use exit_code::ExitCode;
+use start_form::StartForm;
+use theme::Palette;
 
 // 应用状态结构体
 struct UpdateApp {
-    package_path: String,
-    target_path: Option<String>,
-    zip_inner_path: String,
+    config: Config,
     total_files: usize,
     current_file: usize,
     status: String,
@@ -37,20 +41,69 @@ struct UpdateApp {
     current_file_name: String,
     is_complete: bool,
     error: Option<String>,
-    receiver: Option<mpsc::Receiver<UpdateMsg>>,
+    // 关闭错误界面时使用的退出码
+    error_code: ExitCode,
+    // 更新过程中的非致命警告，按收到的顺序显示
+    warnings: Vec<String>,
+    receiver: Option<mpsc::Receiver<UpdateEvent>>,
     dict: &'static LangDict,
-    delay_seconds: u64,
+    palette: Palette,
     start_time: Option<std::time::Instant>,
-    start_exe_path: Option<String>,
+    // 收到完成消息的时间，用于自动关闭倒计时
+    complete_time: Option<std::time::Instant>,
+    plan: Option<Vec<PlannedAction>>,
+    // 继续执行模式下失败的文件及错误信息
+    failures: Option<Vec<(PathBuf, String)>>,
+    copied_bytes: u64,
+    total_bytes: u64,
+    // 按阶段权重合并后的总进度（0到1）
+    overall_progress: f32,
+    // 最近一次进度事件所属的阶段，进度条下方显示
+    phase: Option<Phase>,
+    // 进度条当前显示的进度，每帧向实际进度靠近，使进度条平滑移动
+    displayed_progress: f32,
+    byte_samples: VecDeque<(std::time::Instant, u64)>,
+    // 延时期间显示的更新说明
+    changelog: Option<String>,
+    // 延时期间显示的本次更新大小（--show-size）
+    size_hint: Option<String>,
+    // 用户点击"立即更新"后跳过剩余延时
+    skip_delay: bool,
+    // 等待用户决定如何处理的受保护文件，以及回复更新线程的发送端
+    conflict: Option<(String, mpsc::Sender<ConflictChoice>)>,
+    // 确认模式下等待用户确认的操作、总字节数和回复更新线程的发送端；用户取消后更新线程清理完临时文件即退出
+    confirm: Option<(Vec<PlannedAction>, u64, mpsc::Sender<bool>)>,
+    declined: bool,
+    // 等待被占用的文件释放时的等待详情和取消等待的发送端，收到其他事件后清除
+    waiting: Option<(String, mpsc::Sender<()>)>,
+    // 更新线程结束时返回的统计，完成界面中显示
+    summary_receiver: Option<mpsc::Receiver<UpdateSummary>>,
+    summary: Option<UpdateSummary>,
+    // 不带参数启动时显示的启动表单，填写完成后替换为按表单内容开始的更新
+    form: Option<StartForm>,
 }
 
+// 受保护文件确认按钮的最小宽度
+const CONFLICT_BUTTON_WIDTH: f32 = 80.0;
+
+// 解压、校验和收尾阶段在总进度中所占的比例，其余为复制阶段
+const EXTRACT_WEIGHT: f32 = 0.4;
+const VERIFY_WEIGHT: f32 = 0.05;
+const FINALIZE_WEIGHT: f32 = 0.05;
+
+// 进度条向实际进度靠近的速度：每秒缩小剩余差距的比例的指数系数，越大越快
+const PROGRESS_EASE_RATE: f32 = 8.0;
+
+// 估算界面顶部信息每个字符的平均宽度（像素），用于决定省略的长度
+const HEADER_CHAR_WIDTH: f32 = 7.0;
+
+// 计算传输速度的滑动窗口时长
+const SPEED_WINDOW: std::time::Duration = std::time::Duration::from_secs(3);
+
 impl UpdateApp {
-    fn new(package_path: String, lang: Language, target_path: Option<String>, zip_inner_path: String, delay_seconds: u64, start_exe_path: Option<String>) -> Self {
-        let dict = get_dict(lang);
+    fn new(config: Config, palette: Palette) -> Self {
+        let dict = get_dict(config.update.lang);
         Self {
-            package_path,
-            target_path,
-            zip_inner_path,
             total_files: 0,
             current_file: 0,
             status: dict.status_preparing.to_string(),
@@ -58,19 +111,155 @@ impl UpdateApp {
             current_file_name: "".to_string(),
             is_complete: false,
             error: None,
+            error_code: ExitCode::Failed,
+            warnings: Vec::new(),
             receiver: None,
             dict,
-            delay_seconds,
+            palette,
             start_time: None,
-            start_exe_path,
+            complete_time: None,
+            plan: None,
+            failures: None,
+            copied_bytes: 0,
+            total_bytes: 0,
+            overall_progress: 0.0,
+            phase: None,
+            displayed_progress: 0.0,
+            byte_samples: VecDeque::new(),
+            changelog: changelog::load(&config),
+            size_hint: size_hint(&config),
+            skip_delay: false,
+            conflict: None,
+            confirm: None,
+            declined: false,
+            waiting: None,
+            summary_receiver: None,
+            summary: None,
+            form: None,
+            config,
+        }
+    }
+    
+    // 记录已复制字节数的采样点，只保留滑动窗口内的数据
+    fn record_bytes(samples: &mut VecDeque<(std::time::Instant, u64)>, copied: u64) {
+        let now = std::time::Instant::now();
+        samples.push_back((now, copied));
+        while samples.len() > 2 && samples.front().is_some_and(|(t, _)| now.duration_since(*t) > SPEED_WINDOW) {
+            samples.pop_front();
         }
     }
+    
+    // 根据滑动窗口计算平均传输速度（字节/秒）和预计剩余秒数
+    fn transfer_rate(&self) -> Option<(f64, u64)> {
+        let (first_time, first_bytes) = self.byte_samples.front()?;
+        let (last_time, last_bytes) = self.byte_samples.back()?;
+        let elapsed = last_time.duration_since(*first_time).as_secs_f64();
+        if elapsed <= 0.0 || last_bytes <= first_bytes {
+            return None;
+        }
+        let speed = (last_bytes - first_bytes) as f64 / elapsed;
+        let remaining = self.total_bytes.saturating_sub(self.copied_bytes) as f64 / speed;
+        Some((speed, remaining.ceil() as u64))
+    }
+    
+    // 计算进度：解压阶段占前一部分，复制阶段（按已复制的字节数）占其余部分，合并为一个连续的进度条
+    fn progress(&self) -> f32 {
+        if self.is_complete {
+            1.0
+        } else {
+            self.overall_progress
+        }
+    }
+    
+    // 按本帧经过的时间让显示的进度向实际进度靠近，与帧率无关；差距很小时直接对齐
+    fn ease_progress(&mut self, dt: f32) {
+        let target = self.progress();
+        let t = 1.0 - (-PROGRESS_EASE_RATE * dt).exp();
+        self.displayed_progress = egui::lerp(self.displayed_progress..=target, t);
+        if (target - self.displayed_progress).abs() < 0.001 {
+            self.displayed_progress = target;
+        }
+    }
+    
+    // 计划执行的操作在演练和确认界面中的说明
+    fn plan_text(&self, action: &PlannedAction) -> String {
+        match action {
+            PlannedAction::Create(path) => self.dict.plan_create(path),
+            PlannedAction::Overwrite(path) => self.dict.plan_overwrite(path),
+            PlannedAction::RenameExe(from, to) => self.dict.plan_rename_exe(from, to),
+            PlannedAction::Delete(path) => self.dict.plan_delete(path),
+        }
+    }
+    
+    // 进度条下方显示的当前阶段
+    fn phase_name(&self, phase: Phase) -> &'static str {
+        match phase {
+            Phase::Extract => self.dict.phase_extract,
+            Phase::Verify => self.dict.phase_verify,
+            Phase::Copy => self.dict.phase_copy,
+            Phase::Finalize => self.dict.phase_finalize,
+        }
+    }
+    
+    // 把阶段内的完成比例换算为总进度
+    fn phase_progress(phase: Phase, fraction: f32) -> f32 {
+        let copy_weight = 1.0 - EXTRACT_WEIGHT - VERIFY_WEIGHT - FINALIZE_WEIGHT;
+        let overall = match phase {
+            Phase::Extract => fraction * EXTRACT_WEIGHT,
+            Phase::Verify => EXTRACT_WEIGHT + fraction * VERIFY_WEIGHT,
+            Phase::Copy => EXTRACT_WEIGHT + VERIFY_WEIGHT + fraction * copy_weight,
+            Phase::Finalize => 1.0 - FINALIZE_WEIGHT + fraction * FINALIZE_WEIGHT,
+        };
+        overall.clamp(0.0, 1.0)
+    }
+    
+    // 完成后显示复制、跳过和失败的文件数，以及写入的总字节数和用时
+    fn show_summary(&self, ui: &mut egui::Ui) {
+        if let Some(summary) = &self.summary {
+            for text in [
+                self.dict.summary_counts(summary.files_copied, summary.files_skipped, summary.failed_files.len()),
+                self.dict.summary_bytes(summary.bytes_copied, summary.duration.as_secs()),
+            ] {
+                ui.label(egui::RichText::new(text).font(egui::FontId::proportional(12.0)).color(self.palette.muted));
+            }
+        }
+    }
+    
+    // 界面顶部显示的目标路径，多个映射时依次列出各目标目录
+    fn target_text(&self) -> String {
+        let update = &self.config.update;
+        if update.mappings.is_empty() {
+            update.target_path.clone().unwrap_or_default()
+        } else {
+            update.mappings.iter().map(|(_, target)| target.as_str()).collect::<Vec<_>>().join(", ")
+        }
+    }
+    
+    // 参数无效时直接显示错误和用法，不启动更新
+    fn show_usage_error(&mut self, err: String) {
+        self.status = self.dict.status_failed.to_string();
+        self.error = Some(format!("{}\n\n{}", err, self.dict.usage));
+        self.error_code = ExitCode::InvalidArgs;
+    }
 }
 
 impl App for UpdateApp {
     fn update(&mut self, ctx: &Context, _frame: &mut Frame) {
-        // 初始化更新线程
-        if self.receiver.is_none() {
+        // 启动表单中选择了语言时同时更新窗口标题，点击开始且参数有效后按表单的配置开始更新
+        if let Some(form) = &mut self.form {
+            let lang = form.lang();
+            let started = CentralPanel::default().show(ctx, |ui| form.show(ui, &self.palette)).inner;
+            if form.lang() != lang {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Title(get_dict(form.lang()).title.to_string()));
+            }
+            if let Some(config) = started {
+                *self = UpdateApp::new(config, self.palette);
+            }
+            return;
+        }
+        
+        // 初始化更新线程（已有错误时不再启动）
+        if self.receiver.is_none() && self.error.is_none() {
             // 如果还没有开始计时，记录开始时间
             if self.start_time.is_none() {
                 self.start_time = Some(std::time::Instant::now());
@@ -79,25 +268,36 @@ impl App for UpdateApp {
             // 检查是否已经过了指定的延时时间
             if let Some(start_time) = self.start_time {
                 let elapsed = start_time.elapsed();
-                let delay_duration = std::time::Duration::from_secs(self.delay_seconds);
+                let delay_duration = std::time::Duration::from_secs(self.config.delay_seconds);
                 
-                if elapsed < delay_duration {
+                if elapsed < delay_duration && !self.skip_delay {
+                    // 延时期间按Esc取消更新
+                    if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+                        log::info!("用户在延时期间取消了更新");
+                        ExitCode::Cancelled.exit();
+                    }
+                    
                     // 如果延时未完成，显示延时状态
-                    let remaining_seconds = self.delay_seconds - elapsed.as_secs();
-                    let delay_msg = format!("{}", self.dict.status_starting_in(remaining_seconds));
+                    let remaining_seconds = self.config.delay_seconds - elapsed.as_secs();
+                    let delay_msg = self.dict.status_starting_in(remaining_seconds);
                     self.status = delay_msg.clone();
                     self.status_text = delay_msg;
                 } else {
                     // 延时完成，开始执行更新操作
                     let (sender, receiver) = mpsc::channel();
                     self.receiver = Some(receiver);
+                    let (summary_sender, summary_receiver) = mpsc::channel();
+                    self.summary_receiver = Some(summary_receiver);
                     
-                    let package_path = self.package_path.clone();
-                    let target_path = self.target_path.clone();
-                    let zip_inner_path = self.zip_inner_path.clone();
+                    let config = self.config.update.clone();
                     thread::spawn(move || {
-                        // 直接调用perform_update，它内部会处理所有错误并发送到GUI
-                        perform_update(&package_path, &target_path, &zip_inner_path, sender);
+                        // 更新核心库内部会处理所有错误，事件通过通道转发给GUI，界面根据事件显示结果；成功时另外返回统计
+                        let result = run_update(&config, |event| {
+                            let _ = sender.send(event);
+                        });
+                        if let Ok(summary) = result {
+                            let _ = summary_sender.send(summary);
+                        }
                     });
                 }
             }
@@ -106,197 +306,462 @@ impl App for UpdateApp {
         // 处理消息
         if let Some(receiver) = &self.receiver {
             while let Ok(msg) = receiver.try_recv() {
+                // 等待结束后更新线程会继续发送其他事件，此时不再显示等待状态
+                if !matches!(msg, UpdateEvent::Waiting { .. } | UpdateEvent::Warning(_)) {
+                    self.waiting = None;
+                }
                 match msg {
-                    UpdateMsg::Status(text) => {
+                    UpdateEvent::Status(text) => {
                         self.status_text = text.clone();
                         self.status = text;
                     },
-                    UpdateMsg::TotalFiles(total) => {
+                    UpdateEvent::TotalFiles(total) => {
                         self.total_files = total;
                     },
-                    UpdateMsg::Progress(current, total, file) => {
+                    UpdateEvent::Progress { phase, current, total, file } => {
+                        self.phase = Some(phase);
                         self.current_file = current;
                         self.total_files = total;
                         self.current_file_name = file;
                     },
-                    UpdateMsg::Complete => {
+                    UpdateEvent::Bytes(copied, total) => {
+                        self.copied_bytes = copied;
+                        self.total_bytes = total;
+                        Self::record_bytes(&mut self.byte_samples, copied);
+                    },
+                    UpdateEvent::PhaseProgress { phase, fraction } => {
+                        self.phase = Some(phase);
+                        // 进度只增不减（如原子替换失败后改为直接更新时重新复制）
+                        self.overall_progress = self.overall_progress.max(Self::phase_progress(phase, fraction));
+                    },
+                    UpdateEvent::Plan(actions) => {
+                        self.status = self.dict.status_dry_run_complete.to_string();
+                        self.current_file = self.total_files;
+                        self.current_file_name = "".to_string();
+                        self.overall_progress = 1.0;
+                        self.plan = Some(actions);
+                    },
+                    UpdateEvent::Complete => {
                         self.status = self.dict.status_complete.to_string();
                         self.current_file = self.total_files;
                         self.current_file_name = "".to_string();
                         self.is_complete = true;
+                        self.complete_time = Some(std::time::Instant::now());
+                    },
+                    UpdateEvent::PartialComplete(failures) => {
+                        self.status = self.dict.status_partial_complete(failures.len());
+                        self.current_file = self.total_files;
+                        self.current_file_name = "".to_string();
+                        self.overall_progress = 1.0;
+                        self.failures = Some(failures);
+                    },
+                    UpdateEvent::Warning(text) => {
+                        self.warnings.push(text);
+                    },
+                    UpdateEvent::Conflict(path, reply) => {
+                        self.conflict = Some((path, reply));
+                    },
+                    UpdateEvent::Confirm { actions, total_bytes, reply } => {
+                        self.confirm = Some((actions, total_bytes, reply));
                     },
-                    UpdateMsg::Error(err) => {
+                    UpdateEvent::Waiting { message, cancel } => {
+                        self.status = self.dict.status_waiting_for_app.to_string();
+                        self.waiting = Some((message, cancel));
+                    },
+                    UpdateEvent::Error(_, _) if self.declined => {
+                        ExitCode::Cancelled.exit();
+                    },
+                    UpdateEvent::Error(kind, err) => {
                         self.status = self.dict.status_failed.to_string();
                         self.error = Some(err);
+                        self.error_code = kind.into();
                     },
                 }
             }
         }
         
+        if let Some(receiver) = &self.summary_receiver && let Ok(summary) = receiver.try_recv() {
+            self.summary = Some(summary);
+        }
+        
         // 无论是否有更新，都请求重绘UI，确保界面实时更新
         ctx.request_repaint();
-        
-        // 设置现代化主题
-        ctx.set_visuals(Visuals::light());
+        // 限制单帧的时长，窗口被拖动等原因长时间没有绘制后恢复时进度条仍然平滑移动
+        self.ease_progress(ctx.input(|i| i.stable_dt).min(0.1));
         
         // 创建主窗口
         CentralPanel::default().show(ctx, |ui| {
             ui.vertical_centered(|ui| {
                 // 标题
-                ui.label(egui::RichText::new(self.dict.title).font(egui::FontId::proportional(24.0)).color(egui::Color32::from_rgb(0, 120, 212)));
+                ui.label(egui::RichText::new(self.dict.title).font(egui::FontId::proportional(24.0)).color(self.palette.title));
                 
-                ui.add_space(20.0);
+                // 标题下方显示更新包名称和目标路径，过长时省略中间部分，悬停时显示完整内容
+                let max_chars = (ui.available_width() / HEADER_CHAR_WIDTH) as usize;
+                let package_name = Path::new(&self.config.update.package_path).file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                for text in [self.dict.label_package(&package_name), self.dict.label_target(&self.target_text())] {
+                    ui.add(egui::Label::new(egui::RichText::new(shorten_middle(&text, max_chars))
+                        .font(egui::FontId::proportional(12.0))
+                        .color(self.palette.muted)).truncate())
+                        .on_hover_text(text);
+                }
+                
+                ui.add_space(10.0);
                 
                 // 状态信息
                 ui.label(egui::RichText::new(&self.status).font(egui::FontId::proportional(14.0)));
                 
+                // 警告信息，更新完成后改为显示在完成信息下方
+                if !self.is_complete {
+                    for warning in &self.warnings {
+                        ui.label(egui::RichText::new(warning).font(egui::FontId::proportional(12.0)).color(self.palette.warning));
+                    }
+                }
+                
                 ui.add_space(8.0);
                 
-                // 进度条
-                let progress = if self.total_files > 0 {
-                    self.current_file as f32 / self.total_files as f32
-                } else {
-                    0.0
-                };
+                // 进度条，复制阶段按字节数计算，文件数作为辅助信息显示
+                // 进度条平滑移动，百分比显示实际进度；更新已开始但还不知道总数时显示动画且不显示百分比，失败时改为错误颜色
+                let indeterminate = self.receiver.is_some() && self.error.is_none() && !self.is_complete && self.total_files == 0 && self.overall_progress == 0.0;
+                let mut bar = ProgressBar::new(self.displayed_progress).animate(indeterminate);
+                if !indeterminate {
+                    bar = bar.text(format!("{}%", (self.progress() * 100.0) as usize));
+                }
+                if self.error.is_some() {
+                    bar = bar.fill(self.palette.error);
+                }
+                ui.add(bar);
+                if let Some(phase) = self.phase && !self.is_complete && self.error.is_none() && self.failures.is_none() && self.plan.is_none() {
+                    ui.label(egui::RichText::new(self.phase_name(phase)).font(egui::FontId::proportional(12.0)).color(self.palette.muted));
+                }
+                if self.total_files > 0 {
+                    ui.label(egui::RichText::new(self.dict.status_file_count(self.current_file, self.total_files))
+                        .font(egui::FontId::proportional(12.0))
+                        .color(self.palette.muted));
+                }
                 
-                ui.add(ProgressBar::new(progress).show_percentage());
+                // 复制过程中显示传输速度和预计剩余时间
+                if !self.is_complete && self.error.is_none() && self.failures.is_none() && let Some((speed, eta)) = self.transfer_rate() {
+                    ui.add_space(4.0);
+                    ui.label(egui::RichText::new(self.dict.status_transfer(speed as u64, eta))
+                        .font(egui::FontId::proportional(12.0))
+                        .color(self.palette.muted));
+                }
                 
                 ui.add_space(10.0);
                 
-                // 当前处理的文件
+                // 延时期间显示更新说明，可以跳过剩余延时立即更新，或按Esc取消
+                if self.receiver.is_none() && self.error.is_none() {
+                    if let Some(text) = &self.changelog {
+                        egui::ScrollArea::vertical().id_source("changelog").max_height(90.0).show(ui, |ui| {
+                            ui.with_layout(egui::Layout::top_down(egui::Align::Min), |ui| {
+                                changelog::show(ui, text, &self.palette);
+                            });
+                        });
+                        ui.add_space(5.0);
+                    }
+                    if let Some(hint) = &self.size_hint {
+                        ui.label(egui::RichText::new(hint).font(egui::FontId::proportional(12.0)).color(self.palette.muted));
+                        ui.add_space(5.0);
+                    }
+                    if ui.add(egui::Button::new(self.dict.button_update_now).min_size(egui::Vec2::new(80.0, 30.0))).clicked() {
+                        log::info!("用户选择立即更新");
+                        self.skip_delay = true;
+                    }
+                    ui.label(egui::RichText::new(self.dict.hint_cancel_delay).font(egui::FontId::proportional(12.0)).color(self.palette.muted));
+                }
+                
+                // 当前处理的文件，路径过长时可以横向滚动
                 if !self.current_file_name.is_empty() {
-                    ui.label(egui::RichText::new(self.dict.status_processing(&self.current_file_name))
-                        .font(egui::FontId::proportional(12.0))
-                        .color(egui::Color32::GRAY));
+                    egui::ScrollArea::horizontal().id_source("current_file").show(ui, |ui| {
+                        ui.add(egui::Label::new(egui::RichText::new(self.dict.status_processing(&self.current_file_name))
+                            .font(egui::FontId::proportional(12.0))
+                            .color(self.palette.muted)).extend());
+                    });
+                }
+                
+                // 遇到受保护的文件时暂停复制，由用户选择覆盖或跳过
+                if let Some((path, reply)) = &self.conflict {
+                    ui.add_space(10.0);
+                    ui.label(egui::RichText::new(self.dict.prompt_conflict(path)).font(egui::FontId::proportional(13.0)).color(self.palette.warning));
+                    ui.add_space(5.0);
+                    let choices = [
+                        (self.dict.button_overwrite, ConflictChoice::Overwrite),
+                        (self.dict.button_skip, ConflictChoice::Skip),
+                        (self.dict.button_overwrite_all, ConflictChoice::OverwriteAll),
+                        (self.dict.button_skip_all, ConflictChoice::SkipAll),
+                    ];
+                    let mut chosen = None;
+                    ui.horizontal(|ui| {
+                        // 按钮行居中显示
+                        let row_width = CONFLICT_BUTTON_WIDTH * choices.len() as f32 + ui.spacing().item_spacing.x * (choices.len() - 1) as f32;
+                        ui.add_space(((ui.available_width() - row_width) / 2.0).max(0.0));
+                        for (label, choice) in choices {
+                            if ui.add(egui::Button::new(label).min_size(egui::Vec2::new(CONFLICT_BUTTON_WIDTH, 30.0))).clicked() {
+                                chosen = Some(choice);
+                            }
+                        }
+                    });
+                    if let Some(choice) = chosen {
+                        log::info!("用户选择: {:?}", choice);
+                        let _ = reply.send(choice);
+                        self.conflict = None;
+                    }
+                }
+                
+                // 等待被占用的文件释放时显示等待动画和详情，可以取消更新
+                if let Some((message, cancel)) = &self.waiting {
+                    ui.add_space(10.0);
+                    ui.add(egui::Spinner::new().size(24.0));
+                    ui.label(egui::RichText::new(message).font(egui::FontId::proportional(12.0)).color(self.palette.muted));
+                    ui.add_space(10.0);
+                    if ui.add(egui::Button::new(self.dict.button_cancel).min_size(egui::Vec2::new(80.0, 30.0))).clicked() {
+                        log::info!("用户取消了等待");
+                        let _ = cancel.send(());
+                        self.declined = true;
+                        self.waiting = None;
+                    }
+                }
+                
+                // 确认模式下列出即将执行的操作，用户选择应用后才开始复制
+                if let Some((actions, total_bytes, reply)) = &self.confirm {
+                    ui.add_space(10.0);
+                    ui.label(egui::RichText::new(self.dict.confirm_title).font(egui::FontId::proportional(14.0)).color(self.palette.warning));
+                    let deleted = actions.iter().filter(|action| matches!(action, PlannedAction::Delete(_))).count();
+                    ui.label(egui::RichText::new(self.dict.confirm_summary(actions.len() - deleted, *total_bytes, deleted)).font(egui::FontId::proportional(13.0)));
+                    ui.add_space(5.0);
+                    egui::ScrollArea::vertical().id_source("confirm").max_height(90.0).show(ui, |ui| {
+                        for action in actions {
+                            ui.label(egui::RichText::new(self.plan_text(action)).font(egui::FontId::proportional(12.0)).color(self.palette.muted));
+                        }
+                    });
+                    ui.add_space(10.0);
+                    let mut chosen = None;
+                    ui.horizontal(|ui| {
+                        let row_width = CONFLICT_BUTTON_WIDTH * 2.0 + ui.spacing().item_spacing.x;
+                        ui.add_space(((ui.available_width() - row_width) / 2.0).max(0.0));
+                        for (label, apply) in [(self.dict.button_apply, true), (self.dict.button_cancel, false)] {
+                            if ui.add(egui::Button::new(label).min_size(egui::Vec2::new(CONFLICT_BUTTON_WIDTH, 30.0))).clicked() {
+                                chosen = Some(apply);
+                            }
+                        }
+                    });
+                    if let Some(apply) = chosen {
+                        log::info!("用户选择{}更新", if apply { "应用" } else { "取消" });
+                        let _ = reply.send(apply);
+                        self.declined = !apply;
+                        self.confirm = None;
+                    }
                 }
                 
                 // 显示完成或错误信息
                 if self.is_complete {
                     ui.add_space(15.0);
-                    ui.label(egui::RichText::new(self.dict.status_complete).font(egui::FontId::proportional(16.0)).color(egui::Color32::GREEN));
+                    ui.label(egui::RichText::new(self.dict.status_complete).font(egui::FontId::proportional(16.0)).color(self.palette.success));
+                    self.show_summary(ui);
+                    for warning in &self.warnings {
+                        ui.label(egui::RichText::new(warning).font(egui::FontId::proportional(12.0)).color(self.palette.muted));
+                    }
                     ui.add_space(15.0);
+                    
+                    // 指定了自动关闭时在按钮上显示倒计时，到时间后与点击确定相同
+                    let remaining = self.config.auto_close.zip(self.complete_time)
+                        .map(|(seconds, complete_time)| seconds.saturating_sub(complete_time.elapsed().as_secs()));
+                    let label = match remaining {
+                        Some(remaining) => format!("{} ({})", self.dict.button_ok, remaining),
+                        None => self.dict.button_ok.to_string(),
+                    };
+                    let clicked = ui.add(egui::Button::new(label).min_size(egui::Vec2::new(80.0, 30.0))).clicked();
+                    if clicked || remaining == Some(0) {
+                        if !clicked {
+                            log::info!("更新完成，自动关闭窗口");
+                        }
+                        launch_start_exe(&self.config);
+                        ExitCode::Success.exit();
+                    }
+                }
+                
+                // 演练模式下显示计划执行的操作列表
+                if let Some(plan) = &self.plan {
+                    ui.add_space(10.0);
+                    ui.label(egui::RichText::new(self.dict.plan_summary(plan.len())).font(egui::FontId::proportional(13.0)));
+                    ui.add_space(5.0);
+                    egui::ScrollArea::vertical().max_height(90.0).show(ui, |ui| {
+                        for action in plan {
+                            ui.label(egui::RichText::new(self.plan_text(action)).font(egui::FontId::proportional(12.0)).color(self.palette.muted));
+                        }
+                    });
+                    ui.add_space(10.0);
                     if ui.add(egui::Button::new(self.dict.button_ok).min_size(egui::Vec2::new(80.0, 30.0))).clicked() {
-                        // 如果提供了启动exe路径，启动该exe
-                        if let Some(exe_path) = &self.start_exe_path {
-                            log::info!("启动应用程序: {}", exe_path);
-                            if let Err(e) = std::process::Command::new(exe_path).spawn() {
-                                log::error!("启动应用程序失败: {}", e);
-                            }
+                        ExitCode::Success.exit();
+                    }
+                }
+                
+                // 部分文件失败时列出失败的文件及原因
+                if let Some(failures) = &self.failures {
+                    ui.add_space(10.0);
+                    ui.label(egui::RichText::new(self.dict.status_partial_complete(failures.len())).font(egui::FontId::proportional(14.0)).color(self.palette.error));
+                    self.show_summary(ui);
+                    ui.add_space(5.0);
+                    egui::ScrollArea::vertical().id_source("failures").max_height((ui.available_height() - 50.0).max(60.0)).show(ui, |ui| {
+                        for (path, error) in failures {
+                            ui.label(egui::RichText::new(format!("{}: {}", path.display(), error)).font(egui::FontId::proportional(12.0)));
                         }
-                        std::process::exit(0);
+                    });
+                    ui.add_space(10.0);
+                    if ui.add(egui::Button::new(self.dict.button_ok).min_size(egui::Vec2::new(80.0, 30.0))).clicked() {
+                        ExitCode::Copy.exit();
                     }
                 }
                 
                 if let Some(error) = &self.error {
                     ui.add_space(15.0);
-                    ui.label(egui::RichText::new(self.dict.status_failed).font(egui::FontId::proportional(16.0)).color(egui::Color32::RED));
+                    ui.label(egui::RichText::new(self.dict.status_failed).font(egui::FontId::proportional(16.0)).color(self.palette.error));
                     ui.add_space(10.0);
-                    ui.label(egui::RichText::new(error).font(egui::FontId::proportional(13.0)));
+                    egui::ScrollArea::vertical().id_source("error").max_height((ui.available_height() - 50.0).max(60.0)).show(ui, |ui| {
+                        ui.label(egui::RichText::new(error).font(egui::FontId::proportional(13.0)));
+                    });
                     ui.add_space(15.0);
                     if ui.add(egui::Button::new(self.dict.button_ok).min_size(egui::Vec2::new(80.0, 30.0))).clicked() {
-                        std::process::exit(1);
+                        self.error_code.exit();
                     }
                 }
+                
+                // 可展开的详细信息，显示最近的日志，默认收起
+                ui.add_space(10.0);
+                egui::CollapsingHeader::new(self.dict.label_details).id_source("details").default_open(false).show(ui, |ui| {
+                    egui::ScrollArea::both().id_source("details_log").max_height(120.0).stick_to_bottom(true).show(ui, |ui| {
+                        ui.with_layout(egui::Layout::top_down(egui::Align::Min), |ui| {
+                            for line in logging::recent_lines() {
+                                ui.add(egui::Label::new(egui::RichText::new(line).font(egui::FontId::monospace(11.0)).color(self.palette.muted)).extend());
+                            }
+                        });
+                    });
+                });
             });
         });
     }
 }
 
 fn main() -> io::Result<()> {
-    // 初始化日志系统
-    env_logger::init();
+    // 获取命令行参数（不包含程序名）
+    let args: Vec<String> = env::args().skip(1).collect();
+    // 合并从标准输入读取的参数，提升权限后重新启动时也使用合并后的参数
+    let args = match with_stdin_args(args) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::InvalidArgs.exit();
+        }
+    };
     
-    // 获取命令行参数
-    let args: Vec<String> = env::args().collect();
+    // 解析命令行参数，参数无效时在界面中显示错误和用法
+    let (config, arg_error) = match parse_args(&args) {
+        Ok(config) => (config, None),
+        Err(err) => {
+            if !args.is_empty() {
+                eprintln!("{}", err);
+            }
+            (Config::default_for(&args), Some(err))
+        }
+    };
     
-    // 解析语言选项，默认为中文
-    let mut lang = Language::Chinese;
-    let mut lang_index = 0;
+    // 不带任何参数启动且配置文件中也没有完整的参数时，显示启动表单而不是参数错误
+    let show_form = args.is_empty() && arg_error.is_some();
     
-    // 解析参数
-    let package_path = if args.len() > 1 {
-        args[1].clone()
-    } else {
-        "".to_string()
-    };
+    // 初始化日志系统，同时写入日志文件
+    logging::init(config.log_path.as_deref());
+    if show_form {
+        log::info!("未指定参数，显示启动表单");
+    } else if let Some(err) = &arg_error {
+        log::error!("命令行参数无效: {}", err);
+    }
+    if config.positional_args {
+        log::warn!("位置参数用法已弃用，请改用 --package/--inner-path/--target/--delay/--lang 等选项");
+    }
     
-    // 解析压缩包内路径，默认为根目录
-    let zip_inner_path = if args.len() > 2 {
-        args[2].clone()
-    } else {
-        "".to_string()
-    };
+    // 完成上次更新暂存的自我替换，替换后由新版本重新启动
+    match self_update::handoff(&config.update.self_suffix) {
+        Ok(true) => return Ok(()),
+        Ok(false) => {},
+        Err(e) => log::error!("替换暂存的新版本失败: {}", e),
+    }
     
-    let mut target_path = None;
-    let mut delay_seconds = 0;
-    let mut start_exe_path = None;
+    // 显示帮助信息后退出
+    if config.show_help {
+        println!("{}", get_dict(config.update.lang).usage);
+        return Ok(());
+    }
     
-    // 解析目标路径、延时参数、启动exe路径和语言选项
-    for i in 3..args.len() {
-        if parse_language(&args[i]).is_some() {
-            lang_index = i;
-            break;
-        } else if target_path.is_none() {
-            target_path = Some(args[i].clone());
-        } else if delay_seconds == 0 {
-            // 尝试解析为延时参数
-            if let Ok(seconds) = args[i].parse::<u64>() {
-                delay_seconds = seconds;
-            }
-        } else if start_exe_path.is_none() {
-            // 解析为启动exe路径
-            start_exe_path = Some(args[i].clone());
+    // 自检模式下只检查运行环境并输出结果，不执行更新
+    if config.self_test {
+        let code = self_test::run(&config);
+        if code != ExitCode::Success {
+            code.exit();
         }
+        return Ok(());
     }
     
-    // 解析语言
-    if lang_index > 0 {
-        lang = match parse_language(&args[lang_index]) {
-            Some(l) => l,
-            None => {
-                eprintln!("无效的语言选项: {}. 使用默认语言: 中文", args[lang_index]);
-                Language::Chinese
-            }
-        };
+    // 检查模式下只列出更新包的内容，不执行更新
+    if config.inspect {
+        let code = inspect::run(&config);
+        if code != ExitCode::Success {
+            code.exit();
+        }
+        return Ok(());
     }
     
-    // 设置窗口选项
-    let mut viewport_builder = egui::ViewportBuilder::default()
-        .with_inner_size([450.0, 250.0])
-        .with_resizable(false)
-        .with_always_on_top();
+    // 需要管理员权限时以runas重新启动自身，由提升权限后的新进程执行更新
+    #[cfg(windows)]
+    if config.elevate && !elevate::is_elevated() {
+        let dict = get_dict(config.update.lang);
+        if let Err(e) = elevate::relaunch_elevated(&args) {
+            log::error!("无法以管理员权限重新启动: {}", e);
+            elevate::show_error(dict.title, dict.error_elevation_declined);
+            ExitCode::Cancelled.exit();
+        }
+        return Ok(());
+    }
+    #[cfg(not(windows))]
+    if config.elevate {
+        log::warn!("--elevate 仅在Windows上有效，已忽略");
+    }
     
-    // 嵌入图标文件到可执行文件中
-    let icon_bytes = include_bytes!(r"../assets/update.png");
+    // JSON模式和静默模式下不显示界面，直接在主线程执行更新
+    if config.json || config.silent {
+        let code = headless::run(&config, arg_error);
+        if code != ExitCode::Success {
+            code.exit();
+        }
+        return Ok(());
+    }
+    let lang = config.update.lang;
     
-    // 使用image crate解码嵌入的PNG数据
-    if let Ok(img) = image::load_from_memory(icon_bytes) {
-        log::info!("成功解码嵌入的PNG图标");
-        
-        // 转换为RGBA格式
-        let img = img.into_rgba8();
-        let width = img.width();
-        let height = img.height();
-        let rgba = img.into_raw();
-        
-        // 创建IconData
-        let icon_data = egui::IconData {
-            rgba,
-            width,
-            height,
-        };
-        
-        // 设置图标
-        viewport_builder = viewport_builder.with_icon(icon_data);
-        log::info!("已设置窗口图标");
-    } else {
-        log::error!("无法解码嵌入的PNG图标");
-        // 不再绘制备用图标，直接使用默认图标
+    // 显示界面前先检查更新包，明显无效时直接报告错误并退出，不必等到延时结束
+    if arg_error.is_none() && let Err(err) = check_package(&config.update) {
+        log::error!("更新包无效: {}", err);
+        eprintln!("{}", err);
+        #[cfg(windows)]
+        elevate::show_error(get_dict(lang).title, &err.to_string());
+        ExitCode::from(err.kind()).exit();
+    }
+    
+    // 设置窗口选项
+    let mut viewport_builder = egui::ViewportBuilder::default()
+        .with_inner_size([config.width, config.height])
+        .with_resizable(config.resizable);
+    if config.topmost {
+        viewport_builder = viewport_builder.with_always_on_top();
     }
     
+    // 设置窗口图标，嵌入的PNG无法解码时使用备用图标
+    viewport_builder = viewport_builder.with_icon(icon::load_icon());
+    log::info!("已设置窗口图标");
+    
     let options = eframe::NativeOptions {
         viewport: viewport_builder,
-        centered: true,
+        centered: config.center,
         ..Default::default()
     };
     
@@ -308,194 +773,67 @@ fn main() -> io::Result<()> {
         dict.title,
         options,
         Box::new(move |cc| {
-            // 配置字体以支持中文显示
-            let mut fonts = egui::FontDefinitions::default();
+            // 配置字体以支持中文和日文显示
+            fonts::configure_fonts(&cc.egui_ctx);
+            apply_scale(&cc.egui_ctx, &config);
             
-            // 添加微软雅黑字体
-            fonts.font_data.insert(
-                "system_font".to_owned(),
-                egui::FontData::from_static(include_bytes!(r"C:\Windows\Fonts\msyh.ttc")),
-            );
+            // 应用界面主题，之后每帧不再重复设置
+            let palette = theme::apply(&cc.egui_ctx, config.theme);
             
-            // 将中文字体添加到默认字体列表
-            for family in &mut fonts.families.values_mut() {
-                family.insert(0, "system_font".to_owned());
+            let mut app = UpdateApp::new(config, palette);
+            if show_form {
+                app.form = Some(StartForm::new(lang));
+            } else if let Some(err) = arg_error {
+                app.show_usage_error(err);
             }
-            
-            // 应用字体配置
-            cc.egui_ctx.set_fonts(fonts);
-            
-            Ok(Box::new(UpdateApp::new(package_path, lang, target_path, zip_inner_path, delay_seconds, start_exe_path)))
+            Ok(Box::new(app))
         }),
     ).unwrap();
     
     Ok(())
 }
 
-// 执行更新操作
-fn perform_update(package_path: &str, target_path: &Option<String>, zip_inner_path: &str, sender: mpsc::Sender<UpdateMsg>) {
-    match actual_perform_update(package_path, target_path, zip_inner_path, sender.clone()) {
-        Ok(_) => {
-            log::info!("更新完成！");
-            
-            // 更新完成后，删除源zip文件
-            if let Err(e) = std::fs::remove_file(package_path) {
-                log::error!("删除源zip文件失败: {}", e);
-            } else {
-                log::info!("已成功删除源zip文件: {}", package_path);
-            }
-        },
-        Err(e) => {
-            let error_msg = e.to_string();
-            log::error!("更新失败: {}", error_msg);
-            if let Err(send_err) = sender.send(UpdateMsg::Error(error_msg)) {
-                log::error!("无法发送错误消息: {:?}", send_err);
-            }
-        }
+// 指定了 --scale 时设置界面缩放比例，只在启动时设置一次；窗口大小按新的比例重新设置，仍能完整显示界面
+fn apply_scale(ctx: &egui::Context, config: &Config) {
+    if let Some(scale) = config.scale {
+        log::info!("界面缩放比例: {}", scale);
+        ctx.set_pixels_per_point(scale);
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::vec2(config.width, config.height)));
     }
 }
 
-// 实际执行更新操作的内部函数
-fn actual_perform_update(package_path: &str, target_path: &Option<String>, zip_inner_path: &str, sender: mpsc::Sender<UpdateMsg>) -> io::Result<()> {
-    // 检查必要参数
-    if package_path.is_empty() {
-        return Err(io::Error::new(io::ErrorKind::InvalidInput, "未提供更新包路径"));
-    }
-    
-    // 必须提供目标路径
-    let target_dir = match target_path {
-        Some(path) => {
-            log::info!("使用命令行指定的目标目录: {:?}", path);
-            Path::new(path).to_path_buf()
-        },
-        None => {
-            return Err(io::Error::new(io::ErrorKind::InvalidInput, "必须提供目标路径"));
-        }
-    };
-    
-    // 获取当前可执行文件路径
-    let exe_path = env::current_exe()?;
-    let exe_name = exe_path.file_name().unwrap().to_str().unwrap();
-    
-    // 确定目标更新目录
-    let current_dir = target_dir;
-    
-    // 创建临时目录用于解压
-    let temp_dir = tempdir()?;
-    let temp_path = temp_dir.path();
-    
-    // 打开zip文件
-    log::info!("正在解压更新包: {}", package_path);
-    let file = fs::File::open(package_path)?;
-    let mut archive = ZipArchive::new(file)?;
-    
-    // 发送解压状态
-    sender.send(UpdateMsg::Status("正在解压更新包...".to_string())).unwrap();
-    
-    // 计算总文件数
-    let total_files = archive.len();
-    sender.send(UpdateMsg::TotalFiles(total_files)).unwrap();
-    
-    // 逐文件解压，实时更新进度
-    for i in 0..total_files {
-        let mut file = archive.by_index(i)?;
-        let outpath = match file.enclosed_name() {
-            Some(path) => temp_path.join(path),
-            None => continue,
-        };
-        
-        // 发送当前解压的文件名称和进度
-        let file_name = file.name().to_string();
-        sender.send(UpdateMsg::Progress(i + 1, total_files, file_name.clone())).unwrap();
-        
-        // 创建目录
-        if let Some(p) = outpath.parent() {
-            if !p.exists() {
-                fs::create_dir_all(p)?;
-            }
-        }
-        
-        // 跳过目录
-        if (*file.name()).ends_with('/') {
-            continue;
-        }
-        
-        // 写入文件
-        let mut outfile = fs::File::create(&outpath)?;
-        std::io::copy(&mut file, &mut outfile)?;
+// 文本超过指定字符数时省略中间部分，保留开头和结尾（路径的盘符和文件名通常最有用）
+fn shorten_middle(text: &str, max_chars: usize) -> String {
+    let count = text.chars().count();
+    if count <= max_chars || max_chars <= 3 {
+        return text.to_string();
     }
-    
-    // 找到解压后的指定目录
-    let inner_path = if zip_inner_path.is_empty() {
-        temp_path.to_path_buf()
-    } else {
-        temp_path.join(zip_inner_path)
-    };
-    log::info!("压缩包内指定目录路径: {:?}", inner_path);
-    
-    // 验证指定目录是否存在
-    if !inner_path.exists() {
-        return Err(io::Error::new(io::ErrorKind::NotFound, format!("更新包中未找到指定目录: {}", zip_inner_path)));
+    let keep = max_chars - 3;
+    let head: String = text.chars().take(keep / 2).collect();
+    let tail: String = text.chars().skip(count - (keep - keep / 2)).collect();
+    format!("{}...{}", head, tail)
+}
+
+// 指定了 --show-size 时读取更新包的中央目录，返回本次更新将写入的总大小；无法读取时只记录日志，错误留给更新流程报告
+fn size_hint(config: &Config) -> Option<String> {
+    if !config.show_size {
+        return None;
     }
-    
-    // 计算指定目录下的总文件数
-    let total_files: usize = WalkDir::new(&inner_path)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().is_file())
-        .count();
-    
-    // 发送替换文件状态和总文件数
-    sender.send(UpdateMsg::Status("正在复制文件...".to_string())).unwrap();
-    sender.send(UpdateMsg::TotalFiles(total_files)).unwrap();
-    
-    // 遍历指定目录下的文件，复制到目标目录
-    log::info!("开始复制文件...");
-    let mut current_file = 0;
-    
-    for entry in WalkDir::new(&inner_path).into_iter().filter_map(|e| e.ok()) {
-        let entry_path = entry.path();
-        if entry_path.is_dir() {
-            continue;
-        }
-        
-        // 计算相对路径（相对于指定目录）
-        let relative_path = entry_path.strip_prefix(&inner_path)
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
-        let dest_path = current_dir.join(relative_path);
-        
-        // 确保目标目录存在
-        if let Some(parent) = dest_path.parent() {
-            fs::create_dir_all(parent)?;
+    match update_size(&config.update) {
+        Ok(bytes) => Some(get_dict(config.update.lang).hint_update_size(bytes)),
+        Err(e) => {
+            log::warn!("无法计算更新大小: {}", e);
+            None
         }
-        
-        // 处理当前运行的可执行文件
-        let final_dest_path = if let Some(file_name) = dest_path.file_name() {
-            if file_name.to_str().unwrap() == exe_name {
-                // 如果是当前运行的可执行文件，将其重命名为.exe.new后缀
-                let new_path_str = format!("{}.new", dest_path.to_str().unwrap());
-                let new_path = Path::new(&new_path_str).to_path_buf();
-                log::info!("重命名当前运行文件: {:?} -> {:?}", dest_path, new_path);
-                new_path
-            } else {
-                dest_path.clone()
-            }
-        } else {
-            dest_path.clone()
-        };
-        
-        // 复制文件
-        current_file += 1;
-        let file_name = relative_path.to_str().unwrap().to_string();
-        sender.send(UpdateMsg::Progress(current_file, total_files, file_name.clone())).unwrap();
-        log::info!("复制文件: {:?} -> {:?}", entry_path, final_dest_path);
-        fs::copy(entry_path, final_dest_path)?;
     }
-    
-    // 发送完成消息
-    sender.send(UpdateMsg::Complete).unwrap();
-    
-    Ok(())
 }
 
-
+// 如果提供了启动exe路径，启动该exe
+fn launch_start_exe(config: &Config) {
+    if let Some(exe_path) = &config.start_exe_path {
+        log::info!("启动应用程序: {}", exe_path);
+        if let Err(e) = std::process::Command::new(exe_path).spawn() {
+            log::error!("启动应用程序失败: {}", e);
+        }
+    }
+}
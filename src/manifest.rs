@@ -0,0 +1,89 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::language::LangDict;
+use crate::update::{contained_path, sha256_file};
+
+// 更新包内的校验清单文件名，位于要复制的目录的根部，本身不会被复制
+pub const MANIFEST_NAME: &str = "manifest.sha256";
+
+// 解析校验清单，每行为"相对路径  十六进制摘要"，忽略空行和以#开头的注释行
+pub fn parse_manifest(text: &str) -> Vec<(PathBuf, String)> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.rsplit_once(char::is_whitespace) {
+            // 路径中可能包含空格，摘要总是最后一列
+            Some((path, digest)) => (PathBuf::from(path.trim_end()), digest.to_lowercase()),
+            None => (PathBuf::from(line), String::new()),
+        })
+        .collect()
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// 如果更新包中有校验清单，在复制前逐个验证其中列出的文件，遇到缺失或不一致的文件立即失败；
+// 验证每个文件前以当前序号、总数和相对路径调用 on_entry
+pub fn verify(inner_path: &Path, dict: &LangDict, mut on_entry: impl FnMut(usize, usize, &str)) -> io::Result<bool> {
+    let manifest_path = inner_path.join(MANIFEST_NAME);
+    if !manifest_path.is_file() {
+        return Ok(false);
+    }
+    let entries = parse_manifest(&fs::read_to_string(&manifest_path)?);
+    log::info!("校验清单中共有 {} 个文件", entries.len());
+
+    let total = entries.len();
+    for (i, (relative_path, expected)) in entries.into_iter().enumerate() {
+        let display = relative_path.display().to_string();
+        on_entry(i + 1, total, &display);
+        let path = contained_path(inner_path, &relative_path)
+            .filter(|path| path.is_file())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, dict.error_manifest_missing(&display)))?;
+        let actual = to_hex(&sha256_file(&path)?);
+        if actual != expected {
+            log::error!("文件摘要不一致: {}，期望 {}，实际 {}", display, expected, actual);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, dict.error_manifest_mismatch(&display)));
+        }
+    }
+    log::info!("校验清单验证通过");
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::{Language, get_dict};
+
+    #[test]
+    fn parses_lines_with_spaces_and_comments() {
+        let text = "# 校验清单\n\nbin/app.exe  ABCDEF\n  docs/user guide.pdf\t0123  \nlonely\n";
+        assert_eq!(parse_manifest(text), vec![
+            (PathBuf::from("bin/app.exe"), "abcdef".to_string()),
+            (PathBuf::from("docs/user guide.pdf"), "0123".to_string()),
+            (PathBuf::from("lonely"), String::new()),
+        ]);
+    }
+
+    #[test]
+    fn verifies_files_against_manifest() {
+        let dict = get_dict(Language::English);
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!verify(dir.path(), dict, |_, _, _| {}).unwrap());
+
+        fs::write(dir.path().join("a file.txt"), "A").unwrap();
+        let digest = to_hex(&sha256_file(&dir.path().join("a file.txt")).unwrap());
+        fs::write(dir.path().join(MANIFEST_NAME), format!("a file.txt  {}\n", digest)).unwrap();
+        let mut seen = Vec::new();
+        assert!(verify(dir.path(), dict, |current, total, file| seen.push((current, total, file.to_string()))).unwrap());
+        assert_eq!(seen, [(1, 1, "a file.txt".to_string())]);
+
+        fs::write(dir.path().join("a file.txt"), "B").unwrap();
+        let err = verify(dir.path(), dict, |_, _, _| {}).unwrap_err();
+        assert_eq!(err.to_string(), dict.error_manifest_mismatch("a file.txt"));
+        fs::write(dir.path().join(MANIFEST_NAME), "missing.txt 00\n").unwrap();
+        assert_eq!(verify(dir.path(), dict, |_, _, _| {}).unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,229 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+use software_updater::{PACKAGE_METADATA_NAME, PACKAGE_TARGET_NAME, PackageFormat, read_package_file};
+
+// 更新包作者在 update.json 中指定的默认值，命令行和配置文件中的值优先
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PackageMetadata {
+    pub inner_path: Option<String>,
+    pub target: Option<String>,
+    // 更新成功后要启动的程序，相对路径相对于目标目录
+    pub relaunch: Option<String>,
+    // 更新程序的最低版本
+    pub min_version: Option<semver::Version>,
+    // 更新包的版本，以及可以直接更新到该版本的最低已安装版本
+    pub version: Option<semver::Version>,
+    pub min_current_version: Option<semver::Version>,
+    // 目标目录中记录已安装版本的文件（相对于目标目录），未指定 --current-version 时读取
+    pub version_file: Option<String>,
+}
+
+// 拒绝应用更新包的原因
+#[derive(Debug)]
+pub enum Rejection {
+    // 更新包版本不高于已安装版本
+    NotNewer(semver::Version),
+    // 已安装版本低于更新包要求的最低版本，不能直接更新
+    BelowMinimum(semver::Version),
+}
+
+// 读取更新包（压缩包、tar.gz或目录）中的 update.json，没有目标路径时再读取 target.txt 的第一个非空行作为目标路径；
+// 加密的更新包使用指定的密码。更新包无法打开或两个文件都没有时返回None（更新包的错误留给更新流程报告），
+// 文件内容无效时返回错误原因
+pub fn load(package_path: &str, format: Option<PackageFormat>, password: Option<&str>) -> Result<Option<PackageMetadata>, String> {
+    let metadata = read_root_file(package_path, format, password, PACKAGE_METADATA_NAME)?
+        .map(|text| serde_json::from_str::<PackageMetadata>(&text).map_err(|e| e.to_string()))
+        .transpose()?;
+    if metadata.as_ref().is_some_and(|metadata| metadata.target.is_some()) {
+        return Ok(metadata);
+    }
+    let Some(text) = read_root_file(package_path, format, password, PACKAGE_TARGET_NAME)? else {
+        return Ok(metadata);
+    };
+    let target = text.lines().map(str::trim).find(|line| !line.is_empty()).map(str::to_string);
+    Ok(Some(PackageMetadata { target, ..metadata.unwrap_or_default() }))
+}
+
+// 读取更新包根目录下的文本文件，与更新流程一样按 --format 或检测到的格式打开更新包；更新包无法打开或没有该文件时返回None
+fn read_root_file(package_path: &str, format: Option<PackageFormat>, password: Option<&str>, name: &str) -> Result<Option<String>, String> {
+    read_package_file(package_path, format, password, name).map_err(|e| e.to_string())
+}
+
+// 展开路径中的环境变量，支持Windows风格的 %ProgramFiles% 和Unix风格的 $HOME、${HOME}，在所有系统上都可以使用；
+// 不构成变量名的 % 和 $ 原样保留（如 \\server\c$\app），变量未定义时返回变量名
+pub fn expand_env(text: &str) -> Result<String, String> {
+    expand_vars(text, |name| env::var(name).ok())
+}
+
+// 按 lookup 查找变量的值展开路径中的变量，规则与 expand_env 相同
+fn expand_vars(text: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(pos) = rest.find(['%', '$']) {
+        result.push_str(&rest[..pos]);
+        let marker = &rest[pos..pos + 1];
+        let after = &rest[pos + 1..];
+        // 变量名和变量引用之后剩余的文本
+        let variable = if marker == "%" {
+            // Windows的变量名可以包含括号，如 %ProgramFiles(x86)%
+            after.find('%').map(|end| (&after[..end], &after[end + 1..]))
+                .filter(|(name, _)| is_var_name(name, "()"))
+        } else if let Some(braced) = after.strip_prefix('{') {
+            braced.find('}').map(|end| (&braced[..end], &braced[end + 1..]))
+                .filter(|(name, _)| is_var_name(name, ""))
+        } else {
+            let end = after.find(|c: char| !c.is_ascii_alphanumeric() && c != '_').unwrap_or(after.len());
+            Some((&after[..end], &after[end..])).filter(|(name, _)| is_var_name(name, ""))
+        };
+        match variable {
+            Some((name, remaining)) => {
+                result.push_str(&lookup(name).ok_or_else(|| name.to_string())?);
+                rest = remaining;
+            },
+            None => {
+                result.push_str(marker);
+                rest = after;
+            },
+        }
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+// 是否是有效的环境变量名：不为空，由字母、数字、下划线和允许的其他字符组成，不以数字开头
+fn is_var_name(name: &str, extra: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || extra.contains(c))
+}
+
+// 当前更新程序是否满足更新包要求的最低版本
+pub fn version_supported(metadata: &PackageMetadata) -> bool {
+    let current = semver::Version::parse(env!("CARGO_PKG_VERSION")).expect("包版本号应为有效的语义化版本");
+    metadata.min_version.as_ref().is_none_or(|min| current >= *min)
+}
+
+// 读取目标目录中 version_file 记录的已安装版本，未声明该文件或尚未安装时返回None，内容无效时返回文件内容
+pub fn installed_version(metadata: &PackageMetadata, target: Option<&str>) -> Result<Option<semver::Version>, String> {
+    let (Some(name), Some(target)) = (&metadata.version_file, target) else {
+        return Ok(None);
+    };
+    let Ok(text) = fs::read_to_string(Path::new(target).join(name)) else {
+        return Ok(None);
+    };
+    semver::Version::parse(text.trim()).map(Some).map_err(|_| text.trim().to_string())
+}
+
+// 比较已安装版本和更新包声明的版本，决定是否可以应用更新
+pub fn check_upgrade(current: &semver::Version, metadata: &PackageMetadata) -> Result<(), Rejection> {
+    if let Some(min) = &metadata.min_current_version && current < min {
+        return Err(Rejection::BelowMinimum(min.clone()));
+    }
+    if let Some(version) = &metadata.version && version <= current {
+        return Err(Rejection::NotNewer(version.clone()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn version(text: &str) -> semver::Version {
+        semver::Version::parse(text).unwrap()
+    }
+
+    fn metadata(version_text: &str, min_current: Option<&str>) -> PackageMetadata {
+        PackageMetadata { version: Some(version(version_text)), min_current_version: min_current.map(version), ..Default::default() }
+    }
+
+    #[test]
+    fn accepts_newer_package() {
+        assert!(check_upgrade(&version("1.2.0"), &metadata("1.3.0", None)).is_ok());
+        assert!(check_upgrade(&version("1.2.0"), &metadata("1.2.1-beta.1", Some("1.0.0"))).is_ok());
+        // 更新包没有声明版本时不比较
+        assert!(check_upgrade(&version("9.0.0"), &PackageMetadata::default()).is_ok());
+    }
+
+    #[test]
+    fn rejects_equal_or_older_package() {
+        assert!(matches!(check_upgrade(&version("1.2.0"), &metadata("1.2.0", None)), Err(Rejection::NotNewer(v)) if v == version("1.2.0")));
+        assert!(matches!(check_upgrade(&version("1.2.0"), &metadata("1.1.9", None)), Err(Rejection::NotNewer(_))));
+        assert!(matches!(check_upgrade(&version("1.2.0"), &metadata("1.2.0-rc.1", None)), Err(Rejection::NotNewer(_))));
+    }
+
+    #[test]
+    fn rejects_installed_version_below_minimum() {
+        assert!(matches!(check_upgrade(&version("0.9.0"), &metadata("2.0.0", Some("1.0.0"))), Err(Rejection::BelowMinimum(v)) if v == version("1.0.0")));
+        assert!(check_upgrade(&version("1.0.0"), &metadata("2.0.0", Some("1.0.0"))).is_ok());
+    }
+
+    #[test]
+    fn reads_installed_version_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().to_str().unwrap();
+        let metadata = PackageMetadata { version_file: Some("VERSION".to_string()), ..Default::default() };
+        assert_eq!(installed_version(&metadata, Some(target)).unwrap(), None);
+        fs::write(dir.path().join("VERSION"), "1.4.2\n").unwrap();
+        assert_eq!(installed_version(&metadata, Some(target)).unwrap(), Some(version("1.4.2")));
+        fs::write(dir.path().join("VERSION"), "latest").unwrap();
+        assert_eq!(installed_version(&metadata, Some(target)).unwrap_err(), "latest");
+    }
+
+    // 使用固定的变量表测试展开规则，不修改进程的环境变量
+    #[test]
+    fn expands_windows_and_unix_variables() {
+        let vars = HashMap::from([("ROOT", "/opt/root"), ("ProgramFiles(x86)", "C:\\Program Files (x86)")]);
+        let expand = |text: &str| expand_vars(text, |name| vars.get(name).map(|value| value.to_string()));
+        assert_eq!(expand("%ROOT%\\App").unwrap(), "/opt/root\\App");
+        assert_eq!(expand("%ProgramFiles(x86)%\\App").unwrap(), "C:\\Program Files (x86)\\App");
+        assert_eq!(expand("$ROOT/app").unwrap(), "/opt/root/app");
+        assert_eq!(expand("${ROOT}_v2").unwrap(), "/opt/root_v2");
+        // 不构成变量名的 % 和 $ 原样保留
+        assert_eq!(expand(r"\\server\c$\app").unwrap(), r"\\server\c$\app");
+        assert_eq!(expand("100% done, $5").unwrap(), "100% done, $5");
+        assert_eq!(expand("%UNDEFINED%").unwrap_err(), "UNDEFINED");
+        assert_eq!(expand("$UNDEFINED/app").unwrap_err(), "UNDEFINED");
+    }
+
+    #[test]
+    fn reads_target_from_package() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = dir.path().to_str().unwrap();
+        assert!(load(package, None, None).unwrap().is_none());
+        fs::write(dir.path().join(PACKAGE_TARGET_NAME), "\n  /opt/app  \nignored\n").unwrap();
+        assert_eq!(load(package, None, None).unwrap().unwrap().target.as_deref(), Some("/opt/app"));
+        // update.json 中的目标路径优先于 target.txt
+        fs::write(dir.path().join(PACKAGE_METADATA_NAME), r#"{"target": "/srv/app", "inner_path": "bin"}"#).unwrap();
+        let metadata = load(package, None, None).unwrap().unwrap();
+        assert_eq!(metadata.target.as_deref(), Some("/srv/app"));
+        assert_eq!(metadata.inner_path.as_deref(), Some("bin"));
+    }
+
+    // tar.gz更新包与更新流程一样按内容识别，指定的格式无法打开时不读取
+    #[test]
+    fn reads_metadata_from_tar_gz_package() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("download.tmp");
+        let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(fs::File::create(&path).unwrap(), flate2::Compression::default()));
+        let json = br#"{"target": "/srv/app", "version": "2.0.0"}"#;
+        let mut header = tar::Header::new_gnu();
+        header.set_mode(0o644);
+        header.set_size(json.len() as u64);
+        tar.append_data(&mut header, PACKAGE_METADATA_NAME, &json[..]).unwrap();
+        tar.into_inner().unwrap().finish().unwrap();
+        let package = path.to_str().unwrap();
+
+        let metadata = load(package, None, None).unwrap().unwrap();
+        assert_eq!(metadata.target.as_deref(), Some("/srv/app"));
+        assert_eq!(metadata.version, Some(version("2.0.0")));
+        assert_eq!(load(package, Some(PackageFormat::TarGz), None).unwrap().unwrap().target.as_deref(), Some("/srv/app"));
+        assert!(load(package, Some(PackageFormat::Zip), None).unwrap().is_none());
+    }
+}
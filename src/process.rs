@@ -0,0 +1,91 @@
+use std::ffi::OsStr;
+use std::io;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System};
+
+use crate::UpdateEvent;
+use crate::language::LangDict;
+use crate::update::{EventSender, emit};
+
+// 正常结束进程后的等待时间，超时后强制结束
+const GRACEFUL_TIMEOUT: Duration = Duration::from_secs(5);
+// 强制结束进程后等待其退出并释放文件句柄的时间
+const KILL_TIMEOUT: Duration = Duration::from_secs(10);
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+// 进程名是否匹配，Windows上忽略大小写并允许省略.exe后缀
+fn matches_name(process_name: &OsStr, name: &str) -> bool {
+    let process_name = process_name.to_string_lossy();
+    if cfg!(windows) {
+        let process_name = process_name.to_lowercase();
+        let name = name.to_lowercase();
+        process_name == name || process_name.strip_suffix(".exe") == Some(name.as_str())
+    } else {
+        process_name == name
+    }
+}
+
+// 查找名称匹配的进程，不包括更新程序自身
+fn find_processes(system: &mut System, name: &str) -> Vec<Pid> {
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::nothing());
+    let current = sysinfo::get_current_pid().ok();
+    system.processes()
+        .iter()
+        .filter(|(pid, process)| Some(**pid) != current && matches_name(process.name(), name))
+        .map(|(pid, _)| *pid)
+        .collect()
+}
+
+// 等待进程退出，返回超时后仍在运行的进程
+fn wait_for_exit(system: &mut System, pids: &[Pid], timeout: Duration) -> Vec<Pid> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        system.refresh_processes_specifics(ProcessesToUpdate::Some(pids), true, ProcessRefreshKind::nothing());
+        let alive: Vec<Pid> = pids.iter().copied().filter(|pid| system.process(*pid).is_some()).collect();
+        if alive.is_empty() || Instant::now() >= deadline {
+            return alive;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+// 结束所有名称匹配的进程：先请求正常退出，超时后强制结束，并等待进程退出释放文件
+pub fn kill_processes(name: &str, dict: &LangDict, sender: &EventSender) -> io::Result<()> {
+    let mut system = System::new();
+    let pids = find_processes(&mut system, name);
+    if pids.is_empty() {
+        log::info!("没有正在运行的进程: {}", name);
+        return Ok(());
+    }
+    emit(sender, UpdateEvent::Status(dict.status_killing_process(name, pids.len())));
+
+    // Windows上不支持发送SIGTERM，此时直接强制结束
+    let mut graceful = false;
+    for pid in &pids {
+        if let Some(process) = system.process(*pid) {
+            log::info!("正在结束进程: {:?} (PID {})", process.name(), pid);
+            graceful |= process.kill_with(Signal::Term).unwrap_or(false);
+        }
+    }
+    let alive = if graceful {
+        wait_for_exit(&mut system, &pids, GRACEFUL_TIMEOUT)
+    } else {
+        pids
+    };
+
+    for pid in &alive {
+        if let Some(process) = system.process(*pid) {
+            log::warn!("强制结束进程: {:?} (PID {})", process.name(), pid);
+            process.kill();
+        }
+    }
+    let remaining = wait_for_exit(&mut system, &alive, KILL_TIMEOUT);
+    if !remaining.is_empty() {
+        log::error!("进程未能在超时前退出: {:?}", remaining);
+        return Err(io::Error::new(io::ErrorKind::TimedOut, dict.error_kill_process_failed(name)));
+    }
+    log::info!("已结束所有 {} 进程", name);
+    Ok(())
+}
@@ -0,0 +1,31 @@
+// Windows上被其他进程占用的文件无法覆盖，新版本先写到旁边的暂存文件，登记为下次重启时替换原文件
+
+use std::ffi::OsStr;
+use std::io;
+use std::iter;
+use std::os::windows::ffi::OsStrExt;
+use std::path::Path;
+
+use windows_sys::Win32::Foundation::{ERROR_LOCK_VIOLATION, ERROR_SHARING_VIOLATION};
+use windows_sys::Win32::Storage::FileSystem::{MOVEFILE_DELAY_UNTIL_REBOOT, MOVEFILE_REPLACE_EXISTING, MoveFileExW};
+
+// 转换为以0结尾的UTF-16字符串
+fn wide(s: impl AsRef<OsStr>) -> Vec<u16> {
+    s.as_ref().encode_wide().chain(iter::once(0)).collect()
+}
+
+// 错误是否表示文件正被其他进程占用
+pub(crate) fn is_locked(e: &io::Error) -> bool {
+    e.raw_os_error().is_some_and(|code| code == ERROR_SHARING_VIOLATION as i32 || code == ERROR_LOCK_VIOLATION as i32)
+}
+
+// 登记在下次重启时用暂存文件替换目标文件，通常需要管理员权限。
+// 更新失败回滚时暂存文件会被删除，重启时找不到源文件的替换会被系统跳过
+pub(crate) fn replace_on_reboot(staged: &Path, dest: &Path) -> io::Result<()> {
+    let staged = wide(staged);
+    let dest = wide(dest);
+    if unsafe { MoveFileExW(staged.as_ptr(), dest.as_ptr(), MOVEFILE_REPLACE_EXISTING | MOVEFILE_DELAY_UNTIL_REBOOT) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
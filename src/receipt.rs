@@ -0,0 +1,61 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::Serialize;
+
+use crate::manifest::to_hex;
+use crate::update::sha256_file;
+
+// 写入目标目录的更新回执文件名，清理旧文件时不会被删除
+pub const RECEIPT_NAME: &str = "update-receipt.json";
+
+// 更新回执：记录本次安装的更新包和每个文件的大小及摘要，便于审计和之后的完整性检查
+#[derive(Serialize)]
+struct Receipt {
+    package: String,
+    version: Option<String>,
+    installed_at: String,
+    files: Vec<ReceiptFile>,
+}
+
+#[derive(Serialize)]
+struct ReceiptFile {
+    path: String,
+    size: u64,
+    sha256: String,
+}
+
+// 按目标目录中的最终内容计算已安装文件的大小和SHA-256，写入回执文件。
+// 回执先写入临时文件再重命名，不会留下不完整的回执
+pub fn write(target: &Path, mut installed: Vec<PathBuf>, package: &str, version: Option<&str>) -> io::Result<PathBuf> {
+    installed.sort();
+    let mut files = Vec::new();
+    for relative_path in installed {
+        let path = target.join(&relative_path);
+        // 按目标目录中的实际内容记录，不存在的文件不记录
+        if !path.is_file() {
+            continue;
+        }
+        files.push(ReceiptFile {
+            // 统一使用 / 作为分隔符，回执在各系统上格式相同
+            path: relative_path.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/"),
+            size: fs::metadata(&path)?.len(),
+            sha256: to_hex(&sha256_file(&path)?),
+        });
+    }
+
+    let receipt = Receipt {
+        package: Path::new(package).file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+        version: version.map(str::to_string),
+        installed_at: humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+        files,
+    };
+    let json = serde_json::to_string_pretty(&receipt).map_err(io::Error::other)?;
+    let receipt_path = target.join(RECEIPT_NAME);
+    let temp_path = target.join(format!("{}.tmp", RECEIPT_NAME));
+    fs::write(&temp_path, json)?;
+    fs::rename(&temp_path, &receipt_path)?;
+    Ok(receipt_path)
+}
@@ -0,0 +1,220 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tempfile::TempDir;
+
+use crate::update::create_temp_dir;
+
+// 回滚日志中的单条记录
+enum Entry {
+    // 更新前不存在的目录
+    CreatedDir(PathBuf),
+    // 更新前不存在的文件
+    CreatedFile(PathBuf),
+    // 被覆盖的文件及其备份位置
+    Replaced { original: PathBuf, backup: PathBuf },
+    // 被覆盖的符号链接及其原来指向的目标
+    #[cfg(unix)]
+    ReplacedLink { original: PathBuf, target: PathBuf },
+    // 原子替换模式下换上的新目录，以及原目录被重命名后的位置（更新前目标目录不存在时为None）
+    SwappedDir { target: PathBuf, backup: Option<PathBuf> },
+}
+
+// 记录复制过程中对目标目录的修改，失败时按相反顺序恢复
+pub struct Rollback {
+    backup_dir: TempDir,
+    entries: Mutex<Vec<Entry>>,
+    next_backup: AtomicUsize,
+}
+
+impl Rollback {
+    // 备份保存在 temp_dir 指定的目录中，未指定时使用系统临时目录
+    pub fn new(temp_dir: Option<&str>) -> io::Result<Self> {
+        Ok(Self {
+            backup_dir: create_temp_dir(temp_dir)?,
+            entries: Mutex::new(Vec::new()),
+            next_backup: AtomicUsize::new(0),
+        })
+    }
+
+    // 创建目录并记录其中原本不存在的各级目录
+    pub fn create_dir_all(&self, dir: &Path) -> io::Result<()> {
+        let missing: Vec<PathBuf> = dir.ancestors()
+            .take_while(|p| !p.as_os_str().is_empty() && !p.exists())
+            .map(Path::to_path_buf)
+            .collect();
+        let result = fs::create_dir_all(dir);
+        let mut entries = self.entries.lock().unwrap();
+        // 父目录先记录，恢复时子目录先删除；创建失败时也记录已经创建出来的部分
+        for created in missing.into_iter().rev().filter(|p| p.exists()) {
+            entries.push(Entry::CreatedDir(created));
+        }
+        result
+    }
+
+    // 在写入文件之前调用，已存在的文件先备份
+    pub fn record_file(&self, dest: &Path) -> io::Result<()> {
+        #[cfg(unix)]
+        if let Ok(target) = fs::read_link(dest) {
+            self.entries.lock().unwrap().push(Entry::ReplacedLink { original: dest.to_path_buf(), target });
+            return Ok(());
+        }
+        let entry = if dest.exists() {
+            let index = self.next_backup.fetch_add(1, Ordering::SeqCst);
+            let backup = self.backup_dir.path().join(index.to_string());
+            fs::copy(dest, &backup)?;
+            Entry::Replaced { original: dest.to_path_buf(), backup }
+        } else {
+            Entry::CreatedFile(dest.to_path_buf())
+        };
+        self.entries.lock().unwrap().push(entry);
+        Ok(())
+    }
+
+    // 在交换目录之后调用，原目录保留在 backup 中，直到 commit 或 restore
+    pub fn record_swap(&self, target: &Path, backup: Option<&Path>) {
+        self.entries.lock().unwrap().push(Entry::SwappedDir { target: target.to_path_buf(), backup: backup.map(Path::to_path_buf) });
+    }
+
+    // 全部修改都成功后调用：删除交换目录时保留的原目录，之后无法再恢复这些目录
+    pub fn commit(&self) {
+        for entry in self.entries.lock().unwrap().iter() {
+            if let Entry::SwappedDir { backup: Some(backup), .. } = entry && let Err(e) = fs::remove_dir_all(backup) {
+                log::warn!("删除旧版本目录失败: {:?}: {}", backup, e);
+            }
+        }
+    }
+
+    // 撤销所有已记录的修改，尽量恢复全部文件并返回第一个错误
+    pub fn restore(&self) -> io::Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+        let mut first_error = None;
+        while let Some(entry) = entries.pop() {
+            let result = match &entry {
+                Entry::CreatedFile(path) => match fs::remove_file(path) {
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+                    other => other,
+                },
+                Entry::CreatedDir(path) => {
+                    // 目录中仍有其他文件时保留目录
+                    let _ = fs::remove_dir(path);
+                    Ok(())
+                },
+                // 新版本可能在原位置写入了符号链接或硬链接，恢复前先删除该路径，避免写入链接指向的文件
+                Entry::Replaced { original, backup } => remove_existing(original).and_then(|_| fs::copy(backup, original)).map(|_| ()),
+                #[cfg(unix)]
+                Entry::ReplacedLink { original, target } => match fs::remove_file(original) {
+                    Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                    _ => std::os::unix::fs::symlink(target, original),
+                },
+                // 删除换上的新目录，再把原目录改回原来的名称
+                Entry::SwappedDir { target, backup } => match fs::remove_dir_all(target) {
+                    Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                    _ => backup.as_ref().map_or(Ok(()), |backup| fs::rename(backup, target)),
+                },
+            };
+            if let Err(e) = result {
+                log::error!("回滚失败: {}", e);
+                first_error.get_or_insert(e);
+            }
+        }
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+// 删除路径上现有的文件或链接本身（不跟随链接），路径不存在时忽略；目录保留，由复制时报告错误
+fn remove_existing(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if !metadata.is_dir() => fs::remove_file(path),
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 覆盖后被改为硬链接的两个文件回滚后各自恢复原内容，不再共享同一个inode
+    #[cfg(unix)]
+    #[test]
+    fn restore_breaks_hard_links() {
+        use std::os::unix::fs::MetadataExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a");
+        let b = dir.path().join("b");
+        fs::write(&a, "OLD-A").unwrap();
+        fs::write(&b, "OLD-B").unwrap();
+
+        let rollback = Rollback::new(None).unwrap();
+        rollback.record_file(&a).unwrap();
+        fs::write(&a, "NEW").unwrap();
+        rollback.record_file(&b).unwrap();
+        fs::remove_file(&b).unwrap();
+        fs::hard_link(&a, &b).unwrap();
+
+        rollback.restore().unwrap();
+        assert_eq!(fs::read_to_string(&a).unwrap(), "OLD-A");
+        assert_eq!(fs::read_to_string(&b).unwrap(), "OLD-B");
+        assert_ne!(fs::metadata(&a).unwrap().ino(), fs::metadata(&b).unwrap().ino());
+    }
+
+    // 新建的文件和目录被删除，被覆盖的文件恢复原内容
+    #[test]
+    fn restore_undoes_created_and_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("existing.txt");
+        fs::write(&existing, "old").unwrap();
+        let created_dir = dir.path().join("new/sub");
+
+        let rollback = Rollback::new(None).unwrap();
+        rollback.record_file(&existing).unwrap();
+        fs::write(&existing, "new").unwrap();
+        rollback.create_dir_all(&created_dir).unwrap();
+        let created = created_dir.join("file.txt");
+        rollback.record_file(&created).unwrap();
+        fs::write(&created, "new").unwrap();
+
+        rollback.restore().unwrap();
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "old");
+        assert!(!dir.path().join("new").exists());
+    }
+
+    // 回滚时删除换上的新目录并把原目录改回原来的名称，提交后删除原目录
+    #[test]
+    fn restore_and_commit_swapped_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("app");
+        let backup = dir.path().join("app.bak");
+        fs::create_dir(&backup).unwrap();
+        fs::write(backup.join("old.txt"), "old").unwrap();
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("new.txt"), "new").unwrap();
+        let created = dir.path().join("created");
+        fs::create_dir(&created).unwrap();
+
+        let rollback = Rollback::new(None).unwrap();
+        rollback.record_swap(&target, Some(&backup));
+        rollback.record_swap(&created, None);
+        rollback.restore().unwrap();
+        assert_eq!(fs::read_to_string(target.join("old.txt")).unwrap(), "old");
+        assert!(!target.join("new.txt").exists());
+        assert!(!backup.exists());
+        assert!(!created.exists());
+
+        let rollback = Rollback::new(None).unwrap();
+        fs::rename(&target, &backup).unwrap();
+        fs::create_dir(&target).unwrap();
+        rollback.record_swap(&target, Some(&backup));
+        rollback.commit();
+        assert!(!backup.exists());
+        assert!(target.is_dir());
+    }
+}
@@ -0,0 +1,86 @@
+use software_updater::language::{LangDict, get_dict};
+use software_updater::{UpdateConfig, check_free_space, check_package, check_targets_writable};
+
+use crate::config::Config;
+use crate::exit_code::ExitCode;
+use crate::fonts;
+
+// 单项检查的结论，警告不影响自检结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Warning,
+    Fail,
+}
+
+// 单项检查的名称、结论和说明
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn new(name: &'static str, result: Result<String, String>) -> Self {
+        match result {
+            Ok(detail) => Self { name, status: CheckStatus::Pass, detail },
+            Err(detail) => Self { name, status: CheckStatus::Fail, detail },
+        }
+    }
+}
+
+// 更新包是否存在且是有效的压缩包（或目录），以及 --expect-file 指定的文件是否存在
+pub fn check_package_valid(config: &UpdateConfig, dict: &LangDict) -> CheckResult {
+    CheckResult::new(dict.check_package, check_package(config).map(|_| config.package_path.clone()).map_err(|e| e.to_string()))
+}
+
+// 每个目标目录是否可以写入
+pub fn check_target_writable(config: &UpdateConfig, dict: &LangDict) -> CheckResult {
+    let targets = match &config.target_path {
+        Some(target) => target.clone(),
+        None => config.mappings.iter().map(|(_, target)| target.as_str()).collect::<Vec<_>>().join(", "),
+    };
+    CheckResult::new(dict.check_target_writable, check_targets_writable(config).map(|_| targets).map_err(|e| e.to_string()))
+}
+
+// 目标磁盘的剩余空间是否足以容纳更新包中的全部文件
+pub fn check_disk_space(config: &UpdateConfig, dict: &LangDict) -> CheckResult {
+    CheckResult::new(dict.check_disk_space, check_free_space(config).map(|needed| dict.check_space_needed(needed)).map_err(|e| e.to_string()))
+}
+
+// 是否找到了中文系统字体，找不到时界面使用内置的后备字体，只作为警告
+pub fn check_fonts(dict: &LangDict) -> CheckResult {
+    match fonts::system_font() {
+        Some(path) => CheckResult { name: dict.check_fonts, status: CheckStatus::Pass, detail: path.to_string() },
+        None => CheckResult { name: dict.check_fonts, status: CheckStatus::Warning, detail: dict.check_font_fallback.to_string() },
+    }
+}
+
+// 依次执行所有检查并输出结果，有检查失败时返回 Failed
+pub fn run(config: &Config) -> ExitCode {
+    let dict = get_dict(config.update.lang);
+    let results = [
+        check_package_valid(&config.update, dict),
+        check_target_writable(&config.update, dict),
+        check_disk_space(&config.update, dict),
+        check_fonts(dict),
+    ];
+
+    let mut report = format!("{}:\n", dict.self_test_title);
+    for result in &results {
+        let status = match result.status {
+            CheckStatus::Pass => dict.check_pass,
+            CheckStatus::Warning => dict.check_warning,
+            CheckStatus::Fail => dict.check_fail,
+        };
+        log::info!("自检 {}: {:?} {}", result.name, result.status, result.detail);
+        report.push_str(&format!("[{}] {}: {}\n", status, result.name, result.detail));
+    }
+    print!("{}", report);
+
+    let failed = results.iter().any(|result| result.status == CheckStatus::Fail);
+    // 程序没有控制台窗口，同时在消息框中显示结果
+    #[cfg(windows)]
+    crate::elevate::show_message(dict.title, &report, failed);
+    if failed { ExitCode::Failed } else { ExitCode::Success }
+}
@@ -0,0 +1,131 @@
+// 更新程序自身的两阶段替换流程：
+//
+// 1. Windows上更新时，目标目录中与当前运行程序同名的文件无法直接覆盖，
+//    因此新版本被写入为 `<exe>.new`（见 update.rs 中的 staged_destination，后缀可以用 --self-suffix 修改）。
+// 2. 下次启动时，在做任何其他事情之前检查 `<exe>.new`：
+//    先把正在运行的 `<exe>` 重命名为 `<exe>.old`（Windows 允许重命名运行中的程序，但不允许覆盖），
+//    再把 `<exe>.new` 移动为 `<exe>`，然后用相同的参数启动新版本并退出当前进程。
+//    新版本启动后删除遗留的 `<exe>.old`。
+//
+// Unix上可以删除正在运行的程序，更新时直接替换文件，不会产生 `<exe>.new`，启动时的检查不会做任何事。
+
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+// 删除旧版本时的重试次数，等待旧进程完全退出
+const CLEANUP_ATTEMPTS: u32 = 10;
+const CLEANUP_DELAY: Duration = Duration::from_millis(200);
+
+// 在路径末尾追加后缀
+pub fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+// 默认的暂存后缀
+pub const DEFAULT_SUFFIX: &str = ".new";
+
+// 暂存的新版本路径
+pub fn staged_path(exe: &Path, suffix: &str) -> PathBuf {
+    with_suffix(exe, suffix)
+}
+
+// 被替换下来的旧版本路径
+pub fn old_path(exe: &Path) -> PathBuf {
+    with_suffix(exe, ".old")
+}
+
+// 如果存在暂存的新版本，将其提升为正式的可执行文件，返回是否发生了替换
+pub fn promote(exe: &Path, suffix: &str) -> io::Result<bool> {
+    let staged = staged_path(exe, suffix);
+    if !staged.is_file() {
+        return Ok(false);
+    }
+
+    let old = old_path(exe);
+    if old.exists() {
+        fs::remove_file(&old)?;
+    }
+
+    fs::rename(exe, &old)?;
+    if let Err(e) = fs::rename(&staged, exe) {
+        // 移动失败时恢复旧版本，保证程序仍可运行
+        let _ = fs::rename(&old, exe);
+        return Err(e);
+    }
+    Ok(true)
+}
+
+// 删除上次替换遗留的旧版本
+fn cleanup_old(exe: &Path) {
+    let old = old_path(exe);
+    for _ in 0..CLEANUP_ATTEMPTS {
+        if !old.exists() {
+            return;
+        }
+        match fs::remove_file(&old) {
+            Ok(()) => {
+                log::info!("已删除旧版本: {:?}", old);
+                return;
+            },
+            Err(_) => thread::sleep(CLEANUP_DELAY),
+        }
+    }
+    log::warn!("无法删除旧版本: {:?}", old);
+}
+
+// 启动时调用：完成上次更新的自我替换并重新启动，返回true时调用方应立即退出。
+// suffix 必须与上次更新时使用的暂存后缀相同
+pub fn handoff(suffix: &str) -> io::Result<bool> {
+    let exe = env::current_exe()?;
+    if !promote(&exe, suffix)? {
+        cleanup_old(&exe);
+        return Ok(false);
+    }
+
+    log::info!("已使用暂存的新版本替换 {:?}，正在重新启动", exe);
+    let args: Vec<OsString> = env::args_os().skip(1).collect();
+    Command::new(&exe).args(args).spawn()?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn promote_replaces_exe_with_staged_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("app.exe");
+        fs::write(&exe, "v1").unwrap();
+        fs::write(old_path(&exe), "v0").unwrap();
+        fs::write(staged_path(&exe, DEFAULT_SUFFIX), "v2").unwrap();
+
+        assert!(promote(&exe, DEFAULT_SUFFIX).unwrap());
+        assert_eq!(fs::read_to_string(&exe).unwrap(), "v2");
+        assert_eq!(fs::read_to_string(old_path(&exe)).unwrap(), "v1");
+        assert!(!staged_path(&exe, DEFAULT_SUFFIX).exists());
+    }
+
+    #[test]
+    fn promote_without_staged_file_does_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("app.exe");
+        fs::write(&exe, "v1").unwrap();
+        // 使用其他后缀暂存的文件不会被提升
+        fs::write(staged_path(&exe, ".pending"), "v2").unwrap();
+
+        assert!(!promote(&exe, DEFAULT_SUFFIX).unwrap());
+        assert_eq!(fs::read_to_string(&exe).unwrap(), "v1");
+        assert!(!old_path(&exe).exists());
+        assert!(promote(&exe, ".pending").unwrap());
+        assert_eq!(fs::read_to_string(&exe).unwrap(), "v2");
+    }
+}
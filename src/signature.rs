@@ -0,0 +1,131 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use ed25519_dalek::{Signature, VerifyingKey};
+
+use crate::language::LangDict;
+
+// 编译时通过环境变量 UPDATER_PUBKEY 嵌入的受信任公钥（十六进制），设置后所有更新包都必须签名
+const EMBEDDED_PUBKEY: Option<&str> = option_env!("UPDATER_PUBKEY");
+
+// 解析十六进制字符串，忽略首尾空白
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim();
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| text.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+        .collect()
+}
+
+// 读取密钥或签名文件，支持原始字节和十六进制文本两种格式
+fn read_key_file<const N: usize>(path: &str) -> io::Result<Option<[u8; N]>> {
+    let bytes = fs::read(path)?;
+    if let Ok(bytes) = <[u8; N]>::try_from(bytes.as_slice()) {
+        return Ok(Some(bytes));
+    }
+    Ok(std::str::from_utf8(&bytes)
+        .ok()
+        .and_then(decode_hex)
+        .and_then(|bytes| <[u8; N]>::try_from(bytes).ok()))
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// 确定受信任的公钥：命令行指定的公钥文件优先于编译时嵌入的公钥
+fn trusted_key(pubkey_path: Option<&str>, dict: &LangDict) -> io::Result<Option<VerifyingKey>> {
+    let bytes = match (pubkey_path, EMBEDDED_PUBKEY) {
+        (Some(path), _) => read_key_file::<32>(path)?.ok_or_else(|| invalid(dict.error_invalid_pubkey(path)))?,
+        (None, Some(hex)) => decode_hex(hex)
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .ok_or_else(|| invalid(dict.error_invalid_pubkey("UPDATER_PUBKEY")))?,
+        (None, None) => return Ok(None),
+    };
+    VerifyingKey::from_bytes(&bytes)
+        .map(Some)
+        .map_err(|_| invalid(dict.error_invalid_pubkey(pubkey_path.unwrap_or("UPDATER_PUBKEY"))))
+}
+
+// 在解压前使用ed25519公钥验证更新包的分离签名，未配置公钥和签名时跳过
+pub fn verify_package(package_path: &str, pubkey_path: Option<&str>, sig_path: Option<&str>, dict: &LangDict) -> io::Result<()> {
+    let key = trusted_key(pubkey_path, dict)?;
+    let (key, sig_path) = match (key, sig_path) {
+        (None, None) => return Ok(()),
+        (None, Some(_)) => return Err(io::Error::new(io::ErrorKind::InvalidInput, dict.error_no_pubkey)),
+        (Some(_), None) => return Err(invalid(dict.error_signature_missing.to_string())),
+        (Some(key), Some(sig_path)) => (key, sig_path),
+    };
+
+    // 签名针对的是压缩包文件，目录无法验证
+    if Path::new(package_path).is_dir() {
+        return Err(invalid(dict.error_directory_unsigned.to_string()));
+    }
+    let signature = read_key_file::<64>(sig_path)?
+        .map(|bytes| Signature::from_bytes(&bytes))
+        .ok_or_else(|| invalid(dict.error_invalid_signature_file(sig_path)))?;
+    let package = fs::read(Path::new(package_path))?;
+    key.verify_strict(&package, &signature)
+        .map_err(|_| invalid(dict.error_signature_mismatch.to_string()))?;
+    log::info!("更新包签名验证通过: {}", sig_path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::{Language, get_dict};
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    #[test]
+    fn decodes_hex_text() {
+        assert_eq!(decode_hex(" 00ff1A\n"), Some(vec![0x00, 0xff, 0x1a]));
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("zz"), None);
+        assert_eq!(decode_hex(""), Some(Vec::new()));
+    }
+
+    #[test]
+    fn reads_raw_and_hex_key_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let raw = dir.path().join("key.bin");
+        let text = dir.path().join("key.hex");
+        let wrong = dir.path().join("short.hex");
+        fs::write(&raw, [5u8; 32]).unwrap();
+        fs::write(&text, format!("{}\n", hex(&[5u8; 32]))).unwrap();
+        fs::write(&wrong, hex(&[5u8; 8])).unwrap();
+        assert_eq!(read_key_file::<32>(raw.to_str().unwrap()).unwrap(), Some([5u8; 32]));
+        assert_eq!(read_key_file::<32>(text.to_str().unwrap()).unwrap(), Some([5u8; 32]));
+        assert_eq!(read_key_file::<32>(wrong.to_str().unwrap()).unwrap(), None);
+    }
+
+    #[test]
+    fn verifies_signed_package_and_rejects_tampered_one() {
+        let dict = get_dict(Language::English);
+        let dir = tempfile::tempdir().unwrap();
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let package = dir.path().join("update.zip");
+        let pubkey = dir.path().join("key.pub");
+        let sig = dir.path().join("update.zip.sig");
+        fs::write(&package, b"package contents").unwrap();
+        fs::write(&pubkey, hex(signing_key.verifying_key().as_bytes())).unwrap();
+        fs::write(&sig, signing_key.sign(b"package contents").to_bytes()).unwrap();
+        let (package, pubkey, sig) = (package.to_str().unwrap(), pubkey.to_str().unwrap(), sig.to_str().unwrap());
+
+        verify_package(package, Some(pubkey), Some(sig), dict).unwrap();
+
+        fs::write(package, b"package contents!").unwrap();
+        let err = verify_package(package, Some(pubkey), Some(sig), dict).unwrap_err();
+        assert_eq!(err.to_string(), dict.error_signature_mismatch);
+        let err = verify_package(package, Some(pubkey), None, dict).unwrap_err();
+        assert_eq!(err.to_string(), dict.error_signature_missing);
+    }
+}
@@ -0,0 +1,106 @@
+use egui::Ui;
+use software_updater::check_package;
+use software_updater::language::{Language, get_dict};
+
+use crate::config::{Config, parse_args};
+use crate::theme::Palette;
+
+// 不带参数启动时显示的表单：选择更新包、目标目录和界面语言，点击开始后按对应的命令行参数开始更新
+pub struct StartForm {
+    package: String,
+    target: String,
+    lang: Language,
+    error: Option<String>,
+}
+
+impl StartForm {
+    pub fn new(lang: Language) -> Self {
+        Self { package: String::new(), target: String::new(), lang, error: None }
+    }
+
+    // 表单中选择的语言，用于界面文本和窗口标题
+    pub fn lang(&self) -> Language {
+        self.lang
+    }
+
+    // 表单内容对应的命令行参数，目标目录为空时由更新包中的 update.json 或 target.txt 指定
+    fn args(&self) -> Vec<String> {
+        let mut args = vec!["--package".to_string(), self.package.trim().to_string()];
+        if !self.target.trim().is_empty() {
+            args.extend(["--target".to_string(), self.target.trim().to_string()]);
+        }
+        args.extend(["--lang".to_string(), self.lang.code().to_string()]);
+        args
+    }
+
+    // 按表单内容解析参数并检查更新包，有效时返回配置，否则在表单中显示错误
+    fn submit(&mut self) -> Option<Config> {
+        let args = self.args();
+        log::info!("从启动表单开始更新: {:?}", args);
+        let result = parse_args(&args).and_then(|config| match check_package(&config.update) {
+            Ok(()) => Ok(config),
+            Err(err) => Err(err.to_string()),
+        });
+        match result {
+            Ok(config) => Some(config),
+            Err(err) => {
+                log::error!("启动表单中的参数无效: {}", err);
+                self.error = Some(err);
+                None
+            },
+        }
+    }
+
+    // 显示表单，用户点击开始且参数有效时返回配置
+    pub fn show(&mut self, ui: &mut Ui, palette: &Palette) -> Option<Config> {
+        let dict = get_dict(self.lang);
+        let mut config = None;
+        ui.vertical_centered(|ui| {
+            ui.label(egui::RichText::new(dict.title).font(egui::FontId::proportional(24.0)).color(palette.title));
+        });
+        ui.add_space(10.0);
+
+        egui::Grid::new("start_form").num_columns(3).spacing([8.0, 8.0]).show(ui, |ui| {
+            let field_width = (ui.available_width() - 160.0).max(120.0);
+
+            ui.label(dict.form_package);
+            ui.add(egui::TextEdit::singleline(&mut self.package).desired_width(field_width));
+            // 更新包也可以是已解压的目录，此时直接在输入框中粘贴目录路径
+            if ui.button(dict.button_browse).clicked() && let Some(path) = rfd::FileDialog::new().add_filter("ZIP / tar.gz", &["zip", "tar.gz", "tgz"]).pick_file() {
+                self.package = path.display().to_string();
+            }
+            ui.end_row();
+
+            ui.label(dict.form_target);
+            ui.add(egui::TextEdit::singleline(&mut self.target).desired_width(field_width));
+            if ui.button(dict.button_browse).clicked() && let Some(path) = rfd::FileDialog::new().pick_folder() {
+                self.target = path.display().to_string();
+            }
+            ui.end_row();
+
+            ui.label(dict.form_language);
+            egui::ComboBox::from_id_source("start_form_language")
+                .selected_text(self.lang.native_name())
+                .show_ui(ui, |ui| {
+                    for lang in Language::ALL {
+                        ui.selectable_value(&mut self.lang, lang, lang.native_name());
+                    }
+                });
+            ui.end_row();
+        });
+
+        if let Some(err) = &self.error {
+            ui.add_space(5.0);
+            ui.label(egui::RichText::new(err).font(egui::FontId::proportional(12.0)).color(palette.error));
+        }
+
+        ui.add_space(10.0);
+        ui.vertical_centered(|ui| {
+            let start = egui::Button::new(dict.button_start).min_size(egui::Vec2::new(80.0, 30.0));
+            if ui.add_enabled(!self.package.trim().is_empty(), start).clicked() {
+                config = self.submit();
+            }
+        });
+        config
+    }
+}
@@ -0,0 +1,85 @@
+// 测试用的辅助函数：在临时目录中构造更新包，执行更新并读取结果目录
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use walkdir::WalkDir;
+use zip::write::FileOptions;
+
+use crate::language::Language;
+use crate::{UpdateConfig, UpdateError, UpdateEvent, UpdateSummary, run_update};
+
+// 按顺序写入ZIP更新包，名称以 / 结尾的条目为目录
+pub(crate) fn write_zip(path: &Path, entries: &[(&str, &[u8])]) {
+    write_zip_with(path, entries, FileOptions::default());
+}
+
+pub(crate) fn write_zip_with(path: &Path, entries: &[(&str, &[u8])], options: FileOptions) {
+    let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+    for (name, content) in entries {
+        if name.ends_with('/') {
+            zip.add_directory(*name, options).unwrap();
+        } else {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(content).unwrap();
+        }
+    }
+    zip.finish().unwrap();
+}
+
+// 按顺序写入tar.gz更新包，名称以 / 结尾的条目为目录
+pub(crate) fn write_tar_gz(path: &Path, entries: &[(&str, &[u8])]) {
+    let mut tar = tar::Builder::new(GzEncoder::new(fs::File::create(path).unwrap(), Compression::default()));
+    for (name, content) in entries {
+        let mut header = tar::Header::new_gnu();
+        if name.ends_with('/') {
+            header.set_entry_type(tar::EntryType::Directory);
+            header.set_mode(0o755);
+        } else {
+            header.set_mode(0o644);
+        }
+        header.set_size(content.len() as u64);
+        tar.append_data(&mut header, name, *content).unwrap();
+    }
+    tar.into_inner().unwrap().finish().unwrap();
+}
+
+// 在目录下写入文件，自动创建上级目录
+pub(crate) fn write_files(root: &Path, files: &[(&str, &[u8])]) {
+    for (name, content) in files {
+        let path = root.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+}
+
+// 目录下全部文件的相对路径（使用 / 分隔）和内容
+pub(crate) fn read_tree(root: &Path) -> BTreeMap<String, Vec<u8>> {
+    WalkDir::new(root).into_iter().map(|entry| entry.unwrap()).filter(|entry| entry.file_type().is_file())
+        .map(|entry| {
+            let relative = entry.path().strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/");
+            (relative, fs::read(entry.path()).unwrap())
+        })
+        .collect()
+}
+
+// 把更新包复制到目标目录的最小配置，保留更新包以便测试重复使用
+pub(crate) fn config(package: &Path, target: &Path) -> UpdateConfig {
+    UpdateConfig {
+        package_path: package.display().to_string(),
+        target_path: Some(target.display().to_string()),
+        lang: Language::English,
+        keep_package: true,
+        ..Default::default()
+    }
+}
+
+// 执行更新并收集全部事件
+pub(crate) fn run(config: &UpdateConfig) -> (Result<UpdateSummary, UpdateError>, Vec<UpdateEvent>) {
+    let mut events = Vec::new();
+    let result = run_update(config, |event| events.push(event));
+    (result, events)
+}
@@ -0,0 +1,72 @@
+use egui::{Color32, Context, Visuals};
+
+// 界面主题
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Theme {
+    Light,
+    Dark,
+    // 跟随系统的深色/浅色设置
+    System,
+}
+
+// 解析主题名称
+pub fn parse_theme(value: &str) -> Option<Theme> {
+    match value.to_lowercase().as_str() {
+        "light" => Some(Theme::Light),
+        "dark" => Some(Theme::Dark),
+        "system" => Some(Theme::System),
+        _ => None,
+    }
+}
+
+// 各主题下仍然清晰可读的强调色
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    pub title: Color32,
+    pub muted: Color32,
+    pub success: Color32,
+    pub warning: Color32,
+    pub error: Color32,
+}
+
+const LIGHT_PALETTE: Palette = Palette {
+    title: Color32::from_rgb(0, 120, 212),
+    muted: Color32::GRAY,
+    success: Color32::GREEN,
+    warning: Color32::from_rgb(200, 120, 0),
+    error: Color32::RED,
+};
+
+const DARK_PALETTE: Palette = Palette {
+    title: Color32::from_rgb(96, 180, 255),
+    muted: Color32::from_rgb(170, 170, 170),
+    success: Color32::from_rgb(110, 220, 110),
+    warning: Color32::from_rgb(255, 190, 80),
+    error: Color32::from_rgb(255, 110, 110),
+};
+
+// 判断是否使用深色主题，无法检测系统设置时使用浅色主题
+fn is_dark(theme: Theme) -> bool {
+    match theme {
+        Theme::Light => false,
+        Theme::Dark => true,
+        Theme::System => match dark_light::detect() {
+            Ok(mode) => mode == dark_light::Mode::Dark,
+            Err(e) => {
+                log::warn!("无法检测系统主题，使用浅色主题: {}", e);
+                false
+            }
+        },
+    }
+}
+
+// 应用主题并返回对应的强调色，只需在创建窗口时调用一次
+pub fn apply(ctx: &Context, theme: Theme) -> Palette {
+    if is_dark(theme) {
+        ctx.set_visuals(Visuals::dark());
+        DARK_PALETTE
+    } else {
+        ctx.set_visuals(Visuals::light());
+        LIGHT_PALETTE
+    }
+}
@@ -0,0 +1,3185 @@
+use std::cell::Cell;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, mpsc};
+use std::thread;
+use std::time::{Duration, Instant};
+use filetime::FileTime;
+use tempfile::{TempDir, tempdir};
+use walkdir::WalkDir;
+use zip::ZipArchive;
+use zip::result::ZipError;
+use rayon::prelude::*;
+use flate2::read::GzDecoder;
+use sha2::{Digest, Sha256};
+
+use crate::audit::{self, Audit};
+use crate::checkpoint::{self, Checkpoint};
+use crate::conflict::Conflicts;
+use crate::dedup::Dedup;
+use crate::filter::{self, FileFilter};
+use crate::language::LangDict;
+use crate::rollback::Rollback;
+use crate::watchdog::Watchdog;
+use crate::{FailureKind, InnerPath, Inspection, PACKAGE_METADATA_NAME, PACKAGE_TARGET_NAME, PackageEntry, PackageFormat, Phase, PlannedAction, UpdateConfig, UpdateError, UpdateEvent, UpdateSummary, delta, hooks, logging, long_path, locks, manifest, process, receipt, self_update, signature};
+#[cfg(windows)]
+use crate::reboot;
+
+// 将相对路径拼接到根目录下，路径为绝对路径或包含..时返回None，保证结果不会超出根目录
+pub(crate) fn contained_path(root: &Path, relative_path: &Path) -> Option<PathBuf> {
+    let mut path = root.to_path_buf();
+    for component in relative_path.components() {
+        match component {
+            std::path::Component::Normal(part) => path.push(part),
+            std::path::Component::CurDir => {},
+            _ => return None,
+        }
+    }
+    path.starts_with(root).then_some(path)
+}
+
+// 继续执行模式下复制或删除失败的文件（相对路径）及其错误
+type Failures = Vec<(PathBuf, io::Error)>;
+
+// 更新失败的类别及错误
+type Failed = (FailureKind, io::Error);
+
+// 把错误归入指定的失败类别，用于 map_err
+fn failed(kind: FailureKind) -> impl FnOnce(io::Error) -> Failed {
+    move |e| (kind, e)
+}
+
+// 一次更新的事件发送端，记录接收端是否已经断开，用于每次更新只记录一次断开的日志
+pub(crate) struct EventSender {
+    sender: mpsc::Sender<UpdateEvent>,
+    receiver_gone: AtomicBool,
+}
+
+impl EventSender {
+    pub fn new(sender: mpsc::Sender<UpdateEvent>) -> Self {
+        EventSender { sender, receiver_gone: AtomicBool::new(false) }
+    }
+}
+
+// 发送更新事件，返回接收端是否仍然存在。接收端已断开（如调用方提前关闭了窗口）时只记录一次日志，
+// 更新照常进行到结束或回滚，不会让目标目录停留在更新到一半的状态
+pub(crate) fn emit(sender: &EventSender, event: UpdateEvent) -> bool {
+    if sender.sender.send(event).is_ok() {
+        return true;
+    }
+    if !sender.receiver_gone.swap(true, Ordering::SeqCst) {
+        log::warn!("事件接收端已断开，继续执行更新但不再发送进度");
+    }
+    false
+}
+
+// 执行更新操作，过程和结果通过事件发送，同时返回统计或失败原因
+pub(crate) fn perform_update(config: &UpdateConfig, dict: &'static LangDict, sender: mpsc::Sender<UpdateEvent>) -> Result<UpdateSummary, UpdateError> {
+    let package_path = &config.package_path;
+    let start = Instant::now();
+    
+    // 指定了超时时间时由监视线程检查进度，更新流程结束后通知监视线程退出
+    let watchdog = Watchdog::new(config.timeout);
+    let sender = EventSender::new(sender);
+    let done = AtomicBool::new(false);
+    let result = thread::scope(|scope| {
+        scope.spawn(|| watchdog.watch(dict, &sender, &done));
+        let result = actual_perform_update(config, dict, &watchdog, &sender);
+        done.store(true, Ordering::SeqCst);
+        result
+    });
+    match result {
+        Ok(summary) if config.dry_run => {
+            if let Some(command) = &config.post_hook {
+                log::info!("演练模式下跳过后处理钩子: {}", command);
+            }
+            log::info!("演练完成，未修改目标目录");
+            Ok(UpdateSummary { duration: start.elapsed(), ..summary })
+        },
+        Ok(summary) if !summary.failed_files.is_empty() => {
+            // 部分文件失败时不执行后处理钩子，并保留源zip文件以便重新执行
+            log::error!("更新部分完成，{} 个文件失败", summary.failed_files.len());
+            for (path, e) in &summary.failed_files {
+                log::error!("  {:?}: {}", path, e);
+            }
+            emit(&sender, UpdateEvent::PartialComplete(summary.failed_files.clone()));
+            Ok(UpdateSummary { duration: start.elapsed(), ..summary })
+        },
+        Ok(summary) => {
+            // 文件复制完成后执行后处理钩子，失败时保留源zip文件以便重新执行
+            if let Some(command) = &config.post_hook {
+                emit(&sender, UpdateEvent::Status(dict.status_running_hook(command)));
+                let target_dir = update_targets(config).into_iter().next().map(|(_, target)| target).unwrap_or_default();
+                if let Err(e) = hooks::run(command, &target_dir, dict) {
+                    log::error!("后处理钩子执行失败: {}", e);
+                    emit(&sender, UpdateEvent::Error(FailureKind::Other, e.to_string()));
+                    return Err(UpdateError::Other(e.to_string()));
+                }
+            }
+            log::info!("更新完成！共复制 {} 个文件（{} 字节），跳过 {} 个，删除 {} 个",
+                summary.files_copied, summary.bytes_copied, summary.files_skipped, summary.files_deleted);
+            
+            // 更新完成后，删除源zip文件，删除失败时不影响更新结果，只提示用户；更新源是目录时不删除
+            if config.keep_package {
+                log::info!("保留源zip文件: {}", package_path);
+            } else if Path::new(package_path).is_dir() {
+                log::info!("更新源是目录，不删除: {}", package_path);
+            } else if let Err(e) = std::fs::remove_file(package_path) {
+                log::error!("删除源zip文件失败: {}", e);
+                emit(&sender, UpdateEvent::Warning(dict.warning_package_not_removed(package_path, &e.to_string())));
+            } else {
+                log::info!("已成功删除源zip文件: {}", package_path);
+            }
+            
+            // 发送完成消息
+            emit(&sender, UpdateEvent::Complete);
+            Ok(UpdateSummary { duration: start.elapsed(), ..summary })
+        },
+        Err((kind, e)) => {
+            let error_msg = e.to_string();
+            log::error!("更新失败: {}", error_msg);
+            // 超时中止时无论在哪一步失败都归为取消
+            let kind = if watchdog.expired() { FailureKind::Cancelled } else { kind };
+            emit(&sender, UpdateEvent::Error(kind, error_msg.clone()));
+            Err(UpdateError::new(kind, error_msg))
+        }
+    }
+}
+
+// 读取更新包根目录下 update.json 中声明的版本，用于写入回执
+fn package_version(temp_path: &Path) -> Option<String> {
+    let text = fs::read_to_string(temp_path.join(PACKAGE_METADATA_NAME)).ok()?;
+    let metadata: serde_json::Value = serde_json::from_str(&text).ok()?;
+    metadata.get("version")?.as_str().map(str::to_string)
+}
+
+// 按文件开头的魔数检测更新包格式，无法识别时按扩展名判断；目录总是按目录处理，无法判断时返回None
+pub(crate) fn detect_format(package_path: &Path) -> Option<PackageFormat> {
+    if package_path.is_dir() {
+        return Some(PackageFormat::Dir);
+    }
+    let mut magic = [0; 4];
+    if let Ok(mut file) = fs::File::open(package_path) && file.read_exact(&mut magic).is_ok() {
+        // ZIP文件以本地文件头或（空压缩包的）中央目录结束记录开头，gzip以1F 8B开头
+        if magic.starts_with(b"PK") {
+            return Some(PackageFormat::Zip);
+        }
+        if magic.starts_with(&[0x1f, 0x8b]) {
+            return Some(PackageFormat::TarGz);
+        }
+    }
+    let name = package_path.file_name()?.to_string_lossy().to_lowercase();
+    if name.ends_with(".zip") {
+        Some(PackageFormat::Zip)
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(PackageFormat::TarGz)
+    } else {
+        None
+    }
+}
+
+// 更新包的格式及与指定格式不一致时检测到的格式：指定了 --format 时总是使用指定的格式，否则使用检测结果，无法检测时按ZIP处理
+pub(crate) fn package_format(config: &UpdateConfig) -> (PackageFormat, Option<PackageFormat>) {
+    resolve_format(Path::new(&config.package_path), config.format)
+}
+
+fn resolve_format(package_path: &Path, format: Option<PackageFormat>) -> (PackageFormat, Option<PackageFormat>) {
+    let detected = detect_format(package_path);
+    match format {
+        Some(format) => (format, detected.filter(|detected| *detected != format)),
+        None => (detected.unwrap_or(PackageFormat::Zip), None),
+    }
+}
+
+// 按与更新流程相同的格式判断读取更新包中指定相对路径的文本文件，不解压其他条目；
+// 更新包无法按该格式打开或没有该文件时返回None（更新包的错误留给更新流程报告），读取或解密该文件失败时返回错误
+pub(crate) fn read_package_file(package_path: &Path, format: Option<PackageFormat>, password: Option<&[u8]>, name: &str) -> io::Result<Option<String>> {
+    let (format, _) = resolve_format(package_path, format);
+    if format == PackageFormat::Dir {
+        return Ok(if package_path.is_dir() { fs::read_to_string(package_path.join(name)).ok() } else { None });
+    }
+    let Ok(file) = fs::File::open(package_path) else {
+        return Ok(None);
+    };
+    let mut text = String::new();
+    if format == PackageFormat::TarGz {
+        let mut archive = tar::Archive::new(GzDecoder::new(file));
+        let Ok(entries) = archive.entries() else {
+            return Ok(None);
+        };
+        for entry in entries {
+            let Ok(mut entry) = entry else {
+                return Ok(None);
+            };
+            if entry.header().entry_type().is_file() && tar_entry_path(&entry)? == Path::new(name) {
+                entry.read_to_string(&mut text)?;
+                return Ok(Some(text));
+            }
+        }
+        return Ok(None);
+    }
+    let Ok(mut archive) = ZipArchive::new(file) else {
+        return Ok(None);
+    };
+    let entry = match password {
+        Some(password) => archive.by_name_decrypt(name, password).map(|entry| entry.map_err(io::Error::other)),
+        None => archive.by_name(name).map(Ok),
+    };
+    let mut entry = match entry {
+        Ok(entry) => entry?,
+        Err(ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    entry.read_to_string(&mut text)?;
+    Ok(Some(text))
+}
+
+// 检查更新包是否存在，并确认是指定格式的有效文件：ZIP读取中央目录，tar.gz读取第一个条目；更新包也可以是已经解压好的目录
+pub(crate) fn check_package(config: &UpdateConfig, dict: &LangDict) -> Result<(), Failed> {
+    let package_path = config.package_path.as_str();
+    let (format, _) = package_format(config);
+    if format == PackageFormat::Dir && Path::new(package_path).is_dir() {
+        return Ok(());
+    }
+    if !Path::new(package_path).exists() {
+        return Err((FailureKind::PackageMissing, io::Error::new(io::ErrorKind::NotFound, dict.error_package_not_found(package_path))));
+    }
+    let invalid = |message: String| (FailureKind::Extraction, io::Error::new(io::ErrorKind::InvalidData, message));
+    match format {
+        PackageFormat::Dir => Err((FailureKind::InvalidConfig, io::Error::new(io::ErrorKind::InvalidInput, dict.error_package_not_directory(package_path)))),
+        PackageFormat::Zip => {
+            let file = fs::File::open(package_path).map_err(failed(FailureKind::PackageMissing))?;
+            ZipArchive::new(file).map(drop).map_err(|e| invalid(dict.error_invalid_package(&e.to_string())))
+        },
+        PackageFormat::TarGz => {
+            let file = fs::File::open(package_path).map_err(failed(FailureKind::PackageMissing))?;
+            let mut archive = tar::Archive::new(GzDecoder::new(file));
+            archive.entries().and_then(|mut entries| entries.next().transpose()).map(drop).map_err(|e| invalid(dict.error_invalid_tar_package(&e.to_string())))
+        },
+    }
+}
+
+// 实际执行更新操作的内部函数，返回不含用时的统计，失败时返回错误及其类别
+fn actual_perform_update(config: &UpdateConfig, dict: &'static LangDict, watchdog: &Watchdog, sender: &EventSender) -> Result<UpdateSummary, Failed> {
+    let package_path = config.package_path.as_str();
+    
+    // 检查必要参数
+    if package_path.is_empty() {
+        return Err((FailureKind::InvalidConfig, io::Error::new(io::ErrorKind::InvalidInput, dict.error_no_package)));
+    }
+    
+    // 必须提供目标路径或目录映射
+    let targets = update_targets(config);
+    if targets.is_empty() {
+        return Err((FailureKind::InvalidConfig, io::Error::new(io::ErrorKind::InvalidInput, dict.error_no_target)));
+    }
+    for (inner, target) in &targets {
+        log::info!("目标目录: {:?} -> {:?}", inner, target);
+    }
+    
+    // 指定的格式与文件内容不一致时只警告，仍按指定的格式处理
+    let (format, detected) = package_format(config);
+    if let Some(detected) = detected {
+        log::warn!("指定的更新包格式为 {}，但检测到的格式为 {}", format.name(), detected.name());
+        emit(sender, UpdateEvent::Warning(dict.warning_format_mismatch(format.name(), detected.name())));
+    }
+    
+    // 更新包不存在或不是有效的压缩包时不再继续，与签名验证等其他失败区分开
+    check_package(config, dict)?;
+    check_expected_files(config, dict)?;
+    
+    // 解压前确认可以写入目标目录，避免等到复制时才因只读或权限不足失败（演练模式不写入任何文件）
+    if !config.dry_run {
+        for (_, target) in &targets {
+            check_writable(target, dict).map_err(failed(FailureKind::Copy))?;
+        }
+    }
+    
+    // 获取当前可执行文件路径
+    let exe_path = env::current_exe().map_err(failed(FailureKind::Other))?;
+    let running_exe = running_exe(config, &exe_path);
+    
+    // 钩子命令在第一个目标目录中执行
+    let current_dir = targets[0].1.clone();
+    
+    // 安装到子目录时每个映射的目标目录都改为其下的子目录
+    let targets = match &config.target_subdir {
+        Some(subdir) => targets.into_iter().map(|(inner, target)| {
+            contained_path(&target, Path::new(subdir))
+                .map(|target| (inner, target))
+                .ok_or_else(|| (FailureKind::InvalidConfig, io::Error::new(io::ErrorKind::InvalidInput, dict.error_invalid_target_subdir(subdir))))
+        }).collect::<Result<Vec<_>, _>>()?,
+        None => targets,
+    };
+    
+    // 解压前验证更新包签名，验证失败时中止更新
+    if config.pubkey_path.is_some() || config.sig_path.is_some() {
+        emit(sender, UpdateEvent::Status(dict.status_verifying_signature.to_string()));
+    }
+    signature::verify_package(package_path, config.pubkey_path.as_deref(), config.sig_path.as_deref(), dict)
+        .map_err(failed(FailureKind::Verification))?;
+    
+    // 解压前执行预处理钩子，失败时中止更新
+    if let Some(command) = &config.pre_hook {
+        if config.dry_run {
+            log::info!("演练模式下跳过预处理钩子: {}", command);
+        } else {
+            emit(sender, UpdateEvent::Status(dict.status_running_hook(command)));
+            hooks::run(command, &current_dir, dict).map_err(failed(FailureKind::Other))?;
+        }
+    }
+    
+    // 创建临时目录并解压更新包，压缩包内的路径可能很深，Windows上使用长路径
+    let temp_dir = create_temp_dir(config.temp_dir.as_deref()).map_err(failed(FailureKind::Extraction))?;
+    let report_extract = |progress: Progress| {
+        // 超时时中止解压
+        watchdog.progress(dict)?;
+        if let Progress::Entry(current, total, _) = &progress && *total > 0 {
+            emit(sender, UpdateEvent::PhaseProgress { phase: Phase::Extract, fraction: *current as f32 / *total as f32 });
+        }
+        emit(sender, progress.into());
+        Ok(())
+    };
+    let package_dir = format == PackageFormat::Dir;
+    let prefixes = extract_prefixes(&targets);
+    let is_wanted = |entry: &Path| entry == Path::new(PACKAGE_METADATA_NAME) || prefixes.as_ref().is_none_or(|prefixes| prefixes.iter().any(|prefix| entry.starts_with(prefix)));
+    // 按压缩包中记录的CRC32与目标文件比较，内容相同的条目既不解压也不复制
+    let mut unchanged_entries = HashSet::new();
+    let temp_path = &if package_dir && !config.delta {
+        // 更新包是目录时直接从该目录复制，跳过解压
+        log::info!("更新包是目录，直接从中复制文件: {}", package_path);
+        emit(sender, UpdateEvent::PhaseProgress { phase: Phase::Extract, fraction: 1.0 });
+        long_path::extend(Path::new(package_path))
+    } else if package_dir {
+        // 增量更新会在原处应用补丁，先把目录复制到临时目录，不修改更新源
+        log::info!("更新包是目录，复制到临时目录后应用补丁: {}", package_path);
+        let temp_path = long_path::extend(temp_dir.path());
+        emit(sender, UpdateEvent::Status(dict.status_extracting.to_string()));
+        let stats = copy_tree(Path::new(package_path), &temp_path, config.preserve_mtime, report_extract)
+            .map_err(|e| temp_space_exhausted(e, &temp_path, dict))
+            .map_err(failed(FailureKind::Extraction))?;
+        log::info!("已复制 {} 个文件，共 {} 字节", stats.files, stats.bytes);
+        temp_path
+    } else if format == PackageFormat::TarGz {
+        let temp_path = long_path::extend(temp_dir.path());
+        log::info!("正在解压tar.gz更新包: {}", package_path);
+        emit(sender, UpdateEvent::Status(dict.status_extracting.to_string()));
+        let stats = fs::File::open(package_path)
+            .and_then(|file| {
+                let size = file.metadata()?.len();
+                extract_tar_gz(file, size, &temp_path, config.preserve_mtime, is_wanted, report_extract)
+            })
+            .map_err(|e| temp_space_exhausted(e, &temp_path, dict))
+            .map_err(failed(FailureKind::Extraction))?;
+        log::info!("已解压 {} 个文件，共 {} 字节", stats.files, stats.bytes);
+        if stats.skipped > 0 {
+            log::warn!("共跳过 {} 个不安全的压缩包条目", stats.skipped);
+            emit(sender, UpdateEvent::Warning(dict.warning_unsafe_entries(stats.skipped)));
+        }
+        temp_path
+    } else {
+        let temp_path = long_path::extend(temp_dir.path());
+        log::info!("正在解压更新包: {}", package_path);
+        emit(sender, UpdateEvent::Status(dict.status_extracting.to_string()));
+        let password = config.password.as_deref().map(str::as_bytes);
+        let crc_targets = crc_skip_targets(config, &targets);
+        let is_unchanged = |entry: &Path, crc: u32, size: u64| crc_targets.as_ref().is_some_and(|targets| entry_unchanged(targets, entry, crc, size));
+        let stats = extract_to(Path::new(package_path), &temp_path, password, config.preserve_mtime, is_wanted, is_unchanged, report_extract)
+            .map_err(|e| match e.get_ref().and_then(|e| e.downcast_ref::<PasswordError>()) {
+                // 将密码错误转换为本地化的错误信息
+                Some(PasswordError::Required) => io::Error::new(io::ErrorKind::InvalidInput, dict.error_password_required),
+                Some(PasswordError::Invalid) => io::Error::new(io::ErrorKind::InvalidInput, dict.error_password_invalid),
+                None => temp_space_exhausted(e, &temp_path, dict),
+            }).map_err(failed(FailureKind::Extraction))?;
+        log::info!("已解压 {} 个文件，共 {} 字节", stats.files, stats.bytes);
+        // 按UTC还原的DOS时间与实际时间相差打包时所在的时区，比较修改时间的结果可能不准确
+        if config.skip_newer && stats.local_times > 0 {
+            log::warn!("{} 个文件没有扩展时间戳，修改时间按UTC处理，--skip-newer 的比较可能受时区影响", stats.local_times);
+        }
+        if !stats.unchanged.is_empty() {
+            log::info!("共有 {} 个文件与目标目录中的CRC32相同，未解压", stats.unchanged.len());
+        }
+        unchanged_entries = stats.unchanged.into_iter().collect();
+        if stats.skipped > 0 {
+            log::warn!("共跳过 {} 个不安全的压缩包条目", stats.skipped);
+            emit(sender, UpdateEvent::Warning(dict.warning_unsafe_entries(stats.skipped)));
+        }
+        temp_path
+    };
+    
+    // 复制前先检查所有映射的指定目录并收集要复制的文件，任何一个目录无效时都不修改目标目录
+    let filter = FileFilter::new(&config.include, &config.exclude).map_err(failed(FailureKind::InvalidConfig))?;
+    let conflicts = Conflicts::new(config.confirm_overwrite, &config.protect).map_err(failed(FailureKind::InvalidConfig))?;
+    let mut mappings = Vec::new();
+    for (zip_inner_path, target) in targets {
+        mappings.push(collect_mapping(config, dict, sender, &filter, temp_path, &zip_inner_path, long_path::extend(&target), &unchanged_entries)?);
+    }
+    let newer_files: usize = mappings.iter().map(|mapping| mapping.newer_files).sum();
+    if newer_files > 0 {
+        emit(sender, UpdateEvent::Warning(dict.warning_newer_kept(newer_files)));
+    }
+    
+    // 复制阶段的进度按所有映射的总字节数计算，记录每个映射之前已复制的字节数
+    let phase_total = mappings.iter().map(|mapping| mapping.copy_set.total_bytes).sum();
+    let mut phase_start = 0;
+    for mapping in &mut mappings {
+        mapping.copy_set.phase_start = phase_start;
+        mapping.copy_set.phase_total = phase_total;
+        phase_start += mapping.copy_set.total_bytes;
+    }
+    let total = mappings.len();
+    let mut summary = UpdateSummary {
+        files_skipped: mappings.iter().map(|mapping| mapping.excluded_files + mapping.unchanged_files + mapping.newer_files).sum(),
+        ..Default::default()
+    };
+    
+    // 演练模式下只记录计划执行的操作
+    if config.dry_run {
+        let mut plan = Vec::new();
+        for (i, mapping) in mappings.iter().enumerate() {
+            if total > 1 {
+                emit(sender, UpdateEvent::Status(dict.status_mapping(i + 1, total, &mapping.inner, &long_path::display(&mapping.target))));
+            }
+            send_copying_status(dict, sender, mapping);
+            let files = &mapping.copy_set.files;
+            for (i, (_, relative_path)) in files.iter().enumerate() {
+                emit(sender, UpdateEvent::Progress { phase: Phase::Copy, current: i + 1, total: files.len(), file: relative_path.display().to_string() });
+            }
+            plan.extend(planned_actions(config, mapping, &filter, &exe_path, running_exe.as_deref()));
+        }
+        emit(sender, UpdateEvent::Plan(plan));
+        return Ok(summary);
+    }
+    
+    // 确认模式下列出即将执行的操作，等待调用方确认；取消或无法确认（调用方丢弃了回复的发送端）时不修改目标目录
+    if config.confirm {
+        let actions = mappings.iter().flat_map(|mapping| planned_actions(config, mapping, &filter, &exe_path, running_exe.as_deref())).collect();
+        let (reply, response) = mpsc::channel();
+        emit(sender, UpdateEvent::Confirm { actions, total_bytes: phase_total, reply });
+        if !watchdog.suspend(|| response.recv().unwrap_or(false)) {
+            log::info!("用户取消了更新，未修改目标目录");
+            return Err((FailureKind::Cancelled, io::Error::other(dict.error_update_declined)));
+        }
+        log::info!("用户确认应用更新");
+    }
+    
+    // 修改任何文件之前创建审计记录，无法创建时中止更新
+    let audit = Audit::open(config.audit_csv.as_deref())
+        .map_err(|e| (FailureKind::Other, io::Error::new(e.kind(), dict.error_audit_not_opened(config.audit_csv.as_deref().unwrap_or_default(), &e.to_string()))))?;
+    
+    // 结束正在运行的目标程序，避免其文件被占用
+    if let Some(name) = &config.kill_process {
+        process::kill_processes(name, dict, sender).map_err(failed(FailureKind::Other))?;
+    }
+    
+    // 复制前检查要覆盖的文件是否被占用，正在运行的更新程序自身会暂存，不需要检查
+    let overwritten = mappings.iter()
+        .flat_map(|mapping| mapping.copy_set.files.iter().map(|(_, relative_path)| mapping.target.join(relative_path)))
+        .filter(|dest_path| dest_path.is_file() && staged_destination(dest_path, running_exe.as_deref(), &config.self_suffix) == *dest_path)
+        .collect();
+    locks::check(config.on_locked, overwritten, dict, sender, watchdog).map_err(|e| match e.kind() {
+        io::ErrorKind::TimedOut | io::ErrorKind::Interrupted => (FailureKind::Cancelled, e),
+        _ => (FailureKind::Copy, e),
+    })?;
+    
+    // 断点续传模式下读取每个目标目录中上次运行留下的断点
+    if config.resumable {
+        for mapping in &mut mappings {
+            mapping.checkpoint = Some(Checkpoint::load(&mapping.target, package_path).map_err(failed(FailureKind::Copy))?);
+        }
+    }
+    
+    // 按顺序更新每个映射，所有直接更新的修改和原子替换的目录交换都记录到同一个回滚日志中，任何一个失败时全部回滚
+    let rollback = Rollback::new(config.temp_dir.as_deref()).map_err(failed(FailureKind::Copy))?;
+    // 去重缓存在所有映射之间共享，同一份内容写入多个目标目录时只从更新包复制一次
+    let dedup = Dedup::new(config.dedup, mappings.iter().flat_map(|mapping| mapping.copy_set.files.iter().map(|(entry_path, _)| entry_path.as_path())));
+    let mut failures = Vec::new();
+    for (i, mapping) in mappings.iter().enumerate() {
+        if total > 1 {
+            log::info!("正在更新映射 {}/{}: {:?} -> {:?}", i + 1, total, mapping.inner, mapping.target);
+            emit(sender, UpdateEvent::Status(dict.status_mapping(i + 1, total, &mapping.inner, &long_path::display(&mapping.target))));
+        }
+        match apply_mapping(config, dict, sender, mapping, &filter, &exe_path, &rollback, &conflicts, watchdog, &audit, &dedup) {
+            Ok(applied) => {
+                summary.files_copied += applied.copied_files;
+                summary.bytes_copied += applied.copied_bytes;
+                summary.files_deleted += applied.deleted_files;
+                summary.files_pending_reboot += applied.pending_reboot;
+                summary.files_skipped += applied.resumed_files;
+                // 有多个映射时用完整的目标路径报告失败的文件
+                let target = PathBuf::from(long_path::display(&mapping.target));
+                failures.extend(applied.failures.into_iter().map(|(path, e)| if total > 1 { (target.join(path), e) } else { (path, e) }));
+            },
+            // 断点续传模式下保留已复制的文件并保存断点，重新运行时从中断处继续
+            Err(e) if config.resumable => {
+                log::error!("复制失败，保存断点: {}", e);
+                save_checkpoints(&mappings);
+                emit(sender, UpdateEvent::Status(dict.status_checkpoint_saved.to_string()));
+                let kind = if e.kind() == io::ErrorKind::InvalidData { FailureKind::Verification } else { FailureKind::Copy };
+                return Err((kind, e));
+            },
+            Err(e) => {
+                log::error!("复制失败，正在回滚: {}", e);
+                emit(sender, UpdateEvent::Status(dict.status_rolling_back.to_string()));
+                let restored = rollback.restore();
+                if let Err(rollback_err) = &restored {
+                    log::error!("回滚未能完全恢复目标目录: {}", rollback_err);
+                } else {
+                    log::info!("已回滚目标目录");
+                }
+                for mapping in &mappings {
+                    audit.record(audit::Action::Rollback, None, &mapping.target, 0, restored.as_ref().map(|_| ()));
+                }
+                // 复制后的校验失败归为校验错误，其余归为复制错误
+                let kind = if e.kind() == io::ErrorKind::InvalidData { FailureKind::Verification } else { FailureKind::Copy };
+                return Err((kind, e));
+            }
+        }
+    }
+    // 全部映射都已写入，不再需要回滚，删除原子替换时保留的旧版本目录
+    rollback.commit();
+    if conflicts.skipped() > 0 {
+        emit(sender, UpdateEvent::Warning(dict.warning_protected_skipped(conflicts.skipped())));
+    }
+    summary.files_skipped += conflicts.skipped();
+    if summary.files_pending_reboot > 0 {
+        emit(sender, UpdateEvent::Warning(dict.warning_restart_required(summary.files_pending_reboot)));
+    }
+    
+    // 全部复制完成，进入收尾阶段
+    emit(sender, UpdateEvent::PhaseProgress { phase: Phase::Finalize, fraction: 0.0 });
+    
+    // 全部成功后删除断点；继续执行模式下有文件失败时保留断点，重新运行时只复制失败的文件
+    if failures.is_empty() {
+        for checkpoint in mappings.iter().filter_map(|mapping| mapping.checkpoint.as_ref()) {
+            if let Err(e) = checkpoint.remove() {
+                log::warn!("删除断点失败: {}", e);
+            }
+        }
+    } else {
+        save_checkpoints(&mappings);
+    }
+    
+    // 全部文件更新成功后按目标目录中的最终内容写入回执，写入失败时只提示，不影响更新结果
+    if config.receipt && failures.is_empty() {
+        let version = package_version(temp_path);
+        for (i, mapping) in mappings.iter().enumerate() {
+            let target = long_path::display(&mapping.target);
+            emit(sender, UpdateEvent::Progress { phase: Phase::Finalize, current: i + 1, total, file: target.clone() });
+            let installed = mapping.package_files.iter().filter(|path| filter.allows(path)).cloned().collect();
+            match receipt::write(&mapping.target, installed, package_path, version.as_deref()) {
+                Ok(path) => log::info!("已写入更新回执: {:?}", path),
+                Err(e) => {
+                    log::error!("写入更新回执失败: {:?}: {}", mapping.target, e);
+                    emit(sender, UpdateEvent::Warning(dict.warning_receipt_not_written(&target, &e.to_string())));
+                },
+            }
+        }
+    }
+    emit(sender, UpdateEvent::PhaseProgress { phase: Phase::Finalize, fraction: 1.0 });
+    summary.failed_files = failures.into_iter().map(|(path, e)| (path, e.to_string())).collect();
+    Ok(summary)
+}
+
+// 一个映射中计划执行的操作：新建、覆盖或暂存每个要复制的文件，以及 --prune 要删除的旧文件
+fn planned_actions(config: &UpdateConfig, mapping: &Mapping, filter: &FileFilter, exe_path: &Path, running_exe: Option<&Path>) -> Vec<PlannedAction> {
+    let mut plan = Vec::new();
+    for (_, relative_path) in &mapping.copy_set.files {
+        let dest_path = mapping.target.join(relative_path);
+        let final_dest_path = staged_destination(&dest_path, running_exe, &config.self_suffix);
+        let action = if final_dest_path != dest_path {
+            PlannedAction::RenameExe(long_path::display(&dest_path), long_path::display(&final_dest_path))
+        } else if dest_path.exists() {
+            PlannedAction::Overwrite(long_path::display(&dest_path))
+        } else {
+            PlannedAction::Create(long_path::display(&dest_path))
+        };
+        log::info!("计划操作: {:?}", action);
+        plan.push(action);
+    }
+    if mapping.prune {
+        for relative_path in find_prunable(&mapping.target, &mapping.package_files, filter, exe_path, &config.self_suffix) {
+            let action = PlannedAction::Delete(long_path::display(&mapping.target.join(relative_path)));
+            log::info!("计划操作: {:?}", action);
+            plan.push(action);
+        }
+    }
+    plan
+}
+
+// 保存所有映射的断点，失败时只记录日志
+fn save_checkpoints(mappings: &[Mapping]) {
+    for (mapping, checkpoint) in mappings.iter().filter_map(|mapping| mapping.checkpoint.as_ref().map(|c| (mapping, c))) {
+        if let Err(e) = checkpoint.save() {
+            log::error!("保存断点失败: {:?}: {}", mapping.target, e);
+        }
+    }
+}
+
+// 解压或复制过程中报告的进度，调用方通过回调接收，不依赖事件通道
+pub(crate) enum Progress {
+    // 需要处理的总条目数
+    Total(usize),
+    // 当前序号、总数和条目名称；tar.gz无法预先统计条目数，总数为0
+    Entry(usize, usize, String),
+    // 已读取的更新包字节数和更新包大小，流式解压时按此计算进度
+    Read(u64, u64),
+}
+
+impl From<Progress> for UpdateEvent {
+    fn from(progress: Progress) -> Self {
+        match progress {
+            Progress::Total(total) => UpdateEvent::TotalFiles(total),
+            Progress::Entry(current, total, file) => UpdateEvent::Progress { phase: Phase::Extract, current, total, file },
+            Progress::Read(read, size) => UpdateEvent::PhaseProgress { phase: Phase::Extract, fraction: read as f32 / size.max(1) as f32 },
+        }
+    }
+}
+
+// 解压或复制的统计结果
+#[derive(Debug, Default)]
+pub(crate) struct Stats {
+    // 写入的文件（包括符号链接）数和字节数
+    pub files: usize,
+    pub bytes: u64,
+    // 因可能写到目标目录之外而跳过的条目数
+    pub skipped: usize,
+    // 与目标文件CRC32相同而未解压的条目（压缩包内的相对路径）
+    pub unchanged: Vec<PathBuf>,
+    // 没有扩展时间戳、修改时间只能按不含时区的DOS时间还原的文件数
+    pub local_times: usize,
+}
+
+// 加密的更新包缺少密码或密码错误，由调用方转换为本地化的错误信息
+#[derive(Debug)]
+pub(crate) enum PasswordError {
+    Required,
+    Invalid,
+}
+
+impl std::fmt::Display for PasswordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PasswordError::Required => write!(f, "password required to decrypt the package"),
+            PasswordError::Invalid => write!(f, "invalid password for the package"),
+        }
+    }
+}
+
+impl std::error::Error for PasswordError {}
+
+// 在指定的目录（不存在时先创建）或系统临时目录中创建临时目录，离开作用域时删除
+pub(crate) fn create_temp_dir(base: Option<&str>) -> io::Result<TempDir> {
+    match base {
+        Some(base) => {
+            fs::create_dir_all(base)?;
+            tempfile::tempdir_in(base)
+        },
+        None => tempdir(),
+    }
+}
+
+// 解压到临时目录时磁盘已满：立即删除解压了一部分的临时目录以释放空间，返回提示改用 --temp-dir 的错误；
+// 其他错误原样返回
+fn temp_space_exhausted(e: io::Error, temp_path: &Path, dict: &LangDict) -> io::Error {
+    if !matches!(e.kind(), io::ErrorKind::StorageFull | io::ErrorKind::QuotaExceeded) {
+        return e;
+    }
+    log::error!("临时目录所在磁盘空间不足，解压中止: {:?}: {}", temp_path, e);
+    match fs::remove_dir_all(temp_path) {
+        Ok(()) => log::info!("已删除解压了一部分的临时目录: {:?}", temp_path),
+        Err(e) => log::warn!("无法删除临时目录: {:?}: {}", temp_path, e),
+    }
+    io::Error::new(e.kind(), dict.error_temp_space_exhausted(&long_path::display(temp_path)))
+}
+
+// 将更新包解压到指定目录，跳过可能写到该目录之外的条目；进度回调返回错误时中止解压。
+// 指定密码时用于解密加密的条目，未加密的条目忽略密码；is_wanted 为假的条目不解压，也不计入进度的总数；
+// is_unchanged 根据条目路径、CRC32和大小判断为真的文件不解压
+pub(crate) fn extract_to(
+    package_path: &Path,
+    dest: &Path,
+    password: Option<&[u8]>,
+    preserve_mtime: bool,
+    is_wanted: impl Fn(&Path) -> bool,
+    is_unchanged: impl Fn(&Path, u32, u64) -> bool,
+    mut progress: impl FnMut(Progress) -> io::Result<()>,
+) -> io::Result<Stats> {
+    let file = fs::File::open(package_path)?;
+    let mut archive = ZipArchive::new(file)?;
+    let mut stats = Stats::default();
+    
+    // 先按中央目录选出需要解压的条目，不安全的条目保留下来，解压时跳过并计数
+    let mut selected = Vec::new();
+    for i in 0..archive.len() {
+        if archive.by_index_raw(i)?.enclosed_name().is_none_or(&is_wanted) {
+            selected.push(i);
+        }
+    }
+    let total_files = selected.len();
+    if total_files < archive.len() {
+        log::info!("只解压压缩包内路径中的 {} 个条目（共 {} 个）", total_files, archive.len());
+    }
+    progress(Progress::Total(total_files))?;
+    
+    // 逐文件解压，实时报告进度
+    for (n, i) in selected.into_iter().enumerate() {
+        // 中央目录中的CRC32不需要解密即可读取，内容未变化的文件只创建所在目录
+        let raw = archive.by_index_raw(i)?;
+        let is_link = raw.unix_mode().is_some_and(|mode| mode & 0o170000 == 0o120000);
+        if !raw.is_dir() && !is_link && let Some(path) = raw.enclosed_name() && is_unchanged(path, raw.crc32(), raw.size())
+            && let Some(outpath) = contained_path(dest, path) {
+            progress(Progress::Entry(n + 1, total_files, raw.name().to_string()))?;
+            if let Some(parent) = outpath.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            stats.unchanged.push(path.to_path_buf());
+            continue;
+        }
+        drop(raw);
+        let mut file = match password {
+            Some(password) => archive.by_index_decrypt(i, password)?.map_err(|_| io::Error::other(PasswordError::Invalid))?,
+            None => match archive.by_index(i) {
+                Err(ZipError::UnsupportedArchive(ZipError::PASSWORD_REQUIRED)) => return Err(io::Error::other(PasswordError::Required)),
+                result => result?,
+            },
+        };
+        let file_name = file.name().to_string();
+        let outpath = match file.enclosed_name().and_then(|path| contained_path(dest, path)) {
+            Some(path) => path,
+            None => {
+                // 绝对路径或包含..的条目可能写到目标目录之外（Zip Slip），一律跳过
+                log::warn!("跳过不安全的压缩包条目: {:?}", file_name);
+                stats.skipped += 1;
+                continue;
+            }
+        };
+        
+        progress(Progress::Entry(n + 1, total_files, file_name.clone()))?;
+        
+        // 不能通过之前解压出的符号链接写到目标目录之外
+        if has_symlink_component(dest, &outpath) {
+            log::warn!("跳过位于符号链接之下的压缩包条目: {:?}", file_name);
+            stats.skipped += 1;
+            continue;
+        }
+        
+        // 创建目录
+        if let Some(p) = outpath.parent() && !p.exists() {
+            fs::create_dir_all(p)?;
+        }
+        
+        // 目录条目只创建目录，保留更新包中的空目录
+        if (*file.name()).ends_with('/') {
+            fs::create_dir_all(&outpath)?;
+            continue;
+        }
+        
+        // Unix上按条目中记录的文件类型还原符号链接，链接目标不能指向目标目录之外
+        #[cfg(unix)]
+        if file.unix_mode().is_some_and(|mode| mode & 0o170000 == 0o120000) {
+            let link_target = PathBuf::from(io::read_to_string(&mut file)?);
+            if !symlink_contained(dest, &outpath, &link_target) {
+                log::warn!("跳过指向更新包之外的符号链接: {:?} -> {:?}", file_name, link_target);
+                stats.skipped += 1;
+                continue;
+            }
+            std::os::unix::fs::symlink(&link_target, &outpath)?;
+            stats.files += 1;
+            continue;
+        }
+        
+        // 写入文件
+        let mut outfile = fs::File::create(&outpath)?;
+        stats.bytes += std::io::copy(&mut file, &mut outfile)?;
+        stats.files += 1;
+        drop(outfile);
+        
+        // Unix上还原条目中记录的权限，保证可执行文件（包括更新程序自身）复制后仍可以运行
+        #[cfg(unix)]
+        if let Some(mode) = file.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&outpath, fs::Permissions::from_mode(mode & 0o777))?;
+        }
+        
+        // 保留压缩包中记录的修改时间：优先使用扩展时间戳中的UTC时间，没有时DOS时间不含时区信息，按UTC处理
+        if preserve_mtime {
+            match extended_mtime(file.extra_data()) {
+                Some(modified) => filetime::set_file_mtime(&outpath, modified)?,
+                None => if let Ok(modified) = file.last_modified().to_time() {
+                    filetime::set_file_mtime(&outpath, FileTime::from_unix_time(modified.unix_timestamp(), 0))?;
+                    stats.local_times += 1;
+                },
+            }
+        }
+    }
+    Ok(stats)
+}
+
+// 从ZIP条目的扩展字段中读取UTC修改时间：Info-ZIP等工具写入的扩展时间戳（0x5455，Unix时间），
+// 或7-Zip等工具写入的NTFS时间（0x000a，1601年起的100纳秒数）；都没有时返回None
+fn extended_mtime(mut extra: &[u8]) -> Option<FileTime> {
+    // Windows文件时间起点（1601-01-01）到Unix时间起点的秒数
+    const NTFS_EPOCH_OFFSET: i64 = 11_644_473_600;
+    while extra.len() >= 4 {
+        let id = u16::from_le_bytes([extra[0], extra[1]]);
+        let size = u16::from_le_bytes([extra[2], extra[3]]) as usize;
+        let data = extra.get(4..4 + size)?;
+        extra = &extra[4 + size..];
+        match id {
+            // 第一个字节的最低位表示随后是否有修改时间
+            0x5455 if data.first().is_some_and(|flags| flags & 1 != 0) && data.len() >= 5 => {
+                let seconds = i32::from_le_bytes([data[1], data[2], data[3], data[4]]);
+                return Some(FileTime::from_unix_time(seconds.into(), 0));
+            },
+            // 4字节保留字段之后是若干属性，标签1为修改、访问和创建时间
+            0x000a => {
+                let mut attributes = data.get(4..)?;
+                while attributes.len() >= 4 {
+                    let tag = u16::from_le_bytes([attributes[0], attributes[1]]);
+                    let length = u16::from_le_bytes([attributes[2], attributes[3]]) as usize;
+                    let value = attributes.get(4..4 + length)?;
+                    if tag == 1 && length >= 8 {
+                        let ticks = u64::from_le_bytes(value[..8].try_into().ok()?);
+                        let seconds = (ticks / 10_000_000) as i64 - NTFS_EPOCH_OFFSET;
+                        return Some(FileTime::from_unix_time(seconds, (ticks % 10_000_000) as u32 * 100));
+                    }
+                    attributes = &attributes[4 + length..];
+                }
+            },
+            _ => {},
+        }
+    }
+    None
+}
+
+// 读取时统计已读取的字节数，流式解压时据此报告进度
+struct CountingReader<R> {
+    inner: R,
+    read: Rc<Cell<u64>>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read.set(self.read.get() + n as u64);
+        Ok(n)
+    }
+}
+
+// 将tar.gz更新包解压到指定目录，与 extract_to 一样跳过可能写到该目录之外的条目，is_wanted 为假的条目不解压。
+// tar格式没有中央目录，只顺序读一遍更新包，边解压边按已读取的字节数（size 为更新包大小）报告进度，不需要可定位的文件；
+// 只还原目录、普通文件、硬链接和Unix上的符号链接，其他类型的条目跳过
+pub(crate) fn extract_tar_gz(
+    package: impl Read,
+    size: u64,
+    dest: &Path,
+    preserve_mtime: bool,
+    is_wanted: impl Fn(&Path) -> bool,
+    mut progress: impl FnMut(Progress) -> io::Result<()>,
+) -> io::Result<Stats> {
+    let read = Rc::new(Cell::new(0));
+    let mut archive = tar::Archive::new(GzDecoder::new(CountingReader { inner: package, read: read.clone() }));
+    let mut stats = Stats::default();
+    
+    let mut n = 0;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = tar_entry_path(&entry)?;
+        if !is_wanted(&path) {
+            continue;
+        }
+        n += 1;
+        let file_name = path.display().to_string();
+        let Some(outpath) = contained_path(dest, &path) else {
+            // 绝对路径或包含..的条目可能写到目标目录之外，一律跳过
+            log::warn!("跳过不安全的压缩包条目: {:?}", file_name);
+            stats.skipped += 1;
+            continue;
+        };
+        
+        progress(Progress::Entry(n, 0, file_name.clone()))?;
+        progress(Progress::Read(read.get(), size))?;
+        
+        // 不能通过之前解压出的符号链接写到目标目录之外
+        if has_symlink_component(dest, &outpath) {
+            log::warn!("跳过位于符号链接之下的压缩包条目: {:?}", file_name);
+            stats.skipped += 1;
+            continue;
+        }
+        
+        if let Some(p) = outpath.parent() && !p.exists() {
+            fs::create_dir_all(p)?;
+        }
+        
+        match entry.header().entry_type() {
+            tar::EntryType::Directory => {
+                fs::create_dir_all(&outpath)?;
+                continue;
+            },
+            // Unix上还原符号链接，链接目标不能指向目标目录之外
+            #[cfg(unix)]
+            tar::EntryType::Symlink => {
+                let Some(link_target) = entry.link_name()?.map(|target| target.into_owned()) else {
+                    continue;
+                };
+                if !symlink_contained(dest, &outpath, &link_target) {
+                    log::warn!("跳过指向更新包之外的符号链接: {:?} -> {:?}", file_name, link_target);
+                    stats.skipped += 1;
+                    continue;
+                }
+                std::os::unix::fs::symlink(&link_target, &outpath)?;
+                stats.files += 1;
+                continue;
+            },
+            // 硬链接指向之前解压出的文件，同样不能指向目标目录之外；无法创建硬链接时复制该文件
+            tar::EntryType::Link => {
+                let source = entry.link_name()?.and_then(|target| contained_path(dest, &target));
+                let Some(source) = source.filter(|source| !has_symlink_component(dest, source) && source.is_file()) else {
+                    log::warn!("跳过指向更新包之外或未解压的文件的硬链接: {:?}", file_name);
+                    stats.skipped += 1;
+                    continue;
+                };
+                if let Err(e) = fs::hard_link(&source, &outpath) {
+                    log::info!("无法创建硬链接，改为复制: {:?}: {}", outpath, e);
+                    stats.bytes += fs::copy(&source, &outpath)?;
+                }
+                stats.files += 1;
+                continue;
+            },
+            tar::EntryType::Regular | tar::EntryType::Continuous => {},
+            entry_type => {
+                log::warn!("跳过不支持的tar条目类型: {:?} ({:?})", file_name, entry_type);
+                continue;
+            },
+        }
+        
+        // 写入文件
+        let mut outfile = fs::File::create(&outpath)?;
+        stats.bytes += io::copy(&mut entry, &mut outfile)?;
+        stats.files += 1;
+        drop(outfile);
+        
+        // Unix上还原条目中记录的权限，保证可执行文件复制后仍可以运行
+        #[cfg(unix)]
+        if let Ok(mode) = entry.header().mode() {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&outpath, fs::Permissions::from_mode(mode & 0o777))?;
+        }
+        
+        // 保留条目中记录的修改时间（tar中记录的是Unix时间戳）
+        if preserve_mtime && let Ok(mtime) = entry.header().mtime() {
+            filetime::set_file_mtime(&outpath, FileTime::from_unix_time(mtime as i64, 0))?;
+        }
+    }
+    progress(Progress::Read(size, size))?;
+    Ok(stats)
+}
+
+// tar条目的相对路径，去掉打包时常见的 ./ 前缀，使其与ZIP条目的路径一致
+fn tar_entry_path<R: Read>(entry: &tar::Entry<R>) -> io::Result<PathBuf> {
+    Ok(entry.path()?.components().filter(|component| *component != std::path::Component::CurDir).collect())
+}
+
+// 只需要解压的压缩包内路径：每个映射都指定了不含通配符的压缩包内路径时，其余条目不解压；
+// 有映射复制整个压缩包或使用通配符（需要解压后才能确定目录）时返回None，解压全部条目
+fn extract_prefixes(targets: &[(String, PathBuf)]) -> Option<Vec<PathBuf>> {
+    targets.iter().map(|(inner, _)| {
+        let inner = inner.trim_matches('/');
+        (!inner.is_empty() && !filter::is_glob(inner)).then(|| PathBuf::from(inner))
+    }).collect()
+}
+
+// 可以在解压前按CRC32跳过未变化文件时，返回每个映射的（压缩包内目录，目标目录）。
+// 需要完整解压的情况不跳过：更新源是目录、应用补丁、平铺复制、压缩包内路径含通配符，或者更新包中有需要逐个验证的校验清单
+fn crc_skip_targets(config: &UpdateConfig, targets: &[(String, PathBuf)]) -> Option<Vec<(PathBuf, PathBuf)>> {
+    if !config.skip_unchanged || config.delta || config.flatten || targets.iter().any(|(inner, _)| filter::is_glob(inner)) {
+        return None;
+    }
+    let archive = ZipArchive::new(fs::File::open(&config.package_path).ok()?).ok()?;
+    if archive.file_names().any(|name| Path::new(name).file_name() == Some(manifest::MANIFEST_NAME.as_ref())) {
+        log::info!("更新包中有校验清单，解压全部文件");
+        return None;
+    }
+    Some(targets.iter().map(|(inner, target)| (PathBuf::from(inner.trim_matches('/')), long_path::extend(target))).collect())
+}
+
+// 条目是否属于至少一个映射，并且每个映射中对应的目标文件大小和CRC32都与条目相同
+fn entry_unchanged(targets: &[(PathBuf, PathBuf)], entry: &Path, crc: u32, size: u64) -> bool {
+    // 更新包元数据在复制前还要读取
+    if entry == Path::new(PACKAGE_METADATA_NAME) {
+        return false;
+    }
+    let mut matched = false;
+    for (inner, target) in targets {
+        let Ok(relative_path) = entry.strip_prefix(inner) else {
+            continue;
+        };
+        // 指向单个文件的映射照常解压
+        if relative_path.as_os_str().is_empty() {
+            return false;
+        }
+        let dest = target.join(relative_path);
+        if is_symlink(&dest) || !crc32_matches(&dest, crc, size).unwrap_or(false) {
+            return false;
+        }
+        matched = true;
+    }
+    matched
+}
+
+// 文件的大小和CRC32是否与给定的值相同，大小不同时不读取内容
+fn crc32_matches(path: &Path, crc: u32, size: u64) -> io::Result<bool> {
+    let meta = fs::metadata(path)?;
+    if !meta.is_file() || meta.len() != size {
+        return Ok(false);
+    }
+    let mut file = fs::File::open(path)?;
+    let mut hasher = crc32fast::Hasher::new();
+    let mut buffer = vec![0; COPY_CHUNK_SIZE];
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(hasher.finalize() == crc)
+}
+
+// 要更新的（压缩包内目录，目标目录）列表，没有指定映射时使用 zip_inner_path 和 target_path
+pub(crate) fn update_targets(config: &UpdateConfig) -> Vec<(String, PathBuf)> {
+    if !config.mappings.is_empty() {
+        return config.mappings.iter().map(|(inner, target)| (inner.clone(), PathBuf::from(target))).collect();
+    }
+    config.target_path.iter().map(|target| (config.zip_inner_path.clone(), PathBuf::from(target))).collect()
+}
+
+// 一组压缩包内目录到目标目录的映射，以及其中需要复制的文件
+struct Mapping {
+    inner: String,
+    target: PathBuf,
+    copy_set: CopySet,
+    // 更新包中的全部文件（包括被过滤的），用于判断哪些旧文件需要删除
+    package_files: HashSet<PathBuf>,
+    excluded_files: usize,
+    unchanged_files: usize,
+    // --skip-newer 时因目标文件较新而保留的文件数
+    newer_files: usize,
+    // 是否删除目标目录中新版本已不存在的文件，压缩包内路径是单个文件时不删除
+    prune: bool,
+    // 断点续传模式下目标目录中已复制文件的记录
+    checkpoint: Option<Checkpoint>,
+}
+
+// 在解压后的目录中查找与压缩包内路径模式匹配的目录，没有匹配或匹配多个时报错
+fn resolve_inner_glob(dict: &LangDict, temp_path: &Path, pattern: &str) -> Result<String, Failed> {
+    let dirs = filter::matching_dirs(temp_path, pattern).map_err(failed(FailureKind::InvalidConfig))?;
+    let names: Vec<String> = dirs.iter().map(|dir| filter::normalize(dir)).collect();
+    match names.as_slice() {
+        [] => Err((FailureKind::Extraction, io::Error::new(io::ErrorKind::NotFound, dict.error_inner_path_not_found(pattern)))),
+        [name] => {
+            log::info!("压缩包内路径 {} 匹配到目录: {}", pattern, name);
+            Ok(name.clone())
+        },
+        _ => Err((FailureKind::Extraction, io::Error::new(io::ErrorKind::InvalidInput, dict.error_inner_path_ambiguous(pattern, &names.join(", "))))),
+    }
+}
+
+// 找到解压后的指定目录，验证校验清单并收集需要复制的文件，检查目标磁盘剩余空间
+#[allow(clippy::too_many_arguments)]
+fn collect_mapping(
+    config: &UpdateConfig,
+    dict: &'static LangDict,
+    sender: &EventSender,
+    filter: &FileFilter,
+    temp_path: &Path,
+    zip_inner_path: &str,
+    target: PathBuf,
+    unchanged_entries: &HashSet<PathBuf>,
+) -> Result<Mapping, Failed> {
+    // 压缩包内路径包含通配符时使用唯一匹配的目录，如 myapp-* 匹配 myapp-1.2.3
+    let resolved;
+    let zip_inner_path = if filter::is_glob(zip_inner_path) {
+        resolved = resolve_inner_glob(dict, temp_path, zip_inner_path)?;
+        resolved.as_str()
+    } else {
+        zip_inner_path
+    };
+    
+    // 指定路径不能指向临时目录之外
+    let mut inner_path = if zip_inner_path.is_empty() {
+        temp_path.to_path_buf()
+    } else {
+        contained_path(temp_path, Path::new(zip_inner_path))
+            .ok_or_else(|| (FailureKind::Extraction, io::Error::new(io::ErrorKind::InvalidInput, dict.error_unsafe_inner_path(zip_inner_path))))?
+    };
+    log::info!("压缩包内指定目录路径: {:?}", inner_path);
+    
+    // 验证指定目录是否存在
+    if !inner_path.exists() {
+        return Err((FailureKind::Extraction, io::Error::new(io::ErrorKind::NotFound, dict.error_inner_path_not_found(zip_inner_path))));
+    }
+    
+    // 指定路径是单个文件时只复制这一个文件，以原文件名放到目标目录中
+    let single_file = inner_path.is_file();
+    let root = if single_file {
+        log::info!("压缩包内指定路径是文件，只复制该文件");
+        inner_path.parent().unwrap_or(temp_path).to_path_buf()
+    } else {
+        inner_path.clone()
+    };
+    
+    // 先把二进制补丁应用到目标目录中的现有文件，校验清单中列出的是补丁后的文件
+    if config.delta {
+        emit(sender, UpdateEvent::Status(dict.status_applying_patches.to_string()));
+        let patched = delta::apply_patches(&inner_path, &root, &target, dict).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => (FailureKind::Verification, e),
+            _ => (FailureKind::Extraction, e),
+        })?;
+        log::info!("共应用 {} 个补丁", patched);
+        if single_file && !inner_path.exists() {
+            inner_path = inner_path.with_extension("");
+        }
+    }
+    
+    // 复制前按更新包中的校验清单验证解压出的文件
+    if inner_path.join(manifest::MANIFEST_NAME).is_file() {
+        emit(sender, UpdateEvent::Status(dict.status_verifying_manifest.to_string()));
+    }
+    // 有多个映射时按当前映射的清单计算校验阶段的进度
+    let has_manifest = manifest::verify(&inner_path, dict, |current, total, file| {
+        emit(sender, UpdateEvent::Progress { phase: Phase::Verify, current, total, file: file.to_string() });
+        emit(sender, UpdateEvent::PhaseProgress { phase: Phase::Verify, fraction: current as f32 / total as f32 });
+    }).map_err(failed(FailureKind::Verification))?;
+    if single_file && config.prune {
+        log::warn!("压缩包内指定路径是文件，不删除目标目录中的其他文件");
+    }
+    
+    // 收集指定目录下需要复制的文件（源路径和相对于指定目录的路径），并按包含/排除模式过滤
+    let mut files = Vec::new();
+    let mut package_files = HashSet::new();
+    let mut excluded_files = 0;
+    let mut unchanged_files = 0;
+    let mut newer_files = 0;
+    // 平铺复制时每个文件名对应的压缩包内路径
+    let mut flattened: HashMap<PathBuf, PathBuf> = HashMap::new();
+    for entry in WalkDir::new(&inner_path).sort_by_file_name().into_iter().filter_map(|e| e.ok()) {
+        // 指向目录的符号链接按链接本身复制
+        if entry.path().is_dir() && !is_symlink(entry.path()) {
+            continue;
+        }
+        let relative_path = entry.path().strip_prefix(&root)
+            .map_err(|e| (FailureKind::Extraction, io::Error::other(e.to_string())))?
+            .to_path_buf();
+        if has_manifest && relative_path == Path::new(manifest::MANIFEST_NAME) {
+            continue;
+        }
+        if entry.path() == temp_path.join(PACKAGE_METADATA_NAME) || entry.path() == temp_path.join(PACKAGE_TARGET_NAME) {
+            continue;
+        }
+        // 平铺复制时只保留文件名，之后的过滤、比较和清理都按目标目录顶层的文件名进行
+        let relative_path = if config.flatten {
+            let name = PathBuf::from(relative_path.file_name().unwrap_or_default());
+            if let Some(first) = flattened.insert(name.clone(), relative_path.clone()) {
+                log::error!("平铺复制时文件名重复: {:?} 和 {:?}", first, relative_path);
+                return Err((FailureKind::Extraction, io::Error::new(io::ErrorKind::AlreadyExists, dict.error_flatten_collision(
+                    &name.display().to_string(),
+                    &first.display().to_string(),
+                    &relative_path.display().to_string(),
+                ))));
+            }
+            name
+        } else {
+            relative_path
+        };
+        package_files.insert(relative_path.clone());
+        if !filter.allows(&relative_path) {
+            log::info!("跳过被过滤的文件: {:?}", relative_path);
+            excluded_files += 1;
+            continue;
+        }
+        if config.skip_unchanged && !files_differ(entry.path(), &target.join(&relative_path)).map_err(failed(FailureKind::Copy))? {
+            log::info!("跳过未变化的文件: {:?}", relative_path);
+            unchanged_files += 1;
+            continue;
+        }
+        if config.skip_newer && dest_newer(entry.path(), &target.join(&relative_path)).map_err(failed(FailureKind::Copy))? {
+            log::info!("目标文件比更新包中的新，保留: {:?}", relative_path);
+            newer_files += 1;
+            continue;
+        }
+        files.push((entry.path().to_path_buf(), relative_path));
+    }
+    
+    // 解压时因CRC32相同而跳过的文件仍属于新版本，不能被 --prune 删除
+    if !single_file {
+        for entry in unchanged_entries {
+            let Ok(relative_path) = entry.strip_prefix(zip_inner_path.trim_matches('/')) else {
+                continue;
+            };
+            log::info!("跳过未变化的文件（CRC32相同）: {:?}", relative_path);
+            package_files.insert(relative_path.to_path_buf());
+            if filter.allows(relative_path) {
+                unchanged_files += 1;
+            } else {
+                excluded_files += 1;
+            }
+        }
+    }
+    
+    // 没有任何文件时通常是打包错误，不把什么都没做的更新报告为成功
+    if package_files.is_empty() {
+        log::error!("压缩包内 {:?} 中没有任何文件", zip_inner_path);
+        return Err((FailureKind::Extraction, io::Error::new(io::ErrorKind::InvalidData, dict.error_package_empty(zip_inner_path))));
+    }
+    
+    // 计算需要写入的总字节数，并检查目标磁盘剩余空间
+    let total_bytes: u64 = files.iter()
+        .filter(|(src, _)| !is_symlink(src))
+        .filter_map(|(src, _)| fs::metadata(src).ok())
+        .map(|m| m.len())
+        .sum();
+    check_free_space(&target, total_bytes, dict).map_err(failed(FailureKind::Copy))?;
+    
+    Ok(Mapping {
+        inner: zip_inner_path.to_string(),
+        target,
+        copy_set: CopySet { files, total_bytes, phase_start: 0, phase_total: total_bytes },
+        package_files,
+        excluded_files,
+        unchanged_files,
+        newer_files,
+        prune: config.prune && !single_file,
+        checkpoint: None,
+    })
+}
+
+// 发送替换文件状态、总文件数和总字节数
+fn send_copying_status(dict: &'static LangDict, sender: &EventSender, mapping: &Mapping) {
+    let copying_status = if mapping.excluded_files > 0 || mapping.unchanged_files > 0 {
+        log::info!("共跳过 {} 个被过滤的文件，{} 个未变化的文件", mapping.excluded_files, mapping.unchanged_files);
+        dict.status_copying_skipped(mapping.excluded_files, mapping.unchanged_files)
+    } else {
+        dict.status_copying.to_string()
+    };
+    emit(sender, UpdateEvent::Status(copying_status));
+    emit(sender, UpdateEvent::TotalFiles(mapping.copy_set.files.len()));
+    emit(sender, UpdateEvent::Bytes(0, mapping.copy_set.total_bytes));
+}
+
+// 将一个映射的新版本写入目标目录：复制文件并删除新版本中已不存在的文件
+#[allow(clippy::too_many_arguments)]
+fn apply_mapping(
+    config: &UpdateConfig,
+    dict: &'static LangDict,
+    sender: &EventSender,
+    mapping: &Mapping,
+    filter: &FileFilter,
+    exe_path: &Path,
+    rollback: &Rollback,
+    conflicts: &Conflicts,
+    watchdog: &Watchdog,
+    audit: &Audit,
+    dedup: &Dedup,
+) -> io::Result<Applied> {
+    send_copying_status(dict, sender, mapping);
+    // 安装到子目录时先创建该目录，失败回滚时一并删除
+    if config.target_subdir.is_some() {
+        rollback.create_dir_all(&mapping.target)?;
+    }
+    let running_exe = running_exe(config, exe_path);
+    let apply = |dir: &Path, rollback: &Rollback| -> io::Result<Applied> {
+        let mut applied = copy_files(config, dict, sender, &mapping.copy_set, dir, running_exe.as_deref(), rollback, conflicts, watchdog, audit, dedup, mapping.checkpoint.as_ref())?;
+        if mapping.prune {
+            let prunable = find_prunable(dir, &mapping.package_files, filter, exe_path, &config.self_suffix);
+            let failures = prune_files(config, dict, sender, dir, &prunable, rollback, audit)?;
+            applied.deleted_files = prunable.len() - failures.len();
+            applied.failures.extend(failures);
+        }
+        Ok(applied)
+    };
+    
+    // 原子替换模式下在临时目录中组装新版本后交换目录，无法交换时改为直接更新
+    if config.atomic {
+        // 临时目录中的修改不需要回滚，交换本身记录到共享的回滚日志中
+        let swapped = atomic_swap_update(dict, sender, watchdog, rollback, &mapping.target, config.preserve_mtime, config.fsync, |staging| {
+            apply(staging, &Rollback::new(config.temp_dir.as_deref())?)
+        })?;
+        if let Some(applied) = swapped {
+            return Ok(applied);
+        }
+        emit(sender, UpdateEvent::Status(dict.status_swap_fallback.to_string()));
+    }
+    
+    // 直接更新目标目录，修改记录到回滚日志中
+    apply(&mapping.target, rollback)
+}
+
+// 一个映射实际写入目标目录的结果：复制和删除的文件数，以及继续执行模式下失败的文件
+#[derive(Default)]
+struct Applied {
+    failures: Failures,
+    copied_files: usize,
+    copied_bytes: u64,
+    deleted_files: usize,
+    // 被占用而登记为重启时替换的文件数
+    pending_reboot: usize,
+    // 断点中记录为已完成而跳过的文件数
+    resumed_files: usize,
+}
+
+// 待复制的文件（源路径和相对于指定目录的路径）及总字节数
+struct CopySet {
+    files: Vec<(PathBuf, PathBuf)>,
+    total_bytes: u64,
+    // 复制阶段中此前的映射已复制的字节数和所有映射的总字节数
+    phase_start: u64,
+    phase_total: u64,
+}
+
+impl CopySet {
+    // 已复制指定字节数时整个复制阶段的完成比例
+    fn phase_fraction(&self, copied: u64) -> f32 {
+        if self.phase_total == 0 {
+            1.0
+        } else {
+            (self.phase_start + copied) as f32 / self.phase_total as f32
+        }
+    }
+}
+
+// 在目标目录旁的临时目录中组装完整的新版本，成功后将目标目录重命名为.bak并换上新目录，交换记录到回滚日志中，
+// .bak在全部映射成功后才删除。组装前先确认可以交换：无法在目标目录旁创建临时目录或无法重命名目标目录
+// （如跨文件系统、目录中有文件被占用）时返回None，此时尚未写入任何文件，由调用方改为直接更新
+#[allow(clippy::too_many_arguments)]
+fn atomic_swap_update<T>(
+    dict: &'static LangDict,
+    sender: &EventSender,
+    watchdog: &Watchdog,
+    rollback: &Rollback,
+    target: &Path,
+    preserve_mtime: bool,
+    fsync: bool,
+    apply: impl FnOnce(&Path) -> io::Result<T>,
+) -> io::Result<Option<T>> {
+    let parent = target.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    let target_name = target.file_name().unwrap_or_default().to_string_lossy().into_owned();
+    let staging = match tempfile::Builder::new().prefix(&format!(".{}.staging", target_name)).tempdir_in(parent) {
+        Ok(staging) => staging,
+        Err(e) => {
+            log::warn!("无法在 {:?} 中创建临时目录: {}", parent, e);
+            return Ok(None);
+        }
+    };
+    let backup = self_update::with_suffix(target, ".bak");
+    if backup.exists() {
+        fs::remove_dir_all(&backup)?;
+    }
+    let had_target = target.exists();
+    // 临时目录与目标目录在同一个上级目录中，能重命名目标目录就能交换；先试着重命名一次再改回
+    if had_target {
+        if let Err(e) = fs::rename(target, &backup) {
+            log::warn!("无法重命名目标目录，改为直接更新: {}", e);
+            return Ok(None);
+        }
+        fs::rename(&backup, target)?;
+    }
+    
+    // 先复制现有文件，保证新目录中保留未被更新包覆盖的文件
+    emit(sender, UpdateEvent::Status(dict.status_staging.to_string()));
+    log::info!("在临时目录中组装新版本: {:?}", staging.path());
+    if target.is_dir() {
+        // 复制现有文件不显示进度，但仍需报告给超时监视
+        let stats = copy_tree(target, staging.path(), preserve_mtime, |_| watchdog.progress(dict))?;
+        log::info!("已复制 {} 个现有文件，共 {} 字节", stats.files, stats.bytes);
+    }
+    let result = apply(staging.path())?;
+    // 交换前把新目录的全部内容写入磁盘，包括从原目录复制的文件
+    if fsync {
+        sync_tree(staging.path())?;
+    }
+    
+    // 组装期间目标目录又变得无法重命名时更新失败，目标目录保持不变，由调用方回滚此前的映射
+    emit(sender, UpdateEvent::Status(dict.status_swapping.to_string()));
+    // 临时目录默认仅当前用户可访问，交换前沿用原目录的权限
+    let permissions_from = if had_target { target } else { parent };
+    fs::set_permissions(staging.path(), fs::metadata(permissions_from)?.permissions())?;
+    if had_target {
+        fs::rename(target, &backup)?;
+    }
+    let staging_path = staging.keep();
+    if let Err(e) = fs::rename(&staging_path, target) {
+        if had_target {
+            fs::rename(&backup, target)?;
+        }
+        let _ = fs::remove_dir_all(&staging_path);
+        return Err(e);
+    }
+    rollback.record_swap(target, had_target.then_some(backup.as_path()));
+    log::info!("已交换目录: {:?} -> {:?}", staging_path, target);
+    if fsync {
+        sync_path(parent)?;
+    }
+    Ok(Some(result))
+}
+
+// 递归复制目录中的所有文件，每个条目复制前报告进度，回调返回错误时中止复制
+pub(crate) fn copy_tree(src: &Path, dest: &Path, preserve_mtime: bool, mut progress: impl FnMut(Progress) -> io::Result<()>) -> io::Result<Stats> {
+    let entries: Vec<_> = WalkDir::new(src).into_iter().filter_map(|e| e.ok()).collect();
+    let mut stats = Stats::default();
+    progress(Progress::Total(entries.len()))?;
+    for (i, entry) in entries.iter().enumerate() {
+        let relative_path = entry.path().strip_prefix(src).map_err(io::Error::other)?;
+        progress(Progress::Entry(i + 1, entries.len(), relative_path.display().to_string()))?;
+        let dest_path = dest.join(relative_path);
+        if is_symlink(entry.path()) {
+            copy_symlink(entry.path(), &dest_path)?;
+            stats.files += 1;
+        } else if entry.file_type().is_dir() {
+            fs::create_dir_all(&dest_path)?;
+        } else if entry.file_type().is_file() {
+            stats.bytes += fs::copy(entry.path(), &dest_path)?;
+            stats.files += 1;
+            if preserve_mtime {
+                copy_mtime(entry.path(), &dest_path)?;
+            }
+        }
+    }
+    Ok(stats)
+}
+
+// 将收集到的文件复制到目标目录，所有修改都记录到回滚日志中
+#[allow(clippy::too_many_arguments)]
+fn copy_files(
+    config: &UpdateConfig,
+    dict: &'static LangDict,
+    sender: &EventSender,
+    copy_set: &CopySet,
+    current_dir: &Path,
+    running_exe: Option<&Path>,
+    rollback: &Rollback,
+    conflicts: &Conflicts,
+    watchdog: &Watchdog,
+    audit: &Audit,
+    dedup: &Dedup,
+    checkpoint: Option<&Checkpoint>,
+) -> io::Result<Applied> {
+    let files = &copy_set.files;
+    let total_files = files.len();
+    let total_bytes = copy_set.total_bytes;
+    
+    // 先按顺序创建所有目标目录，保证父目录先于子目录创建
+    let mut dirs: Vec<PathBuf> = files.iter()
+        .filter_map(|(_, relative_path)| current_dir.join(relative_path).parent().map(Path::to_path_buf))
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+    for dir in &dirs {
+        // 在慢速的网络驱动器上创建大量目录也算作进度，不会触发超时
+        watchdog.progress(dict)?;
+        let mut attempt = 0;
+        retry(config.retries + 1, RETRY_DELAY, || {
+            if attempt > 0 {
+                emit(sender, UpdateEvent::Status(dict.status_retrying(&dir.display().to_string(), attempt, config.retries)));
+            }
+            attempt += 1;
+            rollback.create_dir_all(dir)
+        })?;
+    }
+    
+    // 使用线程池并发复制文件到目标目录
+    log::info!("开始复制文件，并发数: {}", config.jobs);
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(config.jobs)
+        .build()
+        .map_err(io::Error::other)?;
+    let current_file = AtomicUsize::new(0);
+    let copied_bytes = AtomicU64::new(0);
+    let failures = Mutex::new(Vec::new());
+    // 实际写入的文件数和字节数，不包括跳过和失败的文件
+    let written_files = AtomicUsize::new(0);
+    let written_bytes = AtomicU64::new(0);
+    let pending_reboot = AtomicUsize::new(0);
+    let resumed_files = AtomicUsize::new(0);
+    // 保留硬链接时，同一个inode的后续路径在其余文件复制完成后链接到第一个路径在目标目录中的副本
+    let links = hard_link_sources(files, config.preserve_hardlinks);
+    let linked: Mutex<HashMap<PathBuf, PathBuf>> = Mutex::new(HashMap::new());
+    
+    // 累计已完成的字节数并发送进度
+    let report_bytes = |bytes: u64| {
+        let copied = copied_bytes.fetch_add(bytes, Ordering::SeqCst) + bytes;
+        emit(sender, UpdateEvent::Bytes(copied, total_bytes));
+        emit(sender, UpdateEvent::PhaseProgress { phase: Phase::Copy, fraction: copy_set.phase_fraction(copied) });
+    };
+    
+    // 复制单个文件，失败时返回错误
+    let copy_one = |entry_path: &Path, relative_path: &Path| -> io::Result<()> {
+        let dest_path = current_dir.join(relative_path);
+        let final_dest_path = staged_destination(&dest_path, running_exe, &config.self_suffix);
+        
+        // 发送当前复制的文件名称和进度
+        let current = current_file.fetch_add(1, Ordering::SeqCst) + 1;
+        emit(sender, UpdateEvent::Progress { phase: Phase::Copy, current, total: total_files, file: relative_path.display().to_string() });
+        
+        // 上次运行中已成功复制的文件不再复制，其大小仍计入已完成的进度
+        if checkpoint.is_some_and(|checkpoint| checkpoint.is_done(relative_path)) {
+            log::info!("跳过断点中已完成的文件: {:?}", relative_path);
+            let bytes = fs::metadata(entry_path)?.len();
+            report_bytes(bytes);
+            audit.record(audit::Action::Skip, Some(entry_path), &dest_path, bytes, Ok(()));
+            resumed_files.fetch_add(1, Ordering::SeqCst);
+            return Ok(());
+        }
+        
+        // 用户选择跳过的受保护文件不复制，其大小仍计入已完成的进度
+        if !conflicts.should_overwrite(&dest_path, relative_path, sender, watchdog) {
+            let bytes = fs::metadata(entry_path)?.len();
+            report_bytes(bytes);
+            audit.record(audit::Action::Skip, Some(entry_path), &dest_path, bytes, Ok(()));
+            return Ok(());
+        }
+        
+        log::info!("复制文件: {:?} -> {:?}", entry_path, final_dest_path);
+        rollback.record_file(&final_dest_path)?;
+        if is_symlink(entry_path) {
+            copy_symlink(entry_path, &final_dest_path)?;
+            audit.record(audit::Action::Copy, Some(entry_path), &final_dest_path, 0, Ok(()));
+            written_files.fetch_add(1, Ordering::SeqCst);
+            return Ok(());
+        }
+        // 第一个路径没有复制（如被跳过或失败）时照常复制；无法创建硬链接（如目标文件系统不支持）时也改为复制
+        #[cfg(unix)]
+        if let Some(first) = links.get(relative_path) && let Some(first_dest) = linked.lock().unwrap().get(first).cloned() {
+            match hard_link(&first_dest, &final_dest_path) {
+                Ok(()) => {
+                    log::info!("创建硬链接: {:?} -> {:?}", final_dest_path, first_dest);
+                    let bytes = fs::metadata(entry_path)?.len();
+                    report_bytes(bytes);
+                    audit.record(audit::Action::Link, Some(entry_path), &final_dest_path, 0, Ok(()));
+                    written_files.fetch_add(1, Ordering::SeqCst);
+                    return Ok(());
+                },
+                Err(e) => log::warn!("无法创建硬链接，改为复制: {:?}: {}", final_dest_path, e),
+            }
+        }
+        // 去重模式下内容相同的文件已写入过其他目标位置时从该副本复制，不再从更新包复制
+        let digest = dedup.digest(entry_path)?;
+        let source = match digest.as_deref().and_then(|digest| dedup.copied(digest)) {
+            Some(first_dest) => {
+                log::info!("内容与已复制的文件相同，从副本复制: {:?} -> {:?}", first_dest, final_dest_path);
+                first_dest
+            },
+            None => entry_path.to_path_buf(),
+        };
+        // 按块报告大文件的复制进度，重试时已报告过的字节不重复计入
+        let mut attempt = 0;
+        let mut reported = 0;
+        let bytes = retry(config.retries + 1, RETRY_DELAY, || {
+            if attempt > 0 {
+                emit(sender, UpdateEvent::Status(dict.status_retrying(&relative_path.display().to_string(), attempt, config.retries)));
+            }
+            attempt += 1;
+            let mut written = 0;
+            copy_file(&source, &final_dest_path, config.fsync, |chunk| {
+                written += chunk;
+                if written > reported {
+                    report_bytes(written - reported);
+                    reported = written;
+                }
+                // 只刷新超时计时，已超时时在开始复制下一个文件前中止
+                let _ = watchdog.progress(dict);
+            })
+        });
+        let bytes = match bytes {
+            Ok(bytes) => bytes,
+            // 重试后仍被其他进程占用时，写入暂存文件并登记在下次重启时替换
+            #[cfg(windows)]
+            Err(e) if config.on_locked == crate::LockPolicy::Stage && reboot::is_locked(&e) => {
+                let staged = self_update::staged_path(&final_dest_path, &config.self_suffix);
+                log::warn!("文件被占用，改为在重启时替换: {:?} -> {:?}", staged, final_dest_path);
+                rollback.record_file(&staged)?;
+                let bytes = copy_file(entry_path, &staged, config.fsync, |_| {})?;
+                if config.preserve_mtime {
+                    copy_mtime(entry_path, &staged)?;
+                }
+                reboot::replace_on_reboot(&staged, &final_dest_path)?;
+                report_bytes(bytes.saturating_sub(reported));
+                audit.record(audit::Action::Stage, Some(entry_path), &final_dest_path, bytes, Ok(()));
+                pending_reboot.fetch_add(1, Ordering::SeqCst);
+                return Ok(());
+            },
+            Err(e) => return Err(e),
+        };
+        if config.preserve_mtime {
+            copy_mtime(entry_path, &final_dest_path)?;
+        }
+        
+        // 校验模式下重新读取目标文件并与源文件比较
+        if config.verify && !verify_copy(entry_path, &final_dest_path)? {
+            log::error!("文件校验失败: {:?}", final_dest_path);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, dict.error_verify_failed(&relative_path.display().to_string())));
+        }
+        audit.record(audit::Action::Copy, Some(entry_path), &final_dest_path, bytes, Ok(()));
+        written_files.fetch_add(1, Ordering::SeqCst);
+        written_bytes.fetch_add(bytes, Ordering::SeqCst);
+        if let Some(digest) = digest {
+            dedup.record(digest, &final_dest_path);
+        }
+        if !links.is_empty() {
+            linked.lock().unwrap().insert(relative_path.to_path_buf(), final_dest_path);
+        }
+        Ok(())
+    };
+    
+    let process = |(entry_path, relative_path): &(PathBuf, PathBuf)| -> io::Result<()> {
+        // 超时时中止整个更新，不计入继续执行模式下的失败文件
+        watchdog.progress(dict)?;
+        // 继续执行模式下记录失败的文件，其余文件照常复制
+        let result = copy_one(entry_path, relative_path);
+        if let Err(e) = &result {
+            audit.record(audit::Action::Copy, Some(entry_path), &current_dir.join(relative_path), 0, Err(e));
+        }
+        match result {
+            Err(e) if config.continue_on_error => {
+                log::error!("复制文件失败，继续复制其余文件: {:?}: {}", relative_path, e);
+                failures.lock().unwrap().push((relative_path.clone(), e));
+                Ok(())
+            },
+            Ok(()) => {
+                if let Some(checkpoint) = checkpoint {
+                    checkpoint.mark(relative_path);
+                }
+                Ok(())
+            },
+            result => result,
+        }
+    };
+    pool.install(|| files.par_iter().filter(|(_, relative_path)| !links.contains_key(relative_path)).try_for_each(process))?;
+    files.iter().filter(|(_, relative_path)| links.contains_key(relative_path)).try_for_each(process)?;
+    
+    // 同步写入了文件的目录及其上级目录，保证新建的目录项也已写入磁盘
+    if config.fsync {
+        sync_dirs(current_dir, &dirs)?;
+    }
+    Ok(Applied {
+        failures: failures.into_inner().unwrap(),
+        copied_files: written_files.into_inner(),
+        copied_bytes: written_bytes.into_inner(),
+        deleted_files: 0,
+        pending_reboot: pending_reboot.into_inner(),
+        resumed_files: resumed_files.into_inner(),
+    })
+}
+
+// 找出目标目录中新版本已不存在的文件（相对路径），排除模式匹配的文件和更新程序自身的文件不会被删除
+fn find_prunable(current_dir: &Path, package_files: &HashSet<PathBuf>, filter: &FileFilter, exe_path: &Path, self_suffix: &str) -> Vec<PathBuf> {
+    let exe_name = exe_path.file_name().unwrap_or_default();
+    let updater_names = [
+        exe_name.to_os_string(),
+        self_update::staged_path(Path::new(exe_name), self_suffix).into_os_string(),
+        self_update::old_path(Path::new(exe_name)).into_os_string(),
+    ];
+    let protected: Vec<PathBuf> = [Some(exe_path), logging::log_file()]
+        .into_iter()
+        .flatten()
+        .filter_map(|path| fs::canonicalize(path).ok())
+        .collect();
+    
+    let mut prunable = Vec::new();
+    for entry in WalkDir::new(current_dir).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() && !is_symlink(entry.path()) {
+            continue;
+        }
+        let Ok(relative_path) = entry.path().strip_prefix(current_dir) else {
+            continue;
+        };
+        if package_files.contains(relative_path) || filter.is_excluded(relative_path) || relative_path == Path::new(receipt::RECEIPT_NAME) || relative_path == Path::new(checkpoint::CHECKPOINT_NAME) {
+            continue;
+        }
+        // 不删除正在运行的更新程序、暂存的新版本以及日志文件
+        let is_updater_name = updater_names.iter().any(|name| entry.file_name() == name.as_os_str());
+        let is_protected = fs::canonicalize(entry.path()).is_ok_and(|path| protected.contains(&path));
+        if is_updater_name || is_protected {
+            log::info!("保留更新程序文件: {:?}", entry.path());
+            continue;
+        }
+        prunable.push(relative_path.to_path_buf());
+    }
+    prunable
+}
+
+// 删除新版本中已不存在的文件，删除前备份以便失败时回滚
+fn prune_files(
+    config: &UpdateConfig,
+    dict: &'static LangDict,
+    sender: &EventSender,
+    current_dir: &Path,
+    prunable: &[PathBuf],
+    rollback: &Rollback,
+    audit: &Audit,
+) -> io::Result<Failures> {
+    let mut failures = Vec::new();
+    for relative_path in prunable {
+        let path = current_dir.join(relative_path);
+        emit(sender, UpdateEvent::Status(dict.status_pruning(&relative_path.display().to_string())));
+        log::info!("删除旧文件: {:?}", path);
+        let bytes = fs::symlink_metadata(&path).map(|metadata| metadata.len()).unwrap_or(0);
+        let result = rollback.record_file(&path).and_then(|_| fs::remove_file(&path));
+        audit.record(audit::Action::Delete, None, &path, bytes, result.as_ref().map(|_| ()));
+        match result {
+            Err(e) if config.continue_on_error => {
+                log::error!("删除文件失败，继续执行: {:?}: {}", path, e);
+                failures.push((relative_path.clone(), e));
+            },
+            result => result?,
+        }
+    }
+    if config.fsync {
+        let dirs: Vec<PathBuf> = prunable.iter().filter_map(|path| current_dir.join(path).parent().map(Path::to_path_buf)).collect();
+        sync_dirs(current_dir, &dirs)?;
+    }
+    Ok(failures)
+}
+
+// 正在运行的更新程序的规范路径，用于判断要覆盖的文件是否就是它自身；--no-self-stage 时返回None，不暂存任何文件
+fn running_exe(config: &UpdateConfig, exe_path: &Path) -> Option<PathBuf> {
+    if !config.self_stage {
+        return None;
+    }
+    fs::canonicalize(exe_path).ok()
+}
+
+// 计算文件的实际写入路径，Windows上无法覆盖正在运行的程序，当前运行的可执行文件改为写入带暂存后缀（默认.new）的文件。
+// 按规范路径比较，其他目录中与更新程序同名的文件照常覆盖
+#[cfg(windows)]
+fn staged_destination(dest_path: &Path, running_exe: Option<&Path>, suffix: &str) -> PathBuf {
+    if is_running_exe(dest_path, running_exe) {
+        let new_path = self_update::staged_path(dest_path, suffix);
+        log::info!("重命名当前运行文件: {:?} -> {:?}", dest_path, new_path);
+        new_path
+    } else {
+        dest_path.to_path_buf()
+    }
+}
+
+// 目标路径是否就是正在运行的更新程序（running_exe 为其规范路径）
+#[cfg(any(windows, test))]
+fn is_running_exe(dest_path: &Path, running_exe: Option<&Path>) -> bool {
+    running_exe.is_some_and(|exe| fs::canonicalize(dest_path).is_ok_and(|path| path == exe))
+}
+
+// Unix上可以直接删除正在运行的程序，因此直接覆盖而不需要暂存
+#[cfg(not(windows))]
+fn staged_destination(dest_path: &Path, _running_exe: Option<&Path>, _suffix: &str) -> PathBuf {
+    dest_path.to_path_buf()
+}
+
+// 复制文件到目标路径，Unix上先删除已有文件，避免覆盖正在运行的程序时出现"Text file busy"
+fn copy_file(src: &Path, dest: &Path, fsync: bool, on_bytes: impl FnMut(u64)) -> io::Result<u64> {
+    #[cfg(not(windows))]
+    match fs::remove_file(dest) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {},
+    }
+    let bytes = copy_with_progress(src, dest, COPY_CHUNK_SIZE, on_bytes)?;
+    // 确保文件内容已写入磁盘，而不只是在系统缓存中
+    if fsync {
+        fs::File::open(dest)?.sync_all()?;
+    }
+    Ok(bytes)
+}
+
+// 将文件或目录写入磁盘；同步目录可确保其中新建、重命名和删除的条目持久化，Windows上无法打开目录，只同步文件
+fn sync_path(path: &Path) -> io::Result<()> {
+    if cfg!(windows) && path.is_dir() {
+        return Ok(());
+    }
+    fs::File::open(path)?.sync_all()
+}
+
+// 同步根目录下的指定目录以及它们在根目录内的所有上级目录，每个目录只同步一次
+fn sync_dirs(root: &Path, dirs: &[PathBuf]) -> io::Result<()> {
+    let mut synced = HashSet::new();
+    for dir in dirs {
+        for ancestor in dir.ancestors().take_while(|parent| parent.starts_with(root)) {
+            if synced.insert(ancestor.to_path_buf()) {
+                sync_path(ancestor)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// 同步目录树中的所有文件和目录，符号链接除外
+fn sync_tree(root: &Path) -> io::Result<()> {
+    for entry in WalkDir::new(root).contents_first(true) {
+        let entry = entry?;
+        if !is_symlink(entry.path()) {
+            sync_path(entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+// 每次读写的块大小，复制大文件时每写完一块报告一次进度
+const COPY_CHUNK_SIZE: usize = 1024 * 1024;
+
+// 分块复制文件内容和权限，每写完一块以该块的字节数调用 on_bytes，返回复制的总字节数
+fn copy_with_progress(src: &Path, dst: &Path, chunk_size: usize, mut on_bytes: impl FnMut(u64)) -> io::Result<u64> {
+    let mut reader = fs::File::open(src)?;
+    let permissions = reader.metadata()?.permissions();
+    let mut writer = fs::File::create(dst)?;
+    let mut buffer = vec![0; chunk_size];
+    let mut total = 0;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        writer.write_all(&buffer[..read])?;
+        total += read as u64;
+        on_bytes(read as u64);
+    }
+    writer.set_permissions(permissions)?;
+    Ok(total)
+}
+
+// 保留硬链接时找出更新包中指向同一个inode的文件：返回每个后续路径对应的第一个路径（均为相对路径），
+// 第一个路径照常复制，后续路径在目标目录中链接到它的副本；只在Unix上检查
+#[cfg(unix)]
+fn hard_link_sources(files: &[(PathBuf, PathBuf)], enabled: bool) -> HashMap<PathBuf, PathBuf> {
+    use std::collections::hash_map::Entry;
+    use std::os::unix::fs::MetadataExt;
+    
+    let mut links = HashMap::new();
+    if !enabled {
+        return links;
+    }
+    let mut first_paths: HashMap<(u64, u64), &PathBuf> = HashMap::new();
+    for (entry_path, relative_path) in files {
+        let Ok(metadata) = fs::symlink_metadata(entry_path) else {
+            continue;
+        };
+        if !metadata.is_file() || metadata.nlink() < 2 {
+            continue;
+        }
+        match first_paths.entry((metadata.dev(), metadata.ino())) {
+            Entry::Occupied(first) => {
+                links.insert(relative_path.clone(), first.get().to_path_buf());
+            },
+            Entry::Vacant(slot) => {
+                slot.insert(relative_path);
+            },
+        }
+    }
+    if !links.is_empty() {
+        log::info!("更新包中有 {} 个文件与其他文件是同一个inode，将在目标目录中创建硬链接", links.len());
+    }
+    links
+}
+
+#[cfg(not(unix))]
+fn hard_link_sources(_files: &[(PathBuf, PathBuf)], _enabled: bool) -> HashMap<PathBuf, PathBuf> {
+    HashMap::new()
+}
+
+// 在目标位置创建指向已复制文件的硬链接，目标位置已有的文件先删除（已由回滚日志备份）
+#[cfg(unix)]
+fn hard_link(original: &Path, link: &Path) -> io::Result<()> {
+    match fs::remove_file(link) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {},
+    }
+    fs::hard_link(original, link)
+}
+
+// 路径本身是否为符号链接，只在Unix上按符号链接处理
+#[cfg(unix)]
+fn is_symlink(path: &Path) -> bool {
+    path.is_symlink()
+}
+
+#[cfg(not(unix))]
+fn is_symlink(_path: &Path) -> bool {
+    false
+}
+
+// 路径本身或其在根目录下的某一级父目录是否为符号链接
+fn has_symlink_component(root: &Path, path: &Path) -> bool {
+    path.ancestors()
+        .take_while(|parent| parent.starts_with(root) && *parent != root)
+        .any(is_symlink)
+}
+
+// 在目标位置重新创建指向相同目标的符号链接，而不是复制链接指向的内容
+#[cfg(unix)]
+pub(crate) fn copy_symlink(src: &Path, dest: &Path) -> io::Result<()> {
+    let link_target = fs::read_link(src)?;
+    match fs::remove_file(dest) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        _ => {},
+    }
+    std::os::unix::fs::symlink(link_target, dest)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn copy_symlink(src: &Path, dest: &Path) -> io::Result<()> {
+    fs::copy(src, dest).map(|_| ())
+}
+
+// 符号链接的目标（相对于链接所在目录）解析后是否仍在根目录内
+#[cfg(unix)]
+fn symlink_contained(root: &Path, link: &Path, link_target: &Path) -> bool {
+    let Some(parent) = link.parent().and_then(|parent| parent.strip_prefix(root).ok()) else {
+        return false;
+    };
+    let mut depth = parent.components().count();
+    for component in link_target.components() {
+        match component {
+            std::path::Component::Normal(_) => depth += 1,
+            std::path::Component::CurDir => {},
+            std::path::Component::ParentDir if depth > 0 => depth -= 1,
+            _ => return false,
+        }
+    }
+    true
+}
+
+// 将源文件的修改时间应用到目标文件，fs::copy只复制内容和权限
+fn copy_mtime(src: &Path, dest: &Path) -> io::Result<()> {
+    let modified = fs::metadata(src)?.modified()?;
+    filetime::set_file_mtime(dest, FileTime::from_system_time(modified))
+}
+
+// 判断目标文件较新时允许的时间差，ZIP中记录的修改时间只精确到2秒
+const MTIME_TOLERANCE: Duration = Duration::from_secs(2);
+
+// 目标文件的修改时间是否晚于源文件，差距在允许范围内时不算较新；目标文件不存在时为假
+fn dest_newer(src: &Path, dst: &Path) -> io::Result<bool> {
+    let dst_modified = match fs::symlink_metadata(dst) {
+        Ok(meta) => meta.modified()?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    let src_modified = fs::symlink_metadata(src)?.modified()?;
+    Ok(dst_modified.duration_since(src_modified).is_ok_and(|newer| newer > MTIME_TOLERANCE))
+}
+
+// 判断源文件与目标文件是否不同：大小不同即为不同，大小和修改时间都相同视为相同，否则比较内容的摘要
+fn files_differ(src: &Path, dst: &Path) -> io::Result<bool> {
+    // 符号链接只比较链接目标
+    if is_symlink(src) || is_symlink(dst) {
+        return Ok(!is_symlink(src) || !is_symlink(dst) || fs::read_link(src)? != fs::read_link(dst)?);
+    }
+    let dst_meta = match fs::metadata(dst) {
+        Ok(meta) => meta,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(true),
+        Err(e) => return Err(e),
+    };
+    let src_meta = fs::metadata(src)?;
+    if !dst_meta.is_file() || src_meta.len() != dst_meta.len() {
+        return Ok(true);
+    }
+    if src_meta.modified()? == dst_meta.modified()? {
+        return Ok(false);
+    }
+    Ok(sha256_file(src)? != sha256_file(dst)?)
+}
+
+// 首次重试前的等待时间，之后每次翻倍
+const RETRY_DELAY: Duration = Duration::from_millis(200);
+
+// 对可能短暂失败的文件操作进行重试（如杀毒软件占用、Windows共享冲突），等待时间指数递增
+fn retry<T>(attempts: u32, delay: Duration, mut op: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+    let mut delay = delay;
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < attempts && is_transient(&e) => {
+                log::warn!("操作失败（第 {} 次），{:?} 后重试: {}", attempt, delay, e);
+                thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            },
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+// 只重试可能是暂时性的错误，文件不存在等错误直接失败
+fn is_transient(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::PermissionDenied
+            | io::ErrorKind::Other
+            | io::ErrorKind::ResourceBusy
+            | io::ErrorKind::Interrupted
+            | io::ErrorKind::TimedOut
+            | io::ErrorKind::WouldBlock
+    ) || is_sharing_violation(e)
+}
+
+// Windows上的共享冲突（ERROR_SHARING_VIOLATION / ERROR_LOCK_VIOLATION）没有对应的ErrorKind
+fn is_sharing_violation(e: &io::Error) -> bool {
+    cfg!(windows) && matches!(e.raw_os_error(), Some(32) | Some(33))
+}
+
+// 比较目标文件与源文件的长度和SHA-256摘要，一致时返回true
+fn verify_copy(src: &Path, dst: &Path) -> io::Result<bool> {
+    if fs::metadata(src)?.len() != fs::metadata(dst)?.len() {
+        return Ok(false);
+    }
+    Ok(sha256_file(src)? == sha256_file(dst)?)
+}
+
+// 计算文件的SHA-256摘要
+pub(crate) fn sha256_file(path: &Path) -> io::Result<Vec<u8>> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(hasher.finalize().to_vec())
+}
+
+// 确认第一个目标目录中已有 expect_files 指定的每个文件，缺少任何一个时说明目标目录可能指定错误
+pub(crate) fn check_expected_files(config: &UpdateConfig, dict: &LangDict) -> Result<(), Failed> {
+    let Some((_, target)) = update_targets(config).into_iter().next() else {
+        return Ok(());
+    };
+    for name in &config.expect_files {
+        if !contained_path(&target, Path::new(name)).is_some_and(|path| path.exists()) {
+            log::error!("目标目录中缺少必须存在的文件: {:?} 中的 {}", target, name);
+            return Err((FailureKind::InvalidConfig, io::Error::new(io::ErrorKind::NotFound, dict.error_expected_file_missing(name, &target.display().to_string()))));
+        }
+    }
+    Ok(())
+}
+
+// 在目标目录（尚未创建时为最近的已存在上级目录）中创建并删除一个临时文件，确认有写入权限
+fn check_writable(path: &Path, dict: &LangDict) -> io::Result<()> {
+    check_writable_with(path, dict, |sentinel| fs::OpenOptions::new().write(true).create_new(true).open(sentinel).map(drop))
+}
+
+// 与 check_writable 相同，由 create 创建临时文件，测试中可以模拟没有写入权限
+fn check_writable_with(path: &Path, dict: &LangDict, create: impl FnOnce(&Path) -> io::Result<()>) -> io::Result<()> {
+    let existing = path.ancestors()
+        .map(|p| if p.as_os_str().is_empty() { Path::new(".") } else { p })
+        .find(|p| p.is_dir())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, dict.error_volume_unknown(&path.display().to_string())))?;
+    
+    let sentinel = existing.join(format!(".updater-write-test-{}", std::process::id()));
+    if let Err(e) = create(&sentinel) {
+        log::error!("目标目录不可写: {:?}: {}", existing, e);
+        return Err(io::Error::new(io::ErrorKind::PermissionDenied, dict.error_target_not_writable(&long_path::display(existing), &e.to_string())));
+    }
+    fs::remove_file(&sentinel)
+}
+
+// 确认每个目标目录都可以写入，用于自检
+pub(crate) fn check_targets_writable(config: &UpdateConfig, dict: &LangDict) -> Result<(), Failed> {
+    for (_, target) in update_targets(config) {
+        check_writable(&long_path::extend(&target), dict).map_err(failed(FailureKind::Copy))?;
+    }
+    Ok(())
+}
+
+// 按更新包解压后的总大小检查每个目标磁盘的剩余空间，返回需要的字节数，用于自检
+pub(crate) fn check_targets_space(config: &UpdateConfig, dict: &LangDict) -> Result<u64, Failed> {
+    let needed = update_size(config, dict)?;
+    for (_, target) in update_targets(config) {
+        check_free_space(&long_path::extend(&target), needed, dict).map_err(failed(FailureKind::Copy))?;
+    }
+    Ok(needed)
+}
+
+// 列出更新包中的全部条目：ZIP只读取中央目录（加密的条目也不需要密码），tar.gz需要读一遍全部条目，目录则遍历其中的文件
+fn package_entries(package_path: &str, format: PackageFormat) -> io::Result<Vec<(String, u64, bool)>> {
+    if format == PackageFormat::TarGz {
+        let mut entries = Vec::new();
+        for entry in tar::Archive::new(GzDecoder::new(fs::File::open(package_path)?)).entries()? {
+            let entry = entry?;
+            let mut path = filter::normalize(&tar_entry_path(&entry)?);
+            let is_dir = entry.header().entry_type().is_dir();
+            if path.is_empty() {
+                continue;
+            }
+            if is_dir {
+                path.push('/');
+            }
+            entries.push((path, if is_dir { 0 } else { entry.size() }, is_dir));
+        }
+        return Ok(entries);
+    }
+    if format == PackageFormat::Dir {
+        let mut entries = Vec::new();
+        for entry in WalkDir::new(package_path).min_depth(1).sort_by_file_name().into_iter().filter_map(|e| e.ok()) {
+            let Ok(relative_path) = entry.path().strip_prefix(package_path) else {
+                continue;
+            };
+            let mut path = filter::normalize(relative_path);
+            if entry.file_type().is_dir() {
+                path.push('/');
+                entries.push((path, 0, true));
+            } else {
+                entries.push((path, entry.metadata().map_err(io::Error::other)?.len(), false));
+            }
+        }
+        return Ok(entries);
+    }
+    let mut archive = ZipArchive::new(fs::File::open(package_path)?).map_err(io::Error::other)?;
+    let mut entries = Vec::new();
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i).map_err(io::Error::other)?;
+        entries.push((entry.name().replace('\\', "/"), entry.size(), entry.is_dir()));
+    }
+    Ok(entries)
+}
+
+// 更新包中的全部目录（不以 / 结尾），包括压缩包中没有单独条目、只出现在文件路径中的上级目录
+fn package_dirs(entries: &[(String, u64, bool)]) -> BTreeSet<String> {
+    let mut dirs = BTreeSet::new();
+    for (path, _, is_dir) in entries {
+        let path = path.trim_end_matches('/');
+        dirs.extend(path.match_indices('/').map(|(i, _)| path[..i].to_string()));
+        if *is_dir {
+            dirs.insert(path.to_string());
+        }
+    }
+    dirs
+}
+
+// 列出更新包中的条目并解析每个压缩包内路径，标出将要复制的条目
+pub(crate) fn inspect_package(config: &UpdateConfig, dict: &LangDict) -> Result<Inspection, Failed> {
+    check_package(config, dict)?;
+    let entries = package_entries(&config.package_path, package_format(config).0).map_err(failed(FailureKind::Extraction))?;
+    let dirs = package_dirs(&entries);
+
+    // 未指定目标路径时也解析压缩包内路径，只检查更新包本身
+    let patterns: Vec<String> = if config.mappings.is_empty() {
+        vec![config.zip_inner_path.clone()]
+    } else {
+        config.mappings.iter().map(|(inner, _)| inner.clone()).collect()
+    };
+    let mut inner_paths = Vec::new();
+    for pattern in patterns {
+        let trimmed = pattern.trim_matches('/');
+        let matches = if trimmed.is_empty() {
+            vec![String::new()]
+        } else if filter::is_glob(trimmed) {
+            filter::matching_names(dirs.iter().map(String::as_str), trimmed).map_err(failed(FailureKind::InvalidConfig))?
+        } else if dirs.contains(trimmed) || entries.iter().any(|(path, _, _)| path == trimmed) {
+            vec![trimmed.to_string()]
+        } else {
+            Vec::new()
+        };
+        log::info!("压缩包内路径 {:?} 匹配: {:?}", pattern, matches);
+        inner_paths.push(InnerPath { pattern, matches });
+    }
+
+    let entries = entries.into_iter().map(|(path, size, is_dir)| {
+        let in_inner_path = inner_paths.iter().flat_map(|inner| &inner.matches).any(|inner| {
+            inner.is_empty() || path.trim_end_matches('/') == inner || path.starts_with(&format!("{}/", inner))
+        });
+        PackageEntry { path, size, is_dir, in_inner_path }
+    }).collect();
+    Ok(Inspection { entries, inner_paths })
+}
+
+// 确认更新包存在后计算其中全部文件的总字节数
+pub(crate) fn update_size(config: &UpdateConfig, dict: &LangDict) -> Result<u64, Failed> {
+    check_package(config, dict)?;
+    package_size(&config.package_path, package_format(config).0).map_err(failed(FailureKind::Extraction))
+}
+
+// 更新包中全部文件的总字节数：ZIP只读取中央目录，不解压；tar.gz需要读一遍全部条目；目录则累加其中的文件大小
+fn package_size(package_path: &str, format: PackageFormat) -> io::Result<u64> {
+    if format == PackageFormat::TarGz {
+        let mut total = 0;
+        for entry in tar::Archive::new(GzDecoder::new(fs::File::open(package_path)?)).entries()? {
+            let entry = entry?;
+            if entry.header().entry_type().is_file() {
+                total += entry.size();
+            }
+        }
+        return Ok(total);
+    }
+    if format == PackageFormat::Dir {
+        let mut total = 0;
+        for entry in WalkDir::new(package_path).into_iter().filter_map(|e| e.ok()) {
+            if entry.file_type().is_file() {
+                total += entry.metadata().map_err(io::Error::other)?.len();
+            }
+        }
+        return Ok(total);
+    }
+    let mut archive = ZipArchive::new(fs::File::open(package_path)?).map_err(io::Error::other)?;
+    let mut total = 0;
+    for i in 0..archive.len() {
+        total += archive.by_index_raw(i).map_err(io::Error::other)?.size();
+    }
+    Ok(total)
+}
+
+// 检查目标路径所在磁盘是否有足够的剩余空间
+fn check_free_space(path: &Path, needed: u64, dict: &LangDict) -> io::Result<()> {
+    // 目标目录可能尚未创建，向上查找最近的已存在目录（相对路径最终为当前目录）
+    let existing = path.ancestors()
+        .map(|p| if p.as_os_str().is_empty() { Path::new(".") } else { p })
+        .find(|p| p.exists())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, dict.error_volume_unknown(&path.display().to_string())))?;
+    
+    let available = fs2::available_space(existing)?;
+    log::info!("需要空间: {} 字节, 可用空间: {} 字节", needed, available);
+    
+    if available < needed {
+        return Err(io::Error::new(
+            io::ErrorKind::StorageFull,
+            dict.error_insufficient_space(needed, available),
+        ));
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{self, read_tree, write_files, write_tar_gz, write_zip};
+
+    fn entries(tree: &[(&str, &str)]) -> std::collections::BTreeMap<String, Vec<u8>> {
+        tree.iter().map(|(path, content)| (path.to_string(), content.as_bytes().to_vec())).collect()
+    }
+
+    #[test]
+    fn extract_to_writes_entries_and_reports_progress() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("app/", b""), ("app/a.txt", b"A"), ("app/sub/b.txt", b"BB"), ("empty/", b"")]);
+        let dest = dir.path().join("out");
+        
+        let mut events = Vec::new();
+        let stats = extract_to(&package, &dest, None, true, |_| true, |_, _, _| false, |progress| {
+            events.push(UpdateEvent::from(progress));
+            Ok(())
+        }).unwrap();
+        assert_eq!(stats.files, 2);
+        assert_eq!(stats.bytes, 3);
+        assert_eq!(read_tree(&dest), entries(&[("app/a.txt", "A"), ("app/sub/b.txt", "BB")]));
+        assert!(dest.join("empty").is_dir());
+        assert!(matches!(events[0], UpdateEvent::TotalFiles(4)));
+        assert!(matches!(&events[4], UpdateEvent::Progress { phase: Phase::Extract, current: 4, total: 4, .. }));
+    }
+
+    #[test]
+    fn extract_to_stops_when_progress_fails() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("a.txt", b"A"), ("b.txt", b"B")]);
+        let dest = dir.path().join("out");
+        
+        let result = extract_to(&package, &dest, None, true, |_| true, |_, _, _| false, |progress| match progress {
+            Progress::Entry(2, ..) => Err(io::Error::other("cancelled")),
+            _ => Ok(()),
+        });
+        assert!(result.is_err());
+        assert_eq!(read_tree(&dest), entries(&[("a.txt", "A")]));
+    }
+
+    #[test]
+    fn copy_tree_copies_nested_files() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        write_files(&src, &[("a.txt", b"A"), ("sub/b.txt", b"B")]);
+        let dest = dir.path().join("dest");
+        
+        let stats = copy_tree(&src, &dest, true, |_| Ok(())).unwrap();
+        assert_eq!(stats.files, 2);
+        assert_eq!(read_tree(&dest), entries(&[("a.txt", "A"), ("sub/b.txt", "B")]));
+    }
+
+    #[test]
+    fn update_copies_inner_path_into_target() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("app/a.txt", b"new"), ("app/sub/b.txt", b"B"), ("other.txt", b"X")]);
+        let target = dir.path().join("target");
+        write_files(&target, &[("a.txt", b"old"), ("keep.txt", b"K")]);
+        
+        let config = UpdateConfig { zip_inner_path: "app".to_string(), ..test_util::config(&package, &target) };
+        let (result, events) = test_util::run(&config);
+        let summary = result.unwrap();
+        assert_eq!(summary.files_copied, 2);
+        assert!(matches!(events.last(), Some(UpdateEvent::Complete)));
+        assert_eq!(read_tree(&target), entries(&[("a.txt", "new"), ("keep.txt", "K"), ("sub/b.txt", "B")]));
+        assert!(package.exists());
+    }
+
+    #[test]
+    fn failed_update_rolls_back_target() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("a.txt", b"new"), ("new.txt", b"N"), ("z.txt", b"Z")]);
+        let target = dir.path().join("target");
+        // 目标目录中与更新包文件同名的目录无法被文件覆盖，更新失败
+        write_files(&target, &[("a.txt", b"old"), ("z.txt/inner.txt", b"I")]);
+        
+        let config = UpdateConfig { jobs: 1, retries: 0, ..test_util::config(&package, &target) };
+        let (result, events) = test_util::run(&config);
+        assert!(result.is_err());
+        assert!(matches!(events.last(), Some(UpdateEvent::Error(..))));
+        assert_eq!(read_tree(&target), entries(&[("a.txt", "old"), ("z.txt/inner.txt", "I")]));
+    }
+
+    // 合成较多的小文件，单线程和多线程复制的结果一致
+    #[test]
+    fn parallel_copy_matches_serial_copy() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        let names: Vec<String> = (0..300).map(|i| format!("dir{}/file{}.bin", i % 10, i)).collect();
+        let contents: Vec<Vec<u8>> = (0..300).map(|i| vec![i as u8; 1024 + i]).collect();
+        let files: Vec<(&str, &[u8])> = names.iter().map(String::as_str).zip(contents.iter().map(Vec::as_slice)).collect();
+        write_zip(&package, &files);
+        
+        let mut trees = Vec::new();
+        for jobs in [1, 8] {
+            let target = dir.path().join(format!("target{}", jobs));
+            let config = UpdateConfig { jobs, ..test_util::config(&package, &target) };
+            let summary = test_util::run(&config).0.unwrap();
+            assert_eq!(summary.files_copied, 300);
+            trees.push(read_tree(&target));
+        }
+        assert_eq!(trees[0], trees[1]);
+        assert_eq!(trees[0].len(), 300);
+    }
+
+    #[test]
+    fn verify_copy_detects_corrupted_destination() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src.bin");
+        let dst = dir.path().join("dst.bin");
+        fs::write(&src, b"0123456789").unwrap();
+        fs::copy(&src, &dst).unwrap();
+        assert!(verify_copy(&src, &dst).unwrap());
+        
+        // 长度相同但内容被破坏
+        fs::write(&dst, b"0123456780").unwrap();
+        assert!(!verify_copy(&src, &dst).unwrap());
+        // 被截断
+        fs::write(&dst, b"01234").unwrap();
+        assert!(!verify_copy(&src, &dst).unwrap());
+        fs::remove_file(&dst).unwrap();
+        assert!(verify_copy(&src, &dst).is_err());
+    }
+
+    #[test]
+    fn prune_removes_files_missing_from_package() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("a.txt", b"new"), ("sub/b.txt", b"B")]);
+        let target = dir.path().join("target");
+        write_files(&target, &[("a.txt", b"old"), ("stale.txt", b"S"), ("sub/old.dll", b"D"), ("logs/today.log", b"L")]);
+        
+        let config = UpdateConfig { prune: true, exclude: vec!["logs/**".to_string()], ..test_util::config(&package, &target) };
+        let (result, events) = test_util::run(&config);
+        assert_eq!(result.unwrap().files_deleted, 2);
+        assert_eq!(read_tree(&target), entries(&[("a.txt", "new"), ("logs/today.log", "L"), ("sub/b.txt", "B")]));
+        // 每个删除的文件都有状态消息
+        let dict = crate::language::get_dict(config.lang);
+        for name in ["stale.txt", "sub/old.dll"] {
+            let status = dict.status_pruning(&PathBuf::from(name).display().to_string());
+            assert!(events.iter().any(|event| matches!(event, UpdateEvent::Status(text) if *text == status)), "{}", name);
+        }
+    }
+
+    #[test]
+    fn contained_path_rejects_escaping_paths() {
+        let root = Path::new("root");
+        assert_eq!(contained_path(root, Path::new("a/./b.txt")), Some(root.join("a").join("b.txt")));
+        assert_eq!(contained_path(root, Path::new("a/../../evil.txt")), None);
+        assert_eq!(contained_path(root, Path::new("/etc/passwd")), None);
+    }
+
+    // 精心构造的压缩包中包含 ../ 和绝对路径条目，解压时跳过这些条目，不会写到临时目录之外
+    #[test]
+    fn extract_to_skips_traversal_entries() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("evil.zip");
+        write_zip(&package, &[("../evil.txt", b"E"), ("a/../../evil2.txt", b"E"), ("/abs.txt", b"E"), ("safe.txt", b"S")]);
+        let dest = dir.path().join("out");
+        fs::create_dir(&dest).unwrap();
+        
+        let stats = extract_to(&package, &dest, None, true, |_| true, |_, _, _| false, |_| Ok(())).unwrap();
+        assert_eq!(stats.files, 1);
+        assert_eq!(stats.skipped, 3);
+        let files: Vec<String> = read_tree(dir.path()).into_keys().collect();
+        assert_eq!(files, ["evil.zip", "out/safe.txt"]);
+    }
+
+    #[test]
+    fn update_preserves_source_mtime() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("package");
+        write_files(&package, &[("a.txt", b"A"), ("sub/b.txt", b"B")]);
+        let modified = FileTime::from_unix_time(1_500_000_000, 0);
+        for name in ["a.txt", "sub/b.txt"] {
+            filetime::set_file_mtime(package.join(name), modified).unwrap();
+        }
+        let target = dir.path().join("target");
+        
+        test_util::run(&test_util::config(&package, &target)).0.unwrap();
+        for name in ["a.txt", "sub/b.txt"] {
+            assert_eq!(FileTime::from_last_modification_time(&fs::metadata(target.join(name)).unwrap()), modified, "{}", name);
+        }
+        
+        // 关闭后使用复制时的当前时间
+        let target = dir.path().join("target2");
+        test_util::run(&UpdateConfig { preserve_mtime: false, ..test_util::config(&package, &target) }).0.unwrap();
+        assert_ne!(FileTime::from_last_modification_time(&fs::metadata(target.join("a.txt")).unwrap()), modified);
+    }
+
+    #[test]
+    fn files_differ_compares_size_mtime_and_content() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src.txt");
+        let dst = dir.path().join("dst.txt");
+        fs::write(&src, "same").unwrap();
+        assert!(files_differ(&src, &dst).unwrap());
+        
+        fs::write(&dst, "same").unwrap();
+        copy_mtime(&src, &dst).unwrap();
+        assert!(!files_differ(&src, &dst).unwrap());
+        // 修改时间不同但内容相同
+        filetime::set_file_mtime(&dst, FileTime::from_unix_time(1_000_000_000, 0)).unwrap();
+        assert!(!files_differ(&src, &dst).unwrap());
+        // 大小相同但内容不同
+        fs::write(&dst, "diff").unwrap();
+        assert!(files_differ(&src, &dst).unwrap());
+        fs::write(&dst, "longer").unwrap();
+        assert!(files_differ(&src, &dst).unwrap());
+        // 目标位置是目录
+        fs::remove_file(&dst).unwrap();
+        fs::create_dir(&dst).unwrap();
+        assert!(files_differ(&src, &dst).unwrap());
+    }
+
+    // 更新包中的相对符号链接在目标目录中还原为符号链接，指向更新包之外的链接被跳过
+    #[cfg(unix)]
+    #[test]
+    fn update_copies_relative_symlinks() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        let options = zip::write::FileOptions::default();
+        let mut zip = zip::ZipWriter::new(fs::File::create(&package).unwrap());
+        zip.start_file("lib/libapp.so.1", options).unwrap();
+        zip.write_all(b"ELF").unwrap();
+        zip.add_symlink("lib/libapp.so", "libapp.so.1", options).unwrap();
+        zip.add_symlink("lib/escape", "../../outside", options).unwrap();
+        zip.finish().unwrap();
+        let target = dir.path().join("target");
+        
+        test_util::run(&test_util::config(&package, &target)).0.unwrap();
+        let link = target.join("lib/libapp.so");
+        assert!(link.is_symlink());
+        assert_eq!(fs::read_link(&link).unwrap(), Path::new("libapp.so.1"));
+        assert_eq!(fs::read(&link).unwrap(), b"ELF");
+        assert!(fs::symlink_metadata(target.join("lib/escape")).is_err());
+    }
+
+    #[test]
+    fn inner_path_can_be_directory_or_single_file() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("app/bin/tool.exe", b"T"), ("app/readme.txt", b"R")]);
+        
+        let target = dir.path().join("dir");
+        let config = UpdateConfig { zip_inner_path: "app/bin".to_string(), ..test_util::config(&package, &target) };
+        test_util::run(&config).0.unwrap();
+        assert_eq!(read_tree(&target), entries(&[("tool.exe", "T")]));
+        
+        // 单个文件以原文件名复制到目标目录中
+        let target = dir.path().join("file");
+        let config = UpdateConfig { zip_inner_path: "app/readme.txt".to_string(), ..test_util::config(&package, &target) };
+        assert_eq!(test_util::run(&config).0.unwrap().files_copied, 1);
+        assert_eq!(read_tree(&target), entries(&[("readme.txt", "R")]));
+        
+        let config = UpdateConfig { zip_inner_path: "app/missing".to_string(), ..test_util::config(&package, &target) };
+        let error = test_util::run(&config).0.unwrap_err();
+        assert_eq!(error.to_string(), crate::language::get_dict(config.lang).error_inner_path_not_found("app/missing"));
+    }
+
+    // testdata/encrypted.zip 由 zip -P hunter2 生成：app/secret.txt 使用ZipCrypto加密，app/plain.txt 未加密
+    #[test]
+    fn update_extracts_encrypted_package() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("encrypted.zip");
+        fs::write(&package, include_bytes!("../testdata/encrypted.zip")).unwrap();
+        let dict = crate::language::get_dict(crate::language::Language::English);
+        
+        let target = dir.path().join("target");
+        let config = UpdateConfig { zip_inner_path: "app".to_string(), password: Some("hunter2".to_string()), ..test_util::config(&package, &target) };
+        test_util::run(&config).0.unwrap();
+        assert_eq!(read_tree(&target), entries(&[("plain.txt", "plain"), ("secret.txt", "secret contents")]));
+        
+        // 密码错误或缺少密码时更新失败，不写入目标目录
+        let target = dir.path().join("failed");
+        let config = UpdateConfig { zip_inner_path: "app".to_string(), password: Some("wrong".to_string()), ..test_util::config(&package, &target) };
+        assert_eq!(test_util::run(&config).0.unwrap_err().to_string(), dict.error_password_invalid);
+        let config = UpdateConfig { password: None, ..config };
+        assert_eq!(test_util::run(&config).0.unwrap_err().to_string(), dict.error_password_required);
+        assert!(!target.join("secret.txt").exists());
+    }
+
+    // 以 root 运行时目录权限不起作用，没有写入权限的情况用返回 PermissionDenied 的 create 模拟
+    #[test]
+    fn check_writable_reports_unwritable_directory() {
+        let dict = crate::language::get_dict(crate::language::Language::English);
+        let dir = tempdir().unwrap();
+        let target = dir.path().join("readonly");
+        fs::create_dir(&target).unwrap();
+        check_writable(&target.join("not/created/yet"), dict).unwrap();
+        assert_eq!(fs::read_dir(&target).unwrap().count(), 0);
+        
+        let mut probed = None;
+        let error = check_writable_with(&target.join("sub"), dict, |sentinel| {
+            probed = sentinel.parent().map(Path::to_path_buf);
+            Err(io::Error::from(io::ErrorKind::PermissionDenied))
+        }).unwrap_err();
+        assert_eq!(probed.as_deref(), Some(target.as_path()));
+        assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+        assert!(error.to_string().starts_with(&dict.error_target_not_writable(&target.display().to_string(), "")), "{}", error);
+    }
+    
+    #[test]
+    fn empty_inner_path_fails() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("app/", b""), ("app/empty/", b""), ("other.txt", b"X")]);
+        let target = dir.path().join("target");
+        write_files(&target, &[("keep.txt", b"K")]);
+        
+        let config = UpdateConfig { zip_inner_path: "app".to_string(), ..test_util::config(&package, &target) };
+        let (result, events) = test_util::run(&config);
+        let error = result.unwrap_err();
+        assert_eq!(error.kind(), FailureKind::Extraction);
+        assert_eq!(error.to_string(), crate::language::get_dict(config.lang).error_package_empty("app"));
+        assert!(matches!(events.last(), Some(UpdateEvent::Error(FailureKind::Extraction, _))));
+        assert_eq!(read_tree(&target), entries(&[("keep.txt", "K")]));
+    }
+
+    #[test]
+    fn update_from_plain_directory() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("package");
+        write_files(&package, &[("app/a.txt", b"A"), ("app/sub/b.txt", b"B")]);
+        let target = dir.path().join("target");
+        
+        let config = UpdateConfig { zip_inner_path: "app".to_string(), keep_package: false, ..test_util::config(&package, &target) };
+        assert_eq!(test_util::run(&config).0.unwrap().files_copied, 2);
+        assert_eq!(read_tree(&target), entries(&[("a.txt", "A"), ("sub/b.txt", "B")]));
+        // 更新源是目录时即使没有 --keep-package 也不删除
+        assert_eq!(read_tree(&package), entries(&[("app/a.txt", "A"), ("app/sub/b.txt", "B")]));
+    }
+
+    #[test]
+    fn receipt_lists_installed_tree() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("app-1.2.0.zip");
+        write_zip(&package, &[("update.json", br#"{"version": "1.2.0"}"#), ("hello.txt", b"hello"), ("sub/a.txt", b"A")]);
+        let target = dir.path().join("target");
+        write_files(&target, &[("local.cfg", b"C")]);
+        
+        test_util::run(&UpdateConfig { receipt: true, ..test_util::config(&package, &target) }).0.unwrap();
+        let receipt: serde_json::Value = serde_json::from_slice(&fs::read(target.join(receipt::RECEIPT_NAME)).unwrap()).unwrap();
+        assert_eq!(receipt["package"], "app-1.2.0.zip");
+        assert_eq!(receipt["version"], "1.2.0");
+        assert_eq!(receipt["files"], serde_json::json!([
+            {"path": "hello.txt", "size": 5, "sha256": "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"},
+            {"path": "sub/a.txt", "size": 1, "sha256": "559aead08264d5795d3909718cdd05abd49572e84fe55590eef31a88a08fdffd"},
+        ]));
+    }
+
+    #[test]
+    fn resolve_inner_glob_handles_zero_one_and_many_matches() {
+        let dict = crate::language::get_dict(crate::language::Language::English);
+        let dir = tempdir().unwrap();
+        for name in ["myapp-1.2.0/bin", "tools-a", "tools-b", "nested/deep/app"] {
+            fs::create_dir_all(dir.path().join(name)).unwrap();
+        }
+        fs::write(dir.path().join("readme-1.txt"), "").unwrap();
+        
+        assert_eq!(resolve_inner_glob(dict, dir.path(), "myapp-*").unwrap(), "myapp-1.2.0");
+        assert_eq!(resolve_inner_glob(dict, dir.path(), "**/deep/*").unwrap(), "nested/deep/app");
+        // 文件不算作匹配，* 不跨越目录层级
+        let (kind, error) = resolve_inner_glob(dict, dir.path(), "readme-*").unwrap_err();
+        assert_eq!(kind, FailureKind::Extraction);
+        assert_eq!(error.to_string(), dict.error_inner_path_not_found("readme-*"));
+        assert!(resolve_inner_glob(dict, dir.path(), "*/app").is_err());
+        let (_, error) = resolve_inner_glob(dict, dir.path(), "tools-*").unwrap_err();
+        assert_eq!(error.to_string(), dict.error_inner_path_ambiguous("tools-*", "tools-a, tools-b"));
+    }
+    
+    #[test]
+    fn update_resolves_inner_path_glob() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("myapp-2.0.1/a.txt", b"A"), ("docs/b.txt", b"B")]);
+        let target = dir.path().join("target");
+        
+        let config = UpdateConfig { zip_inner_path: "myapp-*".to_string(), ..test_util::config(&package, &target) };
+        test_util::run(&config).0.unwrap();
+        assert_eq!(read_tree(&target), entries(&[("a.txt", "A")]));
+    }
+
+    // 只有规范路径与正在运行的更新程序相同的文件才需要暂存，其他目录中的同名文件照常覆盖
+    #[test]
+    fn running_exe_matches_canonical_path_only() {
+        let dir = tempdir().unwrap();
+        write_files(dir.path(), &[("updater.exe", b"U"), ("other/updater.exe", b"O")]);
+        let exe = dir.path().join("updater.exe");
+        let config = UpdateConfig::default();
+        let running = running_exe(&config, &exe);
+        
+        assert!(is_running_exe(&exe, running.as_deref()));
+        assert!(is_running_exe(&dir.path().join("other/../updater.exe"), running.as_deref()));
+        assert!(!is_running_exe(&dir.path().join("other/updater.exe"), running.as_deref()));
+        assert!(!is_running_exe(&dir.path().join("missing.exe"), running.as_deref()));
+        // --no-self-stage 时不暂存任何文件
+        let config = UpdateConfig { self_stage: false, ..config };
+        assert!(!is_running_exe(&exe, running_exe(&config, &exe).as_deref()));
+    }
+
+    #[test]
+    fn target_subdir_receives_files() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("a.txt", b"A"), ("sub/b.txt", b"B")]);
+        let target = dir.path().join("target");
+        write_files(&target, &[("a.txt", b"old")]);
+        
+        let config = UpdateConfig { target_subdir: Some("versions/1.2.0".to_string()), ..test_util::config(&package, &target) };
+        test_util::run(&config).0.unwrap();
+        assert_eq!(read_tree(&target), entries(&[("a.txt", "old"), ("versions/1.2.0/a.txt", "A"), ("versions/1.2.0/sub/b.txt", "B")]));
+    }
+
+    // 目标目录中CRC32和大小与压缩包条目相同的文件不解压也不复制
+    #[test]
+    fn skip_unchanged_uses_zip_crc() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("app/same.txt", b"same"), ("app/changed.txt", b"new"), ("app/added.txt", b"add")]);
+        let target = dir.path().join("target");
+        write_files(&target, &[("same.txt", b"same"), ("changed.txt", b"old")]);
+        
+        let targets = vec![(PathBuf::from("app"), target.clone())];
+        let extracted = dir.path().join("extracted");
+        let stats = extract_to(&package, &extracted, None, true, |_| true, |path, crc, size| entry_unchanged(&targets, path, crc, size), |_| Ok(())).unwrap();
+        assert_eq!(stats.unchanged, [PathBuf::from("app/same.txt")]);
+        assert_eq!(read_tree(&extracted).into_keys().collect::<Vec<_>>(), ["app/added.txt", "app/changed.txt"]);
+        
+        let config = UpdateConfig { zip_inner_path: "app".to_string(), skip_unchanged: true, ..test_util::config(&package, &target) };
+        let summary = test_util::run(&config).0.unwrap();
+        assert_eq!(summary.files_copied, 2);
+        assert_eq!(summary.files_skipped, 1);
+        assert_eq!(read_tree(&target), entries(&[("added.txt", "add"), ("changed.txt", "new"), ("same.txt", "same")]));
+    }
+
+    #[test]
+    fn inspect_lists_package_entries() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("app-1.0/", b""), ("app-1.0/bin/tool.exe", b"TOOL"), ("readme.txt", b"R")]);
+        let dict = crate::language::get_dict(crate::language::Language::English);
+        
+        let config = UpdateConfig { zip_inner_path: "app-*".to_string(), target_path: None, ..test_util::config(&package, dir.path()) };
+        let inspection = inspect_package(&config, dict).unwrap();
+        let listed: Vec<(&str, u64, bool, bool)> = inspection.entries.iter().map(|entry| (entry.path.as_str(), entry.size, entry.is_dir, entry.in_inner_path)).collect();
+        assert_eq!(listed, [("app-1.0/", 0, true, true), ("app-1.0/bin/tool.exe", 4, false, true), ("readme.txt", 1, false, false)]);
+        assert_eq!(inspection.inner_paths.len(), 1);
+        assert_eq!(inspection.inner_paths[0].matches, ["app-1.0"]);
+        // 检查模式不修改任何文件
+        assert_eq!(read_tree(dir.path()).into_keys().collect::<Vec<_>>(), ["update.zip"]);
+    }
+
+    #[test]
+    fn flatten_copies_files_to_top_level() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("bin/app.exe", b"A"), ("lib/x64/core.dll", b"C"), ("readme.txt", b"R")]);
+        let target = dir.path().join("target");
+        
+        test_util::run(&UpdateConfig { flatten: true, ..test_util::config(&package, &target) }).0.unwrap();
+        assert_eq!(read_tree(&target), entries(&[("app.exe", "A"), ("core.dll", "C"), ("readme.txt", "R")]));
+    }
+    
+    // 不同子目录中的同名文件展开到同一层时冲突，中止更新且不修改目标目录
+    #[test]
+    fn flatten_rejects_name_collisions() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("x86/core.dll", b"32"), ("x64/core.dll", b"64"), ("readme.txt", b"R")]);
+        let target = dir.path().join("target");
+        write_files(&target, &[("readme.txt", b"old")]);
+        
+        let (result, _) = test_util::run(&UpdateConfig { flatten: true, ..test_util::config(&package, &target) });
+        let error = result.unwrap_err();
+        assert_eq!(error.kind(), FailureKind::Extraction);
+        let [first, second] = [Path::new("x86/core.dll"), Path::new("x64/core.dll")].map(|path| path.display().to_string());
+        let dict = crate::language::get_dict(crate::language::Language::English);
+        assert!(error.to_string() == dict.error_flatten_collision("core.dll", &first, &second)
+            || error.to_string() == dict.error_flatten_collision("core.dll", &second, &first), "{}", error);
+        assert_eq!(read_tree(&target), entries(&[("readme.txt", "old")]));
+    }
+
+    // 指定了压缩包内路径时只解压该路径下的条目（和更新包元数据），进度总数只计算这些条目
+    #[test]
+    fn extracts_only_inner_path_entries() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("app/a.txt", b"A"), ("app/sub/b.txt", b"B"), ("application.txt", b"X"), ("docs/manual.pdf", b"PDF"), (PACKAGE_METADATA_NAME, b"{}")]);
+        
+        let targets = vec![("app/".to_string(), dir.path().join("target"))];
+        let prefixes = extract_prefixes(&targets).unwrap();
+        assert_eq!(prefixes, [PathBuf::from("app")]);
+        let is_wanted = |entry: &Path| entry == Path::new(PACKAGE_METADATA_NAME) || prefixes.iter().any(|prefix| entry.starts_with(prefix));
+        let extracted = dir.path().join("extracted");
+        let mut total = None;
+        extract_to(&package, &extracted, None, true, is_wanted, |_, _, _| false, |progress| {
+            if let Progress::Total(n) = progress {
+                total = Some(n);
+            }
+            Ok(())
+        }).unwrap();
+        assert_eq!(total, Some(3));
+        assert_eq!(read_tree(&extracted).into_keys().collect::<Vec<_>>(), ["app/a.txt", "app/sub/b.txt", PACKAGE_METADATA_NAME]);
+        
+        // 通配符和整个压缩包需要解压全部条目
+        assert_eq!(extract_prefixes(&[("app-*".to_string(), PathBuf::new())]), None);
+        assert_eq!(extract_prefixes(&[(String::new(), PathBuf::new())]), None);
+    }
+
+    #[test]
+    fn audit_csv_records_each_operation() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("a.txt", b"AAA"), ("sub/b.txt", b"B")]);
+        let target = dir.path().join("target");
+        write_files(&target, &[("stale.txt", b"S")]);
+        let audit_path = dir.path().join("audit.csv");
+        
+        let config = UpdateConfig { prune: true, audit_csv: Some(audit_path.display().to_string()), ..test_util::config(&package, &target) };
+        test_util::run(&config).0.unwrap();
+        let mut reader = csv::Reader::from_path(&audit_path).unwrap();
+        assert_eq!(reader.headers().unwrap(), vec!["timestamp", "action", "source", "destination", "bytes", "result"]);
+        let mut rows: Vec<Vec<String>> = reader.records().map(|record| record.unwrap().iter().map(str::to_string).collect()).collect();
+        rows.sort_by(|a, b| a[3].cmp(&b[3]));
+        let summary: Vec<(&str, String, &str, &str)> = rows.iter().map(|row| (row[1].as_str(), row[3].clone(), row[4].as_str(), row[5].as_str())).collect();
+        assert_eq!(summary, [
+            ("copy", target.join("a.txt").display().to_string(), "3", "ok"),
+            ("delete", target.join("stale.txt").display().to_string(), "1", "ok"),
+            ("copy", target.join("sub").join("b.txt").display().to_string(), "1", "ok"),
+        ]);
+        assert!(rows[0][2].ends_with("a.txt"));
+        assert!(humantime::parse_rfc3339(&rows[0][0]).is_ok());
+    }
+
+    // 确认模式下调用方没有回复就丢弃了发送端时按取消处理，目标目录保持不变
+    #[test]
+    fn dropped_confirm_reply_cancels_update() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("a.txt", b"new")]);
+        let target = dir.path().join("target");
+        write_files(&target, &[("a.txt", b"old")]);
+        
+        let config = UpdateConfig { confirm: true, ..test_util::config(&package, &target) };
+        let mut confirmed = false;
+        let result = crate::run_update(&config, |event| confirmed |= matches!(event, UpdateEvent::Confirm { .. }));
+        assert!(confirmed);
+        assert!(matches!(result, Err(UpdateError::Cancelled(_))));
+        assert_eq!(read_tree(&target), entries(&[("a.txt", "old")]));
+    }
+
+    // 模拟写满磁盘的写入端：接收一定字节后返回 StorageFull
+    struct FullDisk(usize);
+    
+    impl Write for FullDisk {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.0 == 0 {
+                return Err(io::Error::new(io::ErrorKind::StorageFull, "No space left on device"));
+            }
+            let written = buf.len().min(self.0);
+            self.0 -= written;
+            Ok(written)
+        }
+        
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    
+    // 解压中磁盘写满时删除解压了一部分的临时目录，返回提示改用 --temp-dir 的本地化错误；其他错误原样返回
+    #[test]
+    fn full_disk_during_extraction_removes_temp_dir() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("a.txt", b"A"), ("b.txt", &[7u8; 4096])]);
+        let temp_path = dir.path().join("temp");
+        let dict = crate::language::get_dict(crate::language::Language::English);
+        
+        // 解压到第二个条目时写入端报告磁盘已满
+        let result = extract_to(&package, &temp_path, None, true, |_| true, |_, _, _| false, |progress| match progress {
+            Progress::Entry(2, ..) => io::copy(&mut &[7u8; 4096][..], &mut FullDisk(1024)).map(|_| ()),
+            _ => Ok(()),
+        });
+        assert!(temp_path.join("a.txt").is_file());
+        let error = temp_space_exhausted(result.unwrap_err(), &temp_path, dict);
+        assert_eq!(error.kind(), io::ErrorKind::StorageFull);
+        assert_eq!(error.to_string(), dict.error_temp_space_exhausted(&long_path::display(&temp_path)));
+        assert!(error.to_string().contains("--temp-dir"));
+        assert!(!temp_path.exists());
+        
+        write_files(&temp_path, &[("a.txt", b"A")]);
+        let error = temp_space_exhausted(io::Error::new(io::ErrorKind::PermissionDenied, "denied"), &temp_path, dict);
+        assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(error.to_string(), "denied");
+        assert!(temp_path.join("a.txt").is_file());
+    }
+
+    // 去重模式下把内容相同的文件映射到两个目标目录，两边的内容都与更新包一致
+    #[test]
+    fn dedup_copies_identical_content_to_each_mapping() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("client/logo.png", b"logo"), ("client/a.txt", b"A"), ("server/logo.png", b"logo"), ("server/b.txt", b"BB")]);
+        let client = dir.path().join("client");
+        let server = dir.path().join("server");
+        
+        let config = UpdateConfig {
+            mappings: vec![("client".to_string(), client.display().to_string()), ("server".to_string(), server.display().to_string())],
+            target_path: None,
+            dedup: true,
+            jobs: 1,
+            ..test_util::config(&package, &client)
+        };
+        let (result, _) = test_util::run(&config);
+        let summary = result.unwrap();
+        assert_eq!(summary.files_copied, 4);
+        assert_eq!(read_tree(&client), entries(&[("a.txt", "A"), ("logo.png", "logo")]));
+        assert_eq!(read_tree(&server), entries(&[("b.txt", "BB"), ("logo.png", "logo")]));
+    }
+
+    // 完整的一次更新按解压、校验、复制、收尾的顺序发送进度，每个进度事件都标记所属的阶段
+    #[test]
+    fn progress_events_are_tagged_by_phase() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        let listing = format!("a.txt  {}\nb.txt  {}\n", manifest::to_hex(&Sha256::digest(b"A")), manifest::to_hex(&Sha256::digest(b"BB")));
+        write_zip(&package, &[("a.txt", b"A"), ("b.txt", b"BB"), (manifest::MANIFEST_NAME, listing.as_bytes())]);
+        let target = dir.path().join("target");
+        
+        let config = UpdateConfig { receipt: true, jobs: 1, ..test_util::config(&package, &target) };
+        let (result, events) = test_util::run(&config);
+        result.unwrap();
+        
+        let progress: Vec<_> = events.iter().filter_map(|event| match event {
+            UpdateEvent::Progress { phase, current, total, .. } => Some((*phase, *current, *total)),
+            _ => None,
+        }).collect();
+        assert_eq!(progress, [
+            (Phase::Extract, 1, 3), (Phase::Extract, 2, 3), (Phase::Extract, 3, 3),
+            (Phase::Verify, 1, 2), (Phase::Verify, 2, 2),
+            (Phase::Copy, 1, 2), (Phase::Copy, 2, 2),
+            (Phase::Finalize, 1, 1),
+        ]);
+        
+        // 阶段进度不回退，每个阶段都以 1.0 结束
+        let phases: Vec<_> = events.iter().filter_map(|event| match event {
+            UpdateEvent::PhaseProgress { phase, fraction } => Some((*phase, *fraction)),
+            _ => None,
+        }).collect();
+        for pair in phases.windows(2) {
+            assert!(pair[0].0 != pair[1].0 || pair[0].1 <= pair[1].1, "{:?}", pair);
+        }
+        for phase in [Phase::Extract, Phase::Verify, Phase::Copy, Phase::Finalize] {
+            assert_eq!(phases.iter().rfind(|(p, _)| *p == phase).map(|(_, fraction)| *fraction), Some(1.0), "{:?}", phase);
+        }
+        let order: Vec<_> = phases.iter().map(|(phase, _)| *phase).fold(Vec::new(), |mut order, phase| {
+            if order.last() != Some(&phase) {
+                order.push(phase);
+            }
+            order
+        });
+        assert_eq!(order, [Phase::Extract, Phase::Verify, Phase::Copy, Phase::Finalize]);
+    }
+
+    // 扩展时间戳（0x5455）和NTFS时间（0x000a）都按UTC读取，其他扩展字段被忽略
+    #[test]
+    fn reads_extended_mtime_from_extra_field() {
+        let mut extra = vec![0xfe, 0xca, 2, 0, 1, 2];
+        extra.extend([0x55, 0x54, 5, 0, 1]);
+        extra.extend(1_622_548_800i32.to_le_bytes());
+        assert_eq!(extended_mtime(&extra), Some(FileTime::from_unix_time(1_622_548_800, 0)));
+        
+        let ticks = (1_622_548_800u64 + 11_644_473_600) * 10_000_000 + 5;
+        let mut ntfs = vec![0x0a, 0, 32, 0, 0, 0, 0, 0, 1, 0, 24, 0];
+        ntfs.extend(ticks.to_le_bytes());
+        ntfs.extend([0; 16]);
+        assert_eq!(extended_mtime(&ntfs), Some(FileTime::from_unix_time(1_622_548_800, 500)));
+        
+        // 没有修改时间的标志位、字段被截断或没有扩展字段时按DOS时间处理
+        assert_eq!(extended_mtime(&[0x55, 0x54, 1, 0, 2]), None);
+        assert_eq!(extended_mtime(&[0x55, 0x54, 5, 0, 1, 0]), None);
+        assert_eq!(extended_mtime(&[]), None);
+    }
+    
+    // 测试数据由Info-ZIP在UTC+8时区打包：DOS时间为本地时间 20:00，扩展时间戳为UTC 12:00
+    #[test]
+    fn skip_newer_uses_extended_timestamp() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        fs::write(&package, include_bytes!("../testdata/timestamp.zip")).unwrap();
+        let packaged = 1_622_548_800;
+        let config = |target: &Path| UpdateConfig { zip_inner_path: "app".to_string(), skip_newer: true, ..test_util::config(&package, target) };
+        
+        // 目标文件比更新包中的新一小时，DOS时间按UTC处理时会被误判为较旧
+        let newer = dir.path().join("newer");
+        write_files(&newer, &[("a.txt", b"patched")]);
+        filetime::set_file_mtime(newer.join("a.txt"), FileTime::from_unix_time(packaged + 3600, 0)).unwrap();
+        let (result, events) = test_util::run(&config(&newer));
+        assert_eq!(result.unwrap().files_skipped, 1);
+        assert_eq!(read_tree(&newer), entries(&[("a.txt", "patched")]));
+        assert!(events.iter().any(|event| matches!(event, UpdateEvent::Warning(_))));
+        
+        let older = dir.path().join("older");
+        write_files(&older, &[("a.txt", b"old")]);
+        filetime::set_file_mtime(older.join("a.txt"), FileTime::from_unix_time(packaged - 3600, 0)).unwrap();
+        test_util::run(&config(&older)).0.unwrap();
+        assert_eq!(read_tree(&older), entries(&[("a.txt", "new")]));
+        let modified = FileTime::from_last_modification_time(&fs::metadata(older.join("a.txt")).unwrap());
+        assert_eq!(modified, FileTime::from_unix_time(packaged, 0));
+    }
+    
+    // 目标文件被人为设置为较新的时间时保留，较旧或只差2秒以内时覆盖
+    #[test]
+    fn skip_newer_keeps_newer_destination() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("kept.txt", b"new"), ("close.txt", b"new"), ("old.txt", b"new")]);
+        let target = dir.path().join("target");
+        write_files(&target, &[("kept.txt", b"patched"), ("close.txt", b"close"), ("old.txt", b"old")]);
+        let packaged = FileTime::from_unix_time(ZipArchive::new(fs::File::open(&package).unwrap()).unwrap().by_index(0).unwrap().last_modified().to_time().unwrap().unix_timestamp(), 0);
+        filetime::set_file_mtime(target.join("kept.txt"), FileTime::from_unix_time(packaged.unix_seconds() + 86_400, 0)).unwrap();
+        filetime::set_file_mtime(target.join("close.txt"), FileTime::from_unix_time(packaged.unix_seconds() + 1, 0)).unwrap();
+        filetime::set_file_mtime(target.join("old.txt"), FileTime::from_unix_time(packaged.unix_seconds() - 86_400, 0)).unwrap();
+        
+        let config = UpdateConfig { skip_newer: true, ..test_util::config(&package, &target) };
+        let summary = test_util::run(&config).0.unwrap();
+        assert_eq!((summary.files_copied, summary.files_skipped), (2, 1));
+        assert_eq!(read_tree(&target), entries(&[("close.txt", "new"), ("kept.txt", "patched"), ("old.txt", "new")]));
+    }
+
+    // tar.gz中的硬链接还原为指向之前解压出的文件的链接，指向更新包之外或未解压的文件时按不安全的条目跳过
+    #[test]
+    fn extract_tar_gz_restores_hard_links() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.tar.gz");
+        let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(fs::File::create(&package).unwrap(), flate2::Compression::default()));
+        let mut header = tar::Header::new_gnu();
+        header.set_mode(0o644);
+        header.set_size(1);
+        tar.append_data(&mut header, "app/a.txt", &b"A"[..]).unwrap();
+        for (name, link) in [("app/same.txt", "app/a.txt"), ("app/evil.txt", "../outside.txt"), ("app/dangling.txt", "app/missing.txt")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(tar::EntryType::Link);
+            header.set_size(0);
+            tar.append_link(&mut header, name, link).unwrap();
+        }
+        tar.into_inner().unwrap().finish().unwrap();
+        fs::write(dir.path().join("outside.txt"), "O").unwrap();
+        let dest = dir.path().join("out");
+        
+        let size = fs::metadata(&package).unwrap().len();
+        let stats = extract_tar_gz(fs::File::open(&package).unwrap(), size, &dest, true, |_| true, |_| Ok(())).unwrap();
+        assert_eq!((stats.files, stats.skipped), (2, 2));
+        assert_eq!(read_tree(&dest), entries(&[("app/a.txt", "A"), ("app/same.txt", "A")]));
+    }
+    
+    // tar.gz只顺序读一遍，可以从不可定位的数据流解压，进度按已读取的字节数递增到1
+    #[test]
+    fn extract_tar_gz_streams_with_byte_progress() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.tar.gz");
+        write_tar_gz(&package, &[("app/", b""), ("app/a.txt", b"A"), ("app/b.txt", b"BB"), ("docs/c.txt", b"C")]);
+        let bytes = fs::read(&package).unwrap();
+        let dest = dir.path().join("out");
+        
+        let mut entries_seen = Vec::new();
+        let mut fractions = Vec::new();
+        let stats = extract_tar_gz(&bytes[..], bytes.len() as u64, &dest, true, |entry| entry.starts_with("app"), |progress| {
+            match progress {
+                Progress::Entry(current, total, file) => entries_seen.push((current, total, file)),
+                Progress::Read(read, size) => fractions.push(read as f32 / size as f32),
+                Progress::Total(_) => panic!("tar.gz不预先统计条目数"),
+            }
+            Ok(())
+        }).unwrap();
+        assert_eq!((stats.files, stats.bytes), (2, 3));
+        assert_eq!(read_tree(&dest), entries(&[("app/a.txt", "A"), ("app/b.txt", "BB")]));
+        assert_eq!(entries_seen, [(1, 0, "app".to_string()), (2, 0, "app/a.txt".to_string()), (3, 0, "app/b.txt".to_string())]);
+        assert!(fractions.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(fractions[0] > 0.0);
+        assert_eq!(fractions.last(), Some(&1.0));
+    }
+    
+    // 指定的格式优先于扩展名：内容为tar.gz、扩展名为 .zip 的更新包按 --format tar.gz 解压，没有扩展名时按魔数识别
+    #[test]
+    fn explicit_format_overrides_extension() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_tar_gz(&package, &[("app/", b""), ("app/a.txt", b"A")]);
+        assert_eq!(detect_format(&package), Some(PackageFormat::TarGz));
+        let target = dir.path().join("target");
+        let config = UpdateConfig { zip_inner_path: "app".to_string(), format: Some(PackageFormat::TarGz), ..test_util::config(&package, &target) };
+        let (result, events) = test_util::run(&config);
+        result.unwrap();
+        assert!(!events.iter().any(|event| matches!(event, UpdateEvent::Warning(_))));
+        assert_eq!(read_tree(&target), entries(&[("a.txt", "A")]));
+        
+        let download = dir.path().join("download.tmp");
+        fs::copy(&package, &download).unwrap();
+        assert_eq!(detect_format(&download), Some(PackageFormat::TarGz));
+        let unknown = dir.path().join("download.part");
+        fs::write(&unknown, "").unwrap();
+        assert_eq!(detect_format(&unknown), None);
+        let by_extension = dir.path().join("empty.tgz");
+        fs::write(&by_extension, "").unwrap();
+        assert_eq!(detect_format(&by_extension), Some(PackageFormat::TarGz));
+    }
+    
+    // 指定的格式与内容不一致时先警告，再按指定的格式检查更新包
+    #[test]
+    fn format_mismatch_warns_and_uses_specified_format() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.tar.gz");
+        write_zip(&package, &[("a.txt", b"A")]);
+        let target = dir.path().join("target");
+        let dict = crate::language::get_dict(crate::language::Language::English);
+        
+        let config = UpdateConfig { format: Some(PackageFormat::TarGz), ..test_util::config(&package, &target) };
+        let (result, events) = test_util::run(&config);
+        assert!(matches!(result, Err(UpdateError::Extraction(_))));
+        let warning = dict.warning_format_mismatch("tar.gz", "zip");
+        assert!(events.iter().any(|event| matches!(event, UpdateEvent::Warning(text) if *text == warning)));
+        assert!(!target.exists());
+        
+        let config = UpdateConfig { format: Some(PackageFormat::Zip), ..config };
+        let (result, events) = test_util::run(&config);
+        result.unwrap();
+        assert!(!events.iter().any(|event| matches!(event, UpdateEvent::Warning(_))));
+        assert_eq!(read_tree(&target), entries(&[("a.txt", "A")]));
+    }
+    
+    // 读取更新包中的单个文件时使用与更新流程相同的格式判断
+    #[test]
+    fn read_package_file_supports_every_format() {
+        let dir = tempdir().unwrap();
+        let zip = dir.path().join("update.zip");
+        write_zip(&zip, &[("update.json", b"{}"), ("app/CHANGELOG.md", b"# zip")]);
+        let tar_gz = dir.path().join("download");
+        write_tar_gz(&tar_gz, &[("./app/", b""), ("./app/CHANGELOG.md", b"# tar"), ("update.json", b"{\"version\": \"1.0.0\"}")]);
+        let folder = dir.path().join("folder");
+        write_files(&folder, &[("app/CHANGELOG.md", b"# dir")]);
+        
+        assert_eq!(read_package_file(&zip, None, None, "app/CHANGELOG.md").unwrap().as_deref(), Some("# zip"));
+        assert_eq!(read_package_file(&tar_gz, None, None, "app/CHANGELOG.md").unwrap().as_deref(), Some("# tar"));
+        assert_eq!(read_package_file(&tar_gz, None, None, "update.json").unwrap().as_deref(), Some("{\"version\": \"1.0.0\"}"));
+        assert_eq!(read_package_file(&folder, None, None, "app/CHANGELOG.md").unwrap().as_deref(), Some("# dir"));
+        assert_eq!(read_package_file(&tar_gz, None, None, "missing.txt").unwrap(), None);
+        assert_eq!(read_package_file(&dir.path().join("missing.zip"), None, None, "update.json").unwrap(), None);
+        // 按指定的格式无法打开更新包时与缺少该文件一样返回None
+        assert_eq!(read_package_file(&tar_gz, Some(PackageFormat::Zip), None, "update.json").unwrap(), None);
+        assert_eq!(read_package_file(&zip, Some(PackageFormat::Dir), None, "update.json").unwrap(), None);
+    }
+
+    // 无法交换目录时在组装新版本之前就返回None，不会在临时目录中执行一遍更新后再直接更新一遍
+    #[test]
+    fn atomic_swap_checks_before_applying() {
+        let dir = tempdir().unwrap();
+        let dict = crate::language::get_dict(crate::language::Language::English);
+        let (sender, _receiver) = mpsc::channel();
+        let sender = EventSender::new(sender);
+        let watchdog = Watchdog::new(None);
+        
+        let missing = dir.path().join("missing").join("target");
+        let rollback = Rollback::new(None).unwrap();
+        let swapped = atomic_swap_update(dict, &sender, &watchdog, &rollback, &missing, true, false, |_| -> io::Result<()> { panic!("不应组装新版本") });
+        assert!(swapped.unwrap().is_none());
+        assert!(!missing.exists());
+        
+        let target = dir.path().join("target");
+        write_files(&target, &[("kept.txt", b"K"), ("a.txt", b"old")]);
+        let swapped = atomic_swap_update(dict, &sender, &watchdog, &rollback, &target, true, false, |staging| fs::write(staging.join("a.txt"), "new"));
+        assert!(swapped.unwrap().is_some());
+        assert_eq!(read_tree(&target), entries(&[("a.txt", "new"), ("kept.txt", "K")]));
+        rollback.commit();
+        assert!(!dir.path().join("target.bak").exists());
+    }
+
+    // 原子替换多个映射时第二个映射失败，第一个映射已交换的目录也恢复为原来的内容
+    #[test]
+    fn atomic_failure_restores_earlier_mappings() {
+        let dir = tempdir().unwrap();
+        let package = dir.path().join("update.zip");
+        write_zip(&package, &[("one/a.txt", b"new"), ("two/z.txt", b"Z")]);
+        let one = dir.path().join("one");
+        let two = dir.path().join("two");
+        write_files(&one, &[("a.txt", b"old"), ("kept.txt", b"K")]);
+        // 目标目录中与更新包文件同名的目录无法被文件覆盖，第二个映射失败
+        write_files(&two, &[("z.txt/inner.txt", b"I")]);
+        
+        let config = UpdateConfig {
+            mappings: vec![("one".to_string(), one.display().to_string()), ("two".to_string(), two.display().to_string())],
+            target_path: None,
+            atomic: true,
+            retries: 0,
+            ..test_util::config(&package, &one)
+        };
+        let (result, _) = test_util::run(&config);
+        assert!(result.is_err());
+        assert_eq!(read_tree(&one), entries(&[("a.txt", "old"), ("kept.txt", "K")]));
+        assert_eq!(read_tree(&two), entries(&[("z.txt/inner.txt", "I")]));
+        assert!(!dir.path().join("one.bak").exists());
+        
+        // 全部成功后才删除旧版本目录
+        fs::remove_dir_all(&two).unwrap();
+        test_util::run(&config).0.unwrap();
+        assert_eq!(read_tree(&one), entries(&[("a.txt", "new"), ("kept.txt", "K")]));
+        assert!(!dir.path().join("one.bak").exists());
+    }
+    
+    // 接收端断开的状态属于每次更新各自的发送端，之前的更新断开不影响之后的更新再次记录日志
+    #[test]
+    fn receiver_gone_is_tracked_per_run() {
+        let (sender, receiver) = mpsc::channel();
+        let first = EventSender::new(sender);
+        drop(receiver);
+        assert!(!emit(&first, UpdateEvent::Complete));
+        assert!(first.receiver_gone.load(Ordering::SeqCst));
+        
+        let (sender, receiver) = mpsc::channel();
+        let second = EventSender::new(sender);
+        assert!(emit(&second, UpdateEvent::Complete));
+        assert!(!second.receiver_gone.load(Ordering::SeqCst));
+        drop(receiver);
+        assert!(!emit(&second, UpdateEvent::Complete));
+        assert!(second.receiver_gone.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,73 @@
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::UpdateEvent;
+use crate::language::LangDict;
+use crate::update::{EventSender, emit};
+
+// 监视线程检查超时的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+// 更新超时监视：超过指定时间没有新的进度时标记为超时，更新流程在下一次报告进度时中止
+pub(crate) struct Watchdog {
+    timeout: Option<Duration>,
+    last_progress: Mutex<Instant>,
+    expired: AtomicBool,
+    // 等待用户操作期间不计时
+    suspended: AtomicBool,
+}
+
+impl Watchdog {
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            timeout,
+            last_progress: Mutex::new(Instant::now()),
+            expired: AtomicBool::new(false),
+            suspended: AtomicBool::new(false),
+        }
+    }
+
+    // 在发送进度之前调用：已超时时返回错误，否则记录本次进度的时间
+    pub fn progress(&self, dict: &LangDict) -> io::Result<()> {
+        if let Some(timeout) = self.timeout {
+            if self.expired() {
+                return Err(io::Error::new(io::ErrorKind::TimedOut, dict.error_timed_out(timeout.as_secs())));
+            }
+            *self.last_progress.lock().unwrap() = Instant::now();
+        }
+        Ok(())
+    }
+
+    // 执行需要等待用户操作的步骤，期间不会超时，结束后重新开始计时
+    pub fn suspend<T>(&self, f: impl FnOnce() -> T) -> T {
+        self.suspended.store(true, Ordering::SeqCst);
+        let result = f();
+        *self.last_progress.lock().unwrap() = Instant::now();
+        self.suspended.store(false, Ordering::SeqCst);
+        result
+    }
+
+    // 是否已经因超时中止
+    pub fn expired(&self) -> bool {
+        self.expired.load(Ordering::SeqCst)
+    }
+
+    // 在单独的线程中运行，直到更新结束（done 为真）或超时；超时时立即提示正在中止
+    pub fn watch(&self, dict: &LangDict, sender: &EventSender, done: &AtomicBool) {
+        let Some(timeout) = self.timeout else {
+            return;
+        };
+        while !done.load(Ordering::SeqCst) {
+            if !self.suspended.load(Ordering::SeqCst) && self.last_progress.lock().unwrap().elapsed() >= timeout {
+                log::error!("超过 {} 秒没有进度，中止更新", timeout.as_secs());
+                self.expired.store(true, Ordering::SeqCst);
+                emit(sender, UpdateEvent::Status(dict.status_timed_out(timeout.as_secs())));
+                return;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}